@@ -0,0 +1,201 @@
+use crate::util::sync::atomic::Ordering;
+use crate::util::sync::epoch::{Guard, Shared};
+
+use super::{balance::Balance, tree_node::TreeNode, BinEntry};
+
+#[derive(Debug)]
+pub struct Avl;
+
+impl Avl {
+    fn height<K, V>(x: Shared<'_, BinEntry<K, V>>) -> u8 {
+        if x.is_null() {
+            0
+        } else {
+            unsafe { TreeNode::get_tree_node(x) }
+                .height
+                .load(Ordering::Relaxed)
+        }
+    }
+
+    fn update_height<K, V>(x: Shared<'_, BinEntry<K, V>>, guard: &Guard) {
+        let x_deref = unsafe { TreeNode::get_tree_node(x) };
+        let left = Self::height(x_deref.left.load(Ordering::Relaxed, guard));
+        let right = Self::height(x_deref.right.load(Ordering::Relaxed, guard));
+        x_deref.height.store(1 + left.max(right), Ordering::Relaxed);
+    }
+
+    fn rebalance<'t, K, V>(
+        root: &mut Shared<'t, BinEntry<K, V>>,
+        x: Shared<'t, BinEntry<K, V>>,
+        guard: &'t Guard,
+    ) -> Shared<'t, BinEntry<K, V>> {
+        Self::update_height(x, guard);
+
+        let x_deref = unsafe { TreeNode::get_tree_node(x) };
+        let left = x_deref.left.load(Ordering::Relaxed, guard);
+        let right = x_deref.right.load(Ordering::Relaxed, guard);
+        let left_height = Self::height(left);
+        let right_height = Self::height(right);
+
+        if left_height > right_height + 1 {
+            let left_deref = unsafe { TreeNode::get_tree_node(left) };
+            if Self::height(left_deref.left.load(Ordering::Relaxed, guard))
+                < Self::height(left_deref.right.load(Ordering::Relaxed, guard))
+            {
+                *root = TreeNode::rotate_left(*root, left, guard);
+                Self::update_height(left, guard);
+            }
+            *root = TreeNode::rotate_right(*root, x, guard);
+        } else if right_height > left_height + 1 {
+            let right_deref = unsafe { TreeNode::get_tree_node(right) };
+            if Self::height(right_deref.right.load(Ordering::Relaxed, guard))
+                < Self::height(right_deref.left.load(Ordering::Relaxed, guard))
+            {
+                *root = TreeNode::rotate_right(*root, right, guard);
+                Self::update_height(right, guard);
+            }
+            *root = TreeNode::rotate_left(*root, x, guard);
+        } else {
+            return x;
+        }
+
+        Self::update_height(x, guard);
+        let top = x_deref.parent.load(Ordering::Relaxed, guard);
+        Self::update_height(top, guard);
+        top
+    }
+
+    fn rebalance_path<'t, K, V>(
+        mut root: Shared<'t, BinEntry<K, V>>,
+        mut x: Shared<'t, BinEntry<K, V>>,
+        guard: &'t Guard,
+    ) -> Shared<'t, BinEntry<K, V>> {
+        while !x.is_null() {
+            let top = Self::rebalance(&mut root, x, guard);
+            x = unsafe { TreeNode::get_tree_node(top) }
+                .parent
+                .load(Ordering::Relaxed, guard);
+        }
+        root
+    }
+}
+
+impl Balance for Avl {
+    fn attach_unlocked<K, V>(
+        _parent: Shared<'_, BinEntry<K, V>>,
+        _x: Shared<'_, BinEntry<K, V>>,
+    ) -> bool {
+        false
+    }
+
+    #[cfg(any(debug_assertions, feature = "validate"))]
+    fn validate<K, V>(x: Shared<'_, BinEntry<K, V>>, guard: &Guard) -> usize {
+        if x.is_null() {
+            return 0;
+        }
+
+        let x_deref = unsafe { TreeNode::get_tree_node(x) };
+        let left_height = Self::validate(x_deref.left.load(Ordering::Relaxed, guard), guard);
+        let right_height = Self::validate(x_deref.right.load(Ordering::Relaxed, guard), guard);
+        assert!(
+            left_height <= right_height + 1 && right_height <= left_height + 1,
+            "unbalanced avl tree node"
+        );
+
+        let height = 1 + left_height.max(right_height);
+        assert_eq!(
+            x_deref.height.load(Ordering::Relaxed) as usize,
+            height,
+            "stale avl height"
+        );
+        height
+    }
+
+    fn balance_insertion<'t, K, V>(
+        root: Shared<'t, BinEntry<K, V>>,
+        x: Shared<'t, BinEntry<K, V>>,
+        guard: &'t Guard,
+    ) -> Shared<'t, BinEntry<K, V>> {
+        let x_deref = unsafe { TreeNode::get_tree_node(x) };
+        x_deref.height.store(1, Ordering::Relaxed);
+        Self::rebalance_path(root, x_deref.parent.load(Ordering::Relaxed, guard), guard)
+    }
+
+    unsafe fn remove<'t, K, V>(
+        mut root: Shared<'t, BinEntry<K, V>>,
+        p: Shared<'t, BinEntry<K, V>>,
+        guard: &'t Guard,
+    ) -> Shared<'t, BinEntry<K, V>> {
+        let p_deref = TreeNode::get_tree_node(p);
+        let p_left = p_deref.left.load(Ordering::Relaxed, guard);
+        let p_right = p_deref.right.load(Ordering::Relaxed, guard);
+        let p_parent = p_deref.parent.load(Ordering::Relaxed, guard);
+
+        let replacement;
+        let fix_from;
+        if !p_left.is_null() && !p_right.is_null() {
+            let mut succ = p_right;
+            let mut succ_deref = TreeNode::get_tree_node(succ);
+            let mut succ_left = succ_deref.left.load(Ordering::Relaxed, guard);
+            while !succ_left.is_null() {
+                succ = succ_left;
+                succ_deref = TreeNode::get_tree_node(succ);
+                succ_left = succ_deref.left.load(Ordering::Relaxed, guard);
+            }
+
+            if succ == p_right {
+                fix_from = succ;
+            } else {
+                let succ_parent = succ_deref.parent.load(Ordering::Relaxed, guard);
+                let succ_right = succ_deref.right.load(Ordering::Relaxed, guard);
+
+                TreeNode::get_tree_node(succ_parent)
+                    .left
+                    .store(succ_right, Ordering::Relaxed);
+                if !succ_right.is_null() {
+                    TreeNode::get_tree_node(succ_right)
+                        .parent
+                        .store(succ_parent, Ordering::Relaxed);
+                }
+
+                succ_deref.right.store(p_right, Ordering::Relaxed);
+                TreeNode::get_tree_node(p_right)
+                    .parent
+                    .store(succ, Ordering::Relaxed);
+                fix_from = succ_parent;
+            }
+
+            succ_deref.left.store(p_left, Ordering::Relaxed);
+            TreeNode::get_tree_node(p_left)
+                .parent
+                .store(succ, Ordering::Relaxed);
+            replacement = succ;
+        } else {
+            replacement = if p_left.is_null() { p_right } else { p_left };
+            fix_from = p_parent;
+        }
+
+        if !replacement.is_null() {
+            TreeNode::get_tree_node(replacement)
+                .parent
+                .store(p_parent, Ordering::Relaxed);
+        }
+
+        if p_parent.is_null() {
+            root = replacement;
+        } else {
+            let p_parent_deref = TreeNode::get_tree_node(p_parent);
+            if p == p_parent_deref.left.load(Ordering::Relaxed, guard) {
+                p_parent_deref.left.store(replacement, Ordering::Relaxed);
+            } else {
+                p_parent_deref.right.store(replacement, Ordering::Relaxed);
+            }
+        }
+
+        p_deref.parent.store(Shared::null(), Ordering::Relaxed);
+        p_deref.left.store(Shared::null(), Ordering::Relaxed);
+        p_deref.right.store(Shared::null(), Ordering::Relaxed);
+
+        Self::rebalance_path(root, fix_from, guard)
+    }
+}
@@ -0,0 +1,255 @@
+use crate::util::sync::atomic::Ordering;
+use crate::util::sync::epoch::{Guard, Shared};
+
+use super::{tree_node::TreeNode, BinEntry};
+
+pub trait Balance {
+    fn attach_unlocked<K, V>(
+        parent: Shared<'_, BinEntry<K, V>>,
+        x: Shared<'_, BinEntry<K, V>>,
+    ) -> bool;
+
+    fn balance_insertion<'t, K, V>(
+        root: Shared<'t, BinEntry<K, V>>,
+        x: Shared<'t, BinEntry<K, V>>,
+        guard: &'t Guard,
+    ) -> Shared<'t, BinEntry<K, V>>;
+
+    /// # Safety
+    ///
+    /// `p` must be a node of the tree under `root` and the caller must hold the root lock.
+    unsafe fn remove<'t, K, V>(
+        root: Shared<'t, BinEntry<K, V>>,
+        p: Shared<'t, BinEntry<K, V>>,
+        guard: &'t Guard,
+    ) -> Shared<'t, BinEntry<K, V>>;
+
+    #[cfg(any(debug_assertions, feature = "validate"))]
+    fn validate<K, V>(x: Shared<'_, BinEntry<K, V>>, guard: &Guard) -> usize;
+}
+
+#[cfg(not(feature = "avl"))]
+pub type TreeBalance = RedBlack;
+
+#[cfg(feature = "avl")]
+pub type TreeBalance = super::avl::Avl;
+
+#[derive(Debug)]
+pub struct RedBlack;
+
+impl Balance for RedBlack {
+    fn attach_unlocked<K, V>(
+        parent: Shared<'_, BinEntry<K, V>>,
+        x: Shared<'_, BinEntry<K, V>>,
+    ) -> bool {
+        if unsafe { TreeNode::get_tree_node(parent) }
+            .red
+            .load(Ordering::Relaxed)
+        {
+            return false;
+        }
+
+        unsafe { TreeNode::get_tree_node(x) }
+            .red
+            .store(true, Ordering::Relaxed);
+        true
+    }
+
+    fn balance_insertion<'t, K, V>(
+        root: Shared<'t, BinEntry<K, V>>,
+        x: Shared<'t, BinEntry<K, V>>,
+        guard: &'t Guard,
+    ) -> Shared<'t, BinEntry<K, V>> {
+        TreeNode::balance_insertion(root, x, guard)
+    }
+
+    #[cfg(any(debug_assertions, feature = "validate"))]
+    fn validate<K, V>(x: Shared<'_, BinEntry<K, V>>, guard: &Guard) -> usize {
+        if x.is_null() {
+            return 1;
+        }
+
+        let x_deref = unsafe { TreeNode::get_tree_node(x) };
+        let left = x_deref.left.load(Ordering::Relaxed, guard);
+        let right = x_deref.right.load(Ordering::Relaxed, guard);
+        let red = x_deref.red.load(Ordering::Relaxed);
+
+        if red {
+            for child in [left, right].iter() {
+                assert!(
+                    child.is_null()
+                        || !unsafe { TreeNode::get_tree_node(*child) }
+                            .red
+                            .load(Ordering::Relaxed),
+                    "red tree node has a red child"
+                );
+            }
+        }
+
+        let left_height = Self::validate(left, guard);
+        let right_height = Self::validate(right, guard);
+        assert_eq!(
+            left_height, right_height,
+            "unequal black height in tree bin"
+        );
+
+        left_height + if red { 0 } else { 1 }
+    }
+
+    unsafe fn remove<'t, K, V>(
+        mut root: Shared<'t, BinEntry<K, V>>,
+        p: Shared<'t, BinEntry<K, V>>,
+        guard: &'t Guard,
+    ) -> Shared<'t, BinEntry<K, V>> {
+        let p_deref = TreeNode::get_tree_node(p);
+
+        let replacement;
+        let p_left = p_deref.left.load(Ordering::Relaxed, guard);
+        let p_right = p_deref.right.load(Ordering::Relaxed, guard);
+
+        if !p_left.is_null() && !p_right.is_null() {
+            let mut succ = p_right;
+            let mut succ_deref = TreeNode::get_tree_node(succ);
+            let mut succ_left = succ_deref.left.load(Ordering::Relaxed, guard);
+
+            while !succ_left.is_null() {
+                succ = succ_left;
+                succ_deref = TreeNode::get_tree_node(succ);
+                succ_left = succ_deref.left.load(Ordering::Relaxed, guard);
+            }
+
+            let color = succ_deref.red.load(Ordering::Relaxed);
+            succ_deref
+                .red
+                .store(p_deref.red.load(Ordering::Relaxed), Ordering::Relaxed);
+
+            p_deref.red.store(color, Ordering::Relaxed);
+
+            let succ_right = succ_deref.right.load(Ordering::Relaxed, guard);
+            let p_parent = p_deref.parent.load(Ordering::Relaxed, guard);
+
+            if succ == p_right {
+                p_deref.parent.store(succ, Ordering::Relaxed);
+                succ_deref.right.store(p, Ordering::Relaxed);
+            } else {
+                let succ_parent = succ_deref.parent.load(Ordering::Relaxed, guard);
+                p_deref.parent.store(succ_parent, Ordering::Relaxed);
+                if !succ_parent.is_null() {
+                    if succ
+                        == TreeNode::get_tree_node(succ_parent)
+                            .left
+                            .load(Ordering::Relaxed, guard)
+                    {
+                        TreeNode::get_tree_node(succ_parent)
+                            .left
+                            .store(p, Ordering::Relaxed);
+                    } else {
+                        TreeNode::get_tree_node(succ_parent)
+                            .right
+                            .store(p, Ordering::Relaxed);
+                    }
+                }
+                succ_deref.right.store(p_right, Ordering::Relaxed);
+
+                if !p_right.is_null() {
+                    TreeNode::get_tree_node(p_right)
+                        .parent
+                        .store(succ, Ordering::Relaxed);
+                }
+            }
+            p_deref.left.store(Shared::null(), Ordering::Relaxed);
+            p_deref.right.store(succ_right, Ordering::Relaxed);
+
+            if !succ_right.is_null() {
+                TreeNode::get_tree_node(succ_right)
+                    .parent
+                    .store(p, Ordering::Relaxed);
+            }
+
+            succ_deref.left.store(p_left, Ordering::Relaxed);
+            if !p_left.is_null() {
+                TreeNode::get_tree_node(p_left)
+                    .parent
+                    .store(succ, Ordering::Relaxed);
+            }
+
+            succ_deref.parent.store(p_parent, Ordering::Relaxed);
+
+            if p_parent.is_null() {
+                root = succ;
+            } else if p
+                == TreeNode::get_tree_node(p_parent)
+                    .left
+                    .load(Ordering::Relaxed, guard)
+            {
+                TreeNode::get_tree_node(p_parent)
+                    .left
+                    .store(succ, Ordering::Relaxed);
+            } else {
+                TreeNode::get_tree_node(p_parent)
+                    .right
+                    .store(succ, Ordering::Relaxed);
+            }
+
+            if !succ_right.is_null() {
+                replacement = succ_right;
+            } else {
+                replacement = p;
+            }
+        } else if !p_left.is_null() {
+            replacement = p_left;
+        } else if !p_right.is_null() {
+            replacement = p_right;
+        } else {
+            replacement = p;
+        }
+
+        if replacement != p {
+            let p_parent = p_deref.parent.load(Ordering::Relaxed, guard);
+            TreeNode::get_tree_node(replacement)
+                .parent
+                .store(p_parent, Ordering::Relaxed);
+
+            if p_parent.is_null() {
+                root = replacement;
+            } else {
+                let p_parent_deref = TreeNode::get_tree_node(p_parent);
+
+                if p == p_parent_deref.left.load(Ordering::Relaxed, guard) {
+                    p_parent_deref.left.store(replacement, Ordering::Relaxed);
+                } else {
+                    p_parent_deref.right.store(replacement, Ordering::Relaxed);
+                }
+            }
+
+            p_deref.parent.store(Shared::null(), Ordering::Relaxed);
+            p_deref.left.store(Shared::null(), Ordering::Relaxed);
+            p_deref.right.store(Shared::null(), Ordering::Relaxed);
+        }
+
+        if !p_deref.red.load(Ordering::Relaxed) {
+            root = TreeNode::balance_deletion(root, replacement, guard);
+        }
+
+        if p == replacement {
+            let p_parent = p_deref.parent.load(Ordering::Relaxed, guard);
+
+            if !p_parent.is_null() {
+                let p_parent_deref = TreeNode::get_tree_node(p_parent);
+
+                if p == p_parent_deref.left.load(Ordering::Relaxed, guard) {
+                    TreeNode::get_tree_node(p_parent)
+                        .left
+                        .store(Shared::null(), Ordering::Relaxed);
+                } else if p == p_parent_deref.right.load(Ordering::Relaxed, guard) {
+                    p_parent_deref
+                        .right
+                        .store(Shared::null(), Ordering::Relaxed);
+                }
+                p_deref.parent.store(Shared::null(), Ordering::Relaxed);
+            }
+        }
+
+        root
+    }
+}
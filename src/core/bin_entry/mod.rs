@@ -2,6 +2,8 @@ use self::{tree_bin::TreeBin, tree_node::TreeNode};
 
 use super::node::Node;
 
+pub mod avl;
+pub mod balance;
 pub mod tree_bin;
 pub mod tree_node;
 
@@ -30,6 +32,14 @@ impl<K, V> BinEntry<K, V> {
         }
     }
 
+    pub fn as_any_node(&self) -> Option<&Node<K, V>> {
+        match *self {
+            BinEntry::Node(ref n) => Some(n),
+            BinEntry::TreeNode(ref n) => Some(&n.node),
+            _ => None,
+        }
+    }
+
     pub fn as_tree_bin(&self) -> Option<&TreeBin<K, V>> {
         if let BinEntry::Tree(ref n) = *self {
             Some(n)
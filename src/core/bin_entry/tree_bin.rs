@@ -1,30 +1,50 @@
-use std::{
-    borrow::Borrow,
-    env::consts::FAMILY,
-    sync::atomic::{AtomicI64, Ordering},
-    thread::{current, park, Thread},
+#![allow(clippy::bool_comparison)]
+
+use std::hint::spin_loop;
+
+#[cfg(feature = "contention-stats")]
+use crate::util::clock;
+use crate::util::sync::epoch::{Atomic, Guard, Owned, Shared};
+use crate::{
+    core::node::Node,
+    util::{
+        contention_policy::{ContentionTracker, YieldStrategy},
+        dir::Dir,
+        equivalent::Equivalent,
+        lock::Mutex,
+        reclaim::{Pending, Retire},
+        state::State,
+        sync::{
+            atomic::{fence, AtomicU64, AtomicUsize, Ordering},
+            thread::{self, current, park, Thread},
+        },
+    },
 };
 
-use crossbeam_epoch::{Atomic, Guard, Owned, Shared};
-
-use crate::util::{dir::Dir, state::State};
+use super::{
+    balance::{Balance, TreeBalance},
+    tree_node::TreeNode,
+    BinEntry,
+};
 
-use super::{tree_node::TreeNode, BinEntry};
+const MAX_READERS: usize = u16::MAX as usize;
 
 #[derive(Debug)]
 pub struct TreeBin<K, V> {
     pub root: Atomic<BinEntry<K, V>>,
     pub first: Atomic<BinEntry<K, V>>,
     pub waiter: Atomic<Thread>,
-    pub lock: parking_lot::Mutex<()>,
-    pub lock_state: AtomicI64,
+    pub lock: Mutex<()>,
+    pub lock_state: AtomicUsize,
+    pub version: AtomicU64,
+    pub pending: Pending,
 }
 
 impl<K, V> TreeBin<K, V>
 where
-    K: Ord,
+    K: Eq,
 {
-    pub fn new(bin: Owned<BinEntry<K, V>>, guard: &Guard) -> Self {
+    pub fn new(bin: Owned<BinEntry<K, V>>, pending: Pending, guard: &Guard) -> Self {
         let mut root = Shared::null();
         let bin = bin.into_shared(guard);
 
@@ -44,18 +64,19 @@ where
                 continue;
             }
 
-            let key = &x_deref.node.key;
             let hash = x_deref.node.hash;
 
             let mut p = root;
             loop {
                 let p_deref = unsafe { TreeNode::get_tree_node(p) };
-                let p_key = &p_deref.node.key;
                 let p_hash = p_deref.node.hash;
 
                 let xp = p;
                 let dir: Dir;
-                p = match p_hash.cmp(&hash).then(p_key.cmp(key)) {
+                p = match p_hash
+                    .cmp(&hash)
+                    .then_with(|| TreeNode::tie_break_order(p, x))
+                {
                     std::cmp::Ordering::Greater => {
                         dir = Dir::Left;
                         &p_deref.left
@@ -78,53 +99,79 @@ where
                             .right
                             .store(x, Ordering::Relaxed),
                     }
-                }
 
-                root = TreeNode::balance_insertion(root, x, guard);
-                break;
+                    root = TreeBalance::balance_insertion(root, x, guard);
+                    break;
+                }
             }
+            x = next;
         }
 
         TreeBin {
             root: Atomic::from(root),
             first: Atomic::from(bin),
             waiter: Atomic::null(),
-            lock: parking_lot::Mutex::new(()),
-            lock_state: AtomicI64::new(State::None as i64),
+            lock: Mutex::new(()),
+            lock_state: AtomicUsize::new(State::None as usize),
+            version: AtomicU64::new(0),
+            pending,
         }
     }
 
-    fn lock_root(&self, guard: &Guard) {
+    pub fn empty(pending: Pending) -> Self {
+        TreeBin {
+            root: Atomic::null(),
+            first: Atomic::null(),
+            waiter: Atomic::null(),
+            lock: Mutex::new(()),
+            lock_state: AtomicUsize::new(State::None as usize),
+            version: AtomicU64::new(0),
+            pending,
+        }
+    }
+
+    fn lock_root(&self, policy: &ContentionTracker, guard: &Guard) {
         if self
             .lock_state
             .compare_exchange(
-                State::None as i64,
-                State::Writer as i64,
+                State::None as usize,
+                State::Writer as usize,
                 Ordering::SeqCst,
                 Ordering::Relaxed,
             )
             .is_err()
         {
-            self.contended_lock(guard);
+            #[cfg(feature = "contention-stats")]
+            let start = clock::now();
+            self.contended_lock(policy, guard);
+            #[cfg(feature = "contention-stats")]
+            policy.record_tree_bin(start);
         }
+        self.version.fetch_add(1, Ordering::Relaxed);
+        fence(Ordering::Release);
     }
 
     fn unlock_root(&self) {
-        self.lock_state.store(State::None as i64, Ordering::Release);
+        self.version.fetch_add(1, Ordering::Release);
+        self.lock_state
+            .store(State::None as usize, Ordering::Release);
     }
 
-    fn contended_lock(&self, guard: &Guard) {
+    fn contended_lock(&self, policy: &ContentionTracker, guard: &Guard) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("tree_bin_contended_lock").entered();
         let mut waiting = false;
-        let mut state: i64;
+        let mut spins = 0;
+        let mut state: usize;
 
         loop {
             state = self.lock_state.load(Ordering::Acquire);
-            if state & !(State::Waiter as i64) == 0 {
+            if state & !(State::Waiter as usize) == 0 {
                 if self
                     .lock_state
                     .compare_exchange(
                         state,
-                        State::Writer as i64,
+                        State::Writer as usize,
                         Ordering::SeqCst,
                         Ordering::Relaxed,
                     )
@@ -133,16 +180,23 @@ where
                     if waiting {
                         let waiter = self.waiter.swap(Shared::null(), Ordering::SeqCst, guard);
 
-                        unsafe { guard.defer_destroy(waiter) };
+                        unsafe { guard.retire(waiter, &self.pending) };
                     }
                     return;
                 }
-            } else if state & State::Writer as i64 == 0 {
+            } else if spins < policy.spin_budget() {
+                spins += 1;
+            } else if policy.strategy() == YieldStrategy::Yield {
+                thread::yield_now();
+            } else if policy.strategy() == YieldStrategy::Spin {
+                spin_loop();
+                continue;
+            } else if state & State::Waiter as usize == 0 {
                 if self
                     .lock_state
                     .compare_exchange(
                         state,
-                        state | State::Waiter as i64,
+                        state | State::Waiter as usize,
                         Ordering::SeqCst,
                         Ordering::Relaxed,
                     )
@@ -156,7 +210,7 @@ where
             } else if waiting {
                 park();
             }
-            std::hint::spin_loop();
+            spin_loop();
         }
     }
 
@@ -167,18 +221,19 @@ where
         guard: &'l Guard,
     ) -> Shared<'l, BinEntry<K, V>>
     where
-        K: Borrow<Q>,
-        Q: ?Sized + Ord,
+        Q: ?Sized + Equivalent<K>,
     {
         let bin_deref = unsafe { bin.deref() }.as_tree_bin().unwrap();
         let mut element = bin_deref.first.load(Ordering::SeqCst, guard);
         while element.is_null() == false {
             let s = bin_deref.lock_state.load(Ordering::SeqCst);
-            if s & (State::Waiter as i64 | State::Writer as i64) == 0 {
+            if s & (State::Waiter as usize | State::Writer as usize) != 0
+                || s >= MAX_READERS * State::Reader as usize
+            {
                 let element_deref = unsafe { TreeNode::get_tree_node(element) };
                 let element_key = &element_deref.node.key;
 
-                if element_deref.node.hash == hash && element_key.borrow() == key {
+                if element_deref.node.hash == hash && key.equivalent(element_key) {
                     return element;
                 }
 
@@ -187,7 +242,7 @@ where
                 .lock_state
                 .compare_exchange(
                     s,
-                    s + State::Reader as i64,
+                    s + State::Reader as usize,
                     Ordering::SeqCst,
                     Ordering::Relaxed,
                 )
@@ -202,8 +257,8 @@ where
 
                 if bin_deref
                     .lock_state
-                    .fetch_add(-(State::Reader as i64), Ordering::SeqCst)
-                    == (State::Reader as i64 | State::Writer as i64)
+                    .fetch_sub(State::Reader as usize, Ordering::SeqCst)
+                    == (State::Reader as usize | State::Waiter as usize)
                 {
                     let waiter = &bin_deref.waiter.load(Ordering::SeqCst, guard);
 
@@ -217,10 +272,173 @@ where
         Shared::null()
     }
 
+    pub fn find_read_mostly<'l, Q>(
+        bin: Shared<'l, BinEntry<K, V>>,
+        hash: u64,
+        key: &Q,
+        guard: &'l Guard,
+    ) -> Shared<'l, BinEntry<K, V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        let bin_deref = unsafe { bin.deref() }.as_tree_bin().unwrap();
+        let version = bin_deref.version.load(Ordering::Acquire);
+
+        if version & 1 == 0 {
+            let root = bin_deref.root.load(Ordering::Relaxed, guard);
+            let p = if root.is_null() {
+                Some(Shared::null())
+            } else {
+                TreeNode::find_tree_node_while(root, hash, key, guard, &|| {
+                    bin_deref.version.load(Ordering::Relaxed) == version
+                })
+            };
+
+            fence(Ordering::Acquire);
+            if let Some(p) = p {
+                if bin_deref.version.load(Ordering::Relaxed) == version {
+                    return p;
+                }
+            }
+        }
+
+        let mut element = bin_deref.first.load(Ordering::SeqCst, guard);
+        while element.is_null() == false {
+            let element_deref = unsafe { TreeNode::get_tree_node(element) };
+            if element_deref.node.hash == hash && key.equivalent(&element_deref.node.key) {
+                return element;
+            }
+            element = element_deref.node.next.load(Ordering::SeqCst, guard);
+        }
+        Shared::null()
+    }
+
+    pub fn put_tree_val<'l>(
+        &'l self,
+        node: Node<K, V>,
+        policy: &ContentionTracker,
+        guard: &'l Guard,
+    ) -> Shared<'l, BinEntry<K, V>> {
+        let hash = node.hash;
+        let x = Owned::new(BinEntry::TreeNode(TreeNode::from_node(
+            node,
+            Atomic::null(),
+        )))
+        .into_shared(guard);
+        let x_deref = unsafe { TreeNode::get_tree_node(x) };
+
+        let mut p = self.root.load(Ordering::SeqCst, guard);
+        if p.is_null() {
+            x_deref.node.next.store(Shared::null(), Ordering::SeqCst);
+            self.first.store(x, Ordering::SeqCst);
+            self.root.store(x, Ordering::SeqCst);
+            return Shared::null();
+        }
+
+        let mut searched = false;
+        loop {
+            let p_deref = unsafe { TreeNode::get_tree_node(p) };
+            let xp = p;
+            let dir: Dir;
+            p = match p_deref.node.hash.cmp(&hash) {
+                std::cmp::Ordering::Greater => {
+                    dir = Dir::Left;
+                    &p_deref.left
+                }
+                std::cmp::Ordering::Less => {
+                    dir = Dir::Right;
+                    &p_deref.right
+                }
+                std::cmp::Ordering::Equal => {
+                    let key = &x_deref.node.key;
+                    if p_deref.node.key == *key {
+                        drop(unsafe { x.into_owned() });
+                        return p;
+                    }
+
+                    if searched == false {
+                        searched = true;
+                        for child in [&p_deref.left, &p_deref.right].iter() {
+                            let q = TreeNode::find_tree_node(
+                                child.load(Ordering::SeqCst, guard),
+                                hash,
+                                key,
+                                guard,
+                            );
+                            if q.is_null() == false {
+                                drop(unsafe { x.into_owned() });
+                                return q;
+                            }
+                        }
+                    }
+
+                    if TreeNode::tie_break_order(p, x) == std::cmp::Ordering::Greater {
+                        dir = Dir::Left;
+                        &p_deref.left
+                    } else {
+                        dir = Dir::Right;
+                        &p_deref.right
+                    }
+                }
+            }
+            .load(Ordering::SeqCst, guard);
+
+            if p.is_null() {
+                let first = self.first.load(Ordering::SeqCst, guard);
+                x_deref.node.next.store(first, Ordering::SeqCst);
+                x_deref.parent.store(xp, Ordering::SeqCst);
+
+                self.first.store(x, Ordering::SeqCst);
+                if first.is_null() == false {
+                    unsafe { TreeNode::get_tree_node(first) }
+                        .prev
+                        .store(x, Ordering::SeqCst);
+                }
+
+                match dir {
+                    Dir::Left => p_deref.left.store(x, Ordering::SeqCst),
+                    Dir::Right => p_deref.right.store(x, Ordering::SeqCst),
+                }
+
+                if TreeBalance::attach_unlocked(xp, x) == false {
+                    self.lock_root(policy, guard);
+                    let root = self.root.load(Ordering::Relaxed, guard);
+                    self.root.store(
+                        TreeBalance::balance_insertion(root, x, guard),
+                        Ordering::Relaxed,
+                    );
+                    self.unlock_root();
+                }
+                return Shared::null();
+            }
+        }
+    }
+
+    fn count_at_most(first: Shared<'_, BinEntry<K, V>>, limit: usize, guard: &Guard) -> bool {
+        let mut count = 0;
+        let mut element = first;
+        while element.is_null() == false {
+            count += 1;
+            if count > limit {
+                return false;
+            }
+            element = unsafe { TreeNode::get_tree_node(element) }
+                .node
+                .next
+                .load(Ordering::SeqCst, guard);
+        }
+        true
+    }
+
+    /// # Safety
+    ///
+    /// `p` must be a tree node of this bin and the caller must hold the bin lock.
     pub unsafe fn remove_tree_node<'l>(
         &'l self,
         p: Shared<'l, BinEntry<K, V>>,
         drop_value: bool,
+        untreeify_threshold: usize,
+        policy: &ContentionTracker,
         guard: &'l Guard,
     ) -> bool {
         let p_deref = TreeNode::get_tree_node(p);
@@ -242,184 +460,124 @@ where
                 .store(prev, Ordering::SeqCst);
         }
 
-        if self.first.load(Ordering::SeqCst, guard).is_null() {
+        if drop_value {
+            guard.retire(
+                p_deref
+                    .node
+                    .value
+                    .swap(Shared::null(), Ordering::SeqCst, guard),
+                &self.pending,
+            );
+        }
+        let untreeify = self.rebalance_after_remove(p, untreeify_threshold, policy, guard);
+        guard.retire(p, &self.pending);
+        untreeify
+    }
+
+    /// # Safety
+    ///
+    /// `p` must have just been unlinked from this bin and must not have been destroyed yet.
+    unsafe fn rebalance_after_remove<'l>(
+        &'l self,
+        p: Shared<'l, BinEntry<K, V>>,
+        untreeify_threshold: usize,
+        policy: &ContentionTracker,
+        guard: &'l Guard,
+    ) -> bool {
+        let first = self.first.load(Ordering::SeqCst, guard);
+        if first.is_null() {
             self.root.store(Shared::null(), Ordering::SeqCst);
             return true;
         }
 
-        let mut root = self.root.load(Ordering::SeqCst, guard);
-
-        if root.is_null()
-            || TreeNode::get_tree_node(root)
-                .right
-                .load(Ordering::SeqCst, guard)
-                .is_null()
-        {
+        let root = self.root.load(Ordering::SeqCst, guard);
+        if root.is_null() || Self::count_at_most(first, untreeify_threshold, guard) {
             return true;
         }
 
-        self.lock_root(guard);
-
-        let replacement;
-        let p_left = p_deref.left.load(Ordering::Relaxed, guard);
-        let p_right = p_deref.right.load(Ordering::Relaxed, guard);
-
-        if p_left.is_null() == false && p_right.is_null() == false {
-            let mut succ = p_right;
-            let mut succ_deref = TreeNode::get_tree_node(succ);
-            let mut succ_left = succ_deref.left.load(Ordering::Relaxed, guard);
-
-            while succ_left.is_null() == false {
-                succ = succ_left;
-                succ_deref = TreeNode::get_tree_node(succ);
-                succ_left = succ_deref.left.load(Ordering::Relaxed, guard);
-            }
-
-            let color = succ_deref.red.load(Ordering::Relaxed);
-            succ_deref
-                .red
-                .store(p_deref.red.load(Ordering::Relaxed), Ordering::Relaxed);
-
-            p_deref.red.store(color, Ordering::Relaxed);
-
-            let succ_right = succ_deref.right.load(Ordering::Relaxed, guard);
-            let p_parent = p_deref.parent.load(Ordering::Relaxed, guard);
-
-            if succ == p_right {
-                p_deref.parent.store(succ, Ordering::Relaxed);
-                succ_deref.right.store(p, Ordering::Relaxed);
-            } else {
-                let succ_parent = succ_deref.parent.load(Ordering::Relaxed, guard);
-                p_deref.parent.store(succ_parent, Ordering::Relaxed);
-                if succ_parent.is_null() == false {
-                    if succ
-                        == TreeNode::get_tree_node(succ_parent)
-                            .left
-                            .load(Ordering::Relaxed, guard)
-                    {
-                        TreeNode::get_tree_node(succ_parent)
-                            .left
-                            .store(p, Ordering::Relaxed);
-                    } else {
-                        TreeNode::get_tree_node(succ_parent)
-                            .right
-                            .store(p, Ordering::Relaxed);
-                    }
-                }
-                succ_deref.right.store(p_right, Ordering::Relaxed);
-
-                if p_right.is_null() == false {
-                    TreeNode::get_tree_node(p_right)
-                        .parent
-                        .store(succ, Ordering::Relaxed);
-                }
-            }
-            p_deref.left.store(Shared::null(), Ordering::Relaxed);
-            p_deref.right.store(succ_right, Ordering::Relaxed);
-
-            if succ_right.is_null() == false {
-                TreeNode::get_tree_node(p_left)
-                    .parent
-                    .store(succ, Ordering::Relaxed);
-            }
+        self.lock_root(policy, guard);
 
-            succ_deref.left.store(p_left, Ordering::Relaxed);
-            if p_left.is_null() == false {
-                TreeNode::get_tree_node(p_left)
-                    .parent
-                    .store(succ, Ordering::Relaxed);
-            }
+        let root = TreeBalance::remove(root, p, guard);
+        self.root.store(root, Ordering::Relaxed);
 
-            succ_deref.parent.store(p_parent, Ordering::Relaxed);
+        self.unlock_root();
 
-            if p_parent.is_null() {
-                root = succ;
-            } else if p
-                == TreeNode::get_tree_node(p_parent)
-                    .left
-                    .load(Ordering::Relaxed, guard)
-            {
-                TreeNode::get_tree_node(p_parent)
-                    .left
-                    .store(succ, Ordering::Relaxed);
-            } else {
-                TreeNode::get_tree_node(p_parent)
-                    .right
-                    .store(succ, Ordering::Relaxed);
-            }
+        false
+    }
+}
 
-            if succ_right.is_null() == false {
-                replacement = succ_right;
-            } else {
-                replacement = p;
-            }
-        } else if p_left.is_null() == false {
-            replacement = p_left;
-        } else if p_right.is_null() == false {
-            replacement = p_right;
-        } else {
-            replacement = p;
+impl<K, V> TreeBin<K, V> {
+    #[cfg(any(debug_assertions, feature = "validate"))]
+    pub fn validate(&self, guard: &Guard) {
+        let mut count = 0;
+        let mut prev = Shared::null();
+        let mut element = self.first.load(Ordering::SeqCst, guard);
+        while element.is_null() == false {
+            let tree_node = unsafe { TreeNode::get_tree_node(element) };
+            assert!(
+                tree_node.prev.load(Ordering::SeqCst, guard) == prev,
+                "tree node prev link does not match its predecessor"
+            );
+            count += 1;
+            prev = element;
+            element = tree_node.node.next.load(Ordering::SeqCst, guard);
         }
 
-        if replacement != p {
-            let p_parent = p_deref.parent.load(Ordering::Relaxed, guard);
-            TreeNode::get_tree_node(replacement)
-                .parent
-                .store(p_parent, Ordering::Relaxed);
-
-            if p_parent.is_null() {
-                root = replacement;
-            } else {
-                let p_parent_deref = TreeNode::get_tree_node(p_parent);
-
-                if p == p_parent_deref.left.load(Ordering::Relaxed, guard) {
-                    p_parent_deref.left.store(replacement, Ordering::Relaxed);
-                } else {
-                    p_parent_deref.right.store(replacement, Ordering::Relaxed);
-                }
-            }
-
-            p_deref.parent.store(Shared::null(), Ordering::Relaxed);
-            p_deref.left.store(Shared::null(), Ordering::Relaxed);
-            p_deref.right.store(Shared::null(), Ordering::Relaxed);
+        let root = self.root.load(Ordering::SeqCst, guard);
+        if root.is_null() {
+            assert_eq!(count, 0, "tree bin has nodes but no root");
+            return;
         }
 
-        let new_root = if p_deref.red.load(Ordering::Relaxed) {
-            root
-        } else {
-            TreeNode::balance_deletion(root, replacement, guard)
-        };
-
-        self.root.store(new_root, Ordering::Relaxed);
-
-        if p == replacement {
-            let p_parent = p_deref.parent.load(Ordering::Relaxed, guard);
+        assert_eq!(
+            Self::validate_links(root, Shared::null(), guard),
+            count,
+            "tree and list disagree on the number of nodes"
+        );
 
-            if p_parent.is_null() == false {
-                let p_parent_deref = TreeNode::get_tree_node(p_parent);
+        #[cfg(not(feature = "avl"))]
+        assert!(
+            unsafe { TreeNode::get_tree_node(root) }
+                .red
+                .load(Ordering::Relaxed)
+                == false,
+            "red root in tree bin"
+        );
+        TreeBalance::validate(root, guard);
+    }
 
-                if p == p_parent_deref.left.load(Ordering::Relaxed, guard) {
-                    TreeNode::get_tree_node(p_parent)
-                        .left
-                        .store(Shared::null(), Ordering::Relaxed);
-                } else if p == p_parent_deref.right.load(Ordering::Relaxed, guard) {
-                    p_parent_deref
-                        .right
-                        .store(Shared::null(), Ordering::Relaxed);
-                }
-                p_deref.parent.store(Shared::null(), Ordering::Relaxed);
-            }
+    #[cfg(any(debug_assertions, feature = "validate"))]
+    fn validate_links(
+        x: Shared<'_, BinEntry<K, V>>,
+        parent: Shared<'_, BinEntry<K, V>>,
+        guard: &Guard,
+    ) -> usize {
+        if x.is_null() {
+            return 0;
         }
 
-        self.unlock_root();
+        let x_deref = unsafe { TreeNode::get_tree_node(x) };
+        assert!(
+            x_deref.parent.load(Ordering::Relaxed, guard) == parent,
+            "tree node parent link does not match its parent"
+        );
+
+        let left = x_deref.left.load(Ordering::Relaxed, guard);
+        if left.is_null() == false {
+            assert!(
+                unsafe { TreeNode::get_tree_node(left) }.node.hash <= x_deref.node.hash,
+                "left child hash is greater than its parent"
+            );
+        }
 
-        unsafe {
-            if drop_value {
-                guard.defer_destroy(p_deref.node.value.load(Ordering::Relaxed, guard));
-            }
-            guard.defer_destroy(p);
+        let right = x_deref.right.load(Ordering::Relaxed, guard);
+        if right.is_null() == false {
+            assert!(
+                unsafe { TreeNode::get_tree_node(right) }.node.hash >= x_deref.node.hash,
+                "right child hash is less than its parent"
+            );
         }
 
-        false
+        1 + Self::validate_links(left, x, guard) + Self::validate_links(right, x, guard)
     }
 }
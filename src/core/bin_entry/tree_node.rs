@@ -1,12 +1,14 @@
-use std::{
-    borrow::Borrow,
-    sync::atomic::{AtomicBool, Ordering},
+#![allow(clippy::bool_comparison)]
+
+use crate::util::sync::epoch::{Atomic, Guard, Shared};
+use crate::{
+    core::node::Node,
+    util::{
+        equivalent::Equivalent,
+        sync::atomic::{AtomicBool, AtomicU8, Ordering},
+    },
 };
 
-use crossbeam_epoch::{Atomic, Guard, Shared};
-
-use crate::core::node::Node;
-
 use super::BinEntry;
 
 #[derive(Debug)]
@@ -17,6 +19,7 @@ pub struct TreeNode<K, V> {
     pub right: Atomic<BinEntry<K, V>>,
     pub prev: Atomic<BinEntry<K, V>>,
     pub red: AtomicBool,
+    pub height: AtomicU8,
 }
 
 impl<K, V> TreeNode<K, V> {
@@ -27,13 +30,18 @@ impl<K, V> TreeNode<K, V> {
         next: Atomic<BinEntry<K, V>>,
         parent: Atomic<BinEntry<K, V>>,
     ) -> Self {
+        Self::from_node(Node::new(hash, key, value, next), parent)
+    }
+
+    pub fn from_node(node: Node<K, V>, parent: Atomic<BinEntry<K, V>>) -> Self {
         TreeNode {
-            node: Node::new(hash, key, value, next),
+            node,
             parent,
             left: Atomic::null(),
             right: Atomic::null(),
             prev: Atomic::null(),
             red: AtomicBool::new(false),
+            height: AtomicU8::new(1),
         }
     }
 
@@ -44,11 +52,28 @@ impl<K, V> TreeNode<K, V> {
         guard: &'t Guard,
     ) -> Shared<'t, BinEntry<K, V>>
     where
-        K: Borrow<Q>,
-        Q: ?Sized + Ord,
+        Q: ?Sized + Equivalent<K>,
+    {
+        Self::find_tree_node_while(from, hash, key, guard, &|| true).unwrap()
+    }
+
+    pub fn find_tree_node_while<'t, Q, F>(
+        from: Shared<'t, BinEntry<K, V>>,
+        hash: u64,
+        key: &Q,
+        guard: &'t Guard,
+        valid: &F,
+    ) -> Option<Shared<'t, BinEntry<K, V>>>
+    where
+        Q: ?Sized + Equivalent<K>,
+        F: ?Sized + Fn() -> bool,
     {
         let mut p = from;
         while p.is_null() == false {
+            if valid() == false {
+                return None;
+            }
+
             let p_deref = unsafe { Self::get_tree_node(p) };
             let p_hash = p_deref.node.hash;
 
@@ -65,8 +90,8 @@ impl<K, V> TreeNode<K, V> {
             }
 
             let p_key = &p_deref.node.key;
-            if p_key.borrow() == key {
-                return p;
+            if key.equivalent(p_key) {
+                return Some(p);
             }
 
             let p_left = p_deref.left.load(Ordering::SeqCst, guard);
@@ -77,13 +102,12 @@ impl<K, V> TreeNode<K, V> {
                 continue;
             }
 
-            p = match p_key.borrow().cmp(key) {
-                std::cmp::Ordering::Greater => p_left,
-                std::cmp::Ordering::Less => p_right,
-                _ => unreachable!(),
+            match Self::find_tree_node_while(p_right, hash, key, guard, valid) {
+                Some(q) if q.is_null() => p = p_left,
+                found => return found,
             }
         }
-        Shared::null()
+        Some(Shared::null())
     }
 
     pub fn balance_insertion<'t>(
@@ -170,7 +194,7 @@ impl<K, V> TreeNode<K, V> {
                     }
                 }
             } else if x_parent_parent_left.is_null() == false
-                && get_red(x_parent_parent).load(Ordering::Relaxed)
+                && get_red(x_parent_parent_left).load(Ordering::Relaxed)
             {
                 get_red(x_parent_parent_left).store(false, Ordering::Relaxed);
                 get_red(x_parent).store(false, Ordering::Relaxed);
@@ -206,7 +230,7 @@ impl<K, V> TreeNode<K, V> {
         }
     }
 
-    fn rotate_left<'l>(
+    pub fn rotate_left<'l>(
         mut root: Shared<'l, BinEntry<K, V>>,
         p: Shared<'l, BinEntry<K, V>>,
         guard: &'l Guard,
@@ -254,7 +278,7 @@ impl<K, V> TreeNode<K, V> {
         root
     }
 
-    fn rotate_right<'l>(
+    pub fn rotate_right<'l>(
         mut root: Shared<'l, BinEntry<K, V>>,
         p: Shared<'l, BinEntry<K, V>>,
         guard: &'l Guard,
@@ -304,11 +328,195 @@ impl<K, V> TreeNode<K, V> {
     pub fn balance_deletion<'l>(
         mut root: Shared<'l, BinEntry<K, V>>,
         mut x: Shared<'l, BinEntry<K, V>>,
-        guard: &Guard,
+        guard: &'l Guard,
     ) -> Shared<'l, BinEntry<K, V>> {
-        todo!()
+        #[inline]
+        fn get_red<'l, K, V>(x: Shared<'l, BinEntry<K, V>>) -> &'l AtomicBool {
+            &unsafe { TreeNode::get_tree_node(x) }.red
+        }
+
+        #[inline]
+        fn is_red<K, V>(x: Shared<'_, BinEntry<K, V>>) -> bool {
+            x.is_null() == false && get_red(x).load(Ordering::Relaxed)
+        }
+
+        let mut x_parent: Shared<'_, BinEntry<K, V>>;
+        let mut x_parent_left: Shared<'_, BinEntry<K, V>>;
+        let mut x_parent_right: Shared<'_, BinEntry<K, V>>;
+
+        loop {
+            if x.is_null() || x == root {
+                return root;
+            }
+
+            x_parent = unsafe { Self::get_tree_node(x) }
+                .parent
+                .load(Ordering::Relaxed, guard);
+
+            if x_parent.is_null() {
+                get_red(x).store(false, Ordering::Relaxed);
+                return x;
+            }
+
+            if get_red(x).load(Ordering::Relaxed) {
+                get_red(x).store(false, Ordering::Relaxed);
+                return root;
+            }
+
+            x_parent_left = unsafe { Self::get_tree_node(x_parent) }
+                .left
+                .load(Ordering::Relaxed, guard);
+
+            if x_parent_left == x {
+                x_parent_right = unsafe { Self::get_tree_node(x_parent) }
+                    .right
+                    .load(Ordering::Relaxed, guard);
+
+                if is_red(x_parent_right) {
+                    get_red(x_parent_right).store(false, Ordering::Relaxed);
+                    get_red(x_parent).store(true, Ordering::Relaxed);
+                    root = Self::rotate_left(root, x_parent, guard);
+                    x_parent = unsafe { Self::get_tree_node(x) }
+                        .parent
+                        .load(Ordering::Relaxed, guard);
+                    x_parent_right = if x_parent.is_null() {
+                        Shared::null()
+                    } else {
+                        unsafe { Self::get_tree_node(x_parent) }
+                            .right
+                            .load(Ordering::Relaxed, guard)
+                    };
+                }
+
+                if x_parent_right.is_null() {
+                    x = x_parent;
+                    continue;
+                }
+
+                let sibling = unsafe { Self::get_tree_node(x_parent_right) };
+                let sibling_left = sibling.left.load(Ordering::Relaxed, guard);
+                let sibling_right = sibling.right.load(Ordering::Relaxed, guard);
+
+                if is_red(sibling_left) == false && is_red(sibling_right) == false {
+                    sibling.red.store(true, Ordering::Relaxed);
+                    x = x_parent;
+                    continue;
+                }
+
+                if is_red(sibling_right) == false {
+                    if sibling_left.is_null() == false {
+                        get_red(sibling_left).store(false, Ordering::Relaxed);
+                    }
+                    sibling.red.store(true, Ordering::Relaxed);
+                    root = Self::rotate_right(root, x_parent_right, guard);
+                    x_parent = unsafe { Self::get_tree_node(x) }
+                        .parent
+                        .load(Ordering::Relaxed, guard);
+                    x_parent_right = if x_parent.is_null() {
+                        Shared::null()
+                    } else {
+                        unsafe { Self::get_tree_node(x_parent) }
+                            .right
+                            .load(Ordering::Relaxed, guard)
+                    };
+                }
+
+                if x_parent_right.is_null() == false {
+                    get_red(x_parent_right).store(is_red(x_parent), Ordering::Relaxed);
+                    let sibling_right = unsafe { Self::get_tree_node(x_parent_right) }
+                        .right
+                        .load(Ordering::Relaxed, guard);
+                    if sibling_right.is_null() == false {
+                        get_red(sibling_right).store(false, Ordering::Relaxed);
+                    }
+                }
+
+                if x_parent.is_null() == false {
+                    get_red(x_parent).store(false, Ordering::Relaxed);
+                    root = Self::rotate_left(root, x_parent, guard);
+                }
+
+                x = root;
+            } else {
+                if is_red(x_parent_left) {
+                    get_red(x_parent_left).store(false, Ordering::Relaxed);
+                    get_red(x_parent).store(true, Ordering::Relaxed);
+                    root = Self::rotate_right(root, x_parent, guard);
+                    x_parent = unsafe { Self::get_tree_node(x) }
+                        .parent
+                        .load(Ordering::Relaxed, guard);
+                    x_parent_left = if x_parent.is_null() {
+                        Shared::null()
+                    } else {
+                        unsafe { Self::get_tree_node(x_parent) }
+                            .left
+                            .load(Ordering::Relaxed, guard)
+                    };
+                }
+
+                if x_parent_left.is_null() {
+                    x = x_parent;
+                    continue;
+                }
+
+                let sibling = unsafe { Self::get_tree_node(x_parent_left) };
+                let sibling_left = sibling.left.load(Ordering::Relaxed, guard);
+                let sibling_right = sibling.right.load(Ordering::Relaxed, guard);
+
+                if is_red(sibling_left) == false && is_red(sibling_right) == false {
+                    sibling.red.store(true, Ordering::Relaxed);
+                    x = x_parent;
+                    continue;
+                }
+
+                if is_red(sibling_left) == false {
+                    if sibling_right.is_null() == false {
+                        get_red(sibling_right).store(false, Ordering::Relaxed);
+                    }
+                    sibling.red.store(true, Ordering::Relaxed);
+                    root = Self::rotate_left(root, x_parent_left, guard);
+                    x_parent = unsafe { Self::get_tree_node(x) }
+                        .parent
+                        .load(Ordering::Relaxed, guard);
+                    x_parent_left = if x_parent.is_null() {
+                        Shared::null()
+                    } else {
+                        unsafe { Self::get_tree_node(x_parent) }
+                            .left
+                            .load(Ordering::Relaxed, guard)
+                    };
+                }
+
+                if x_parent_left.is_null() == false {
+                    get_red(x_parent_left).store(is_red(x_parent), Ordering::Relaxed);
+                    let sibling_left = unsafe { Self::get_tree_node(x_parent_left) }
+                        .left
+                        .load(Ordering::Relaxed, guard);
+                    if sibling_left.is_null() == false {
+                        get_red(sibling_left).store(false, Ordering::Relaxed);
+                    }
+                }
+
+                if x_parent.is_null() == false {
+                    get_red(x_parent).store(false, Ordering::Relaxed);
+                    root = Self::rotate_right(root, x_parent, guard);
+                }
+
+                x = root;
+            }
+        }
+    }
+
+    pub fn tie_break_order(
+        a: Shared<'_, BinEntry<K, V>>,
+        b: Shared<'_, BinEntry<K, V>>,
+    ) -> std::cmp::Ordering {
+        (a.as_raw() as usize).cmp(&(b.as_raw() as usize))
     }
 
+    /// # Safety
+    ///
+    /// `bin` must be a non-null pointer to a `BinEntry::TreeNode` protected by a guard.
     pub unsafe fn get_tree_node(bin: Shared<'_, BinEntry<K, V>>) -> &'_ TreeNode<K, V> {
         bin.deref().as_tree_node().unwrap()
     }
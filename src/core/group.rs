@@ -0,0 +1,108 @@
+use crate::util::sync::atomic::{AtomicU64, Ordering};
+
+pub const EMPTY: u8 = 0xff;
+pub const DELETED: u8 = 0x80;
+
+const HI: u64 = 0x8080_8080_8080_8080;
+
+pub fn tag(hash: u64) -> u8 {
+    (hash >> 57) as u8
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Group([u64; 2]);
+
+impl Group {
+    pub const WIDTH: usize = 16;
+    pub const WORDS: usize = 2;
+
+    pub fn load(words: &[AtomicU64]) -> Self {
+        Group([
+            words[0].load(Ordering::Acquire),
+            words[1].load(Ordering::Acquire),
+        ])
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    pub fn match_byte(self, byte: u8) -> BitMask {
+        use std::arch::x86_64::{_mm_cmpeq_epi8, _mm_movemask_epi8, _mm_set1_epi8, _mm_set_epi64x};
+
+        unsafe {
+            let ctrl = _mm_set_epi64x(self.0[1] as i64, self.0[0] as i64);
+            let matches = _mm_cmpeq_epi8(ctrl, _mm_set1_epi8(byte as i8));
+            BitMask(_mm_movemask_epi8(matches) as u16)
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    pub fn match_byte(self, byte: u8) -> BitMask {
+        let compress = |word: u64| {
+            let cmp = word ^ u64::from_ne_bytes([byte; 8]);
+            let zero = !(((cmp & !HI) + !HI) | cmp) & HI;
+            movemask(zero)
+        };
+        BitMask(compress(self.0[0]) | compress(self.0[1]) << 8)
+    }
+
+    pub fn match_tag(self, tag: u8) -> BitMask {
+        self.match_byte(tag)
+    }
+
+    pub fn match_empty(self) -> BitMask {
+        self.match_byte(EMPTY)
+    }
+
+    pub fn match_free(self) -> BitMask {
+        BitMask(movemask(self.0[0] & HI) | movemask(self.0[1] & HI) << 8)
+    }
+}
+
+fn movemask(high_bits: u64) -> u16 {
+    ((high_bits >> 7).wrapping_mul(0x0102_0408_1020_4080) >> 56) as u16
+}
+
+pub fn set_byte(words: &[AtomicU64], index: usize, byte: u8) -> u8 {
+    let word = &words[index / 8];
+    let shift = (index % 8) * 8;
+    let previous = word
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+            Some(current & !(0xff << shift) | (byte as u64) << shift)
+        })
+        .unwrap_or_else(|current| current);
+    (previous >> shift) as u8
+}
+
+pub fn claim_byte(words: &[AtomicU64], index: usize, byte: u8) -> Option<u8> {
+    let word = &words[index / 8];
+    let shift = (index % 8) * 8;
+    word.fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+        if (current >> shift) as u8 & DELETED == 0 {
+            return None;
+        }
+        Some(current & !(0xff << shift) | (byte as u64) << shift)
+    })
+    .ok()
+    .map(|previous| (previous >> shift) as u8)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitMask(u16);
+
+impl BitMask {
+    pub fn any(self) -> bool {
+        self.0 != 0
+    }
+}
+
+impl Iterator for BitMask {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(index)
+    }
+}
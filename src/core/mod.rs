@@ -1,3 +1,4 @@
 pub mod bin_entry;
+pub mod group;
 pub mod node;
 pub mod table;
@@ -1,5 +1,10 @@
-use crossbeam_epoch::Atomic;
-use parking_lot::Mutex;
+use crate::util::sync::epoch::{unprotected, Atomic, Guard, Owned, Shared};
+use crate::util::{
+    clock,
+    expiry::Expiry,
+    lock::Mutex,
+    sync::atomic::{AtomicU64, AtomicU8, Ordering},
+};
 
 use super::bin_entry::BinEntry;
 
@@ -9,10 +14,66 @@ pub struct Node<K, V> {
     pub key: K,
     pub value: Atomic<V>,
     pub next: Atomic<BinEntry<K, V>>,
+    pub forward: Atomic<BinEntry<K, V>>,
     pub lock: Mutex<()>,
+    pub ext: LazyExt,
+}
+
+#[derive(Debug)]
+pub struct NodeExt {
+    pub touched: AtomicU64,
+    pub written: AtomicU64,
+    pub frequency: AtomicU64,
+    pub weight: AtomicU64,
+    pub lease: AtomicU64,
+    pub flags: AtomicU8,
+    pub meta: AtomicU64,
+    pub generation: AtomicU64,
+}
+
+impl NodeExt {
+    pub fn new() -> Self {
+        NodeExt {
+            touched: AtomicU64::new(0),
+            written: AtomicU64::new(0),
+            frequency: AtomicU64::new(0),
+            weight: AtomicU64::new(0),
+            lease: AtomicU64::new(0),
+            flags: AtomicU8::new(0),
+            meta: AtomicU64::new(0),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    pub fn inherit(&self, other: &NodeExt) {
+        self.touched
+            .store(other.touched.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.written
+            .store(other.written.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.frequency
+            .store(other.frequency.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.weight
+            .store(other.weight.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.lease
+            .store(other.lease.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.flags
+            .store(other.flags.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.meta
+            .store(other.meta.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.generation
+            .store(other.generation.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+}
+
+impl Default for NodeExt {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<K, V> Node<K, V> {
+    pub const PINNED: u8 = 1;
+
     pub fn new<AV>(hash: u64, key: K, value: AV, next: Atomic<BinEntry<K, V>>) -> Self
     where
         AV: Into<Atomic<V>>,
@@ -22,7 +83,139 @@ impl<K, V> Node<K, V> {
             key,
             value: value.into(),
             next,
+            forward: Atomic::null(),
             lock: Mutex::new(()),
+            ext: LazyExt::new(),
+        }
+    }
+
+    pub fn ext(&self) -> Option<&NodeExt> {
+        self.ext.get()
+    }
+
+    pub fn ext_or_init(&self) -> &NodeExt {
+        self.ext.get_or_init()
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.ext().map_or(0, |ext| ext.flags.load(Ordering::SeqCst)) & Self::PINNED != 0
+    }
+
+    pub fn is_expired(&self, expiry: &Expiry) -> bool {
+        if !expiry.is_enabled() {
+            return false;
+        }
+        self.ext().is_some_and(|ext| {
+            expiry.has_expired(
+                ext.written.load(Ordering::Relaxed),
+                ext.touched.load(Ordering::Relaxed),
+                clock::now(),
+            )
+        })
+    }
+
+    pub fn inherit(&self, other: &Node<K, V>) {
+        if let Some(ext) = other.ext() {
+            self.ext_or_init().inherit(ext);
+        }
+    }
+
+    pub fn load_value<'g>(&'g self, guard: &'g Guard) -> Shared<'g, V> {
+        let mut node = self;
+        loop {
+            let value = node.value.load(Ordering::SeqCst, guard);
+            if !value.is_null() {
+                return value;
+            }
+
+            let forward = node.forward.load(Ordering::SeqCst, guard);
+            match unsafe { forward.as_ref() } {
+                Some(forward) => node = forward.as_any_node().unwrap(),
+                None => return value,
+            }
+        }
+    }
+
+    pub fn forward_to<'g>(&self, copy: Shared<'g, BinEntry<K, V>>, guard: &'g Guard) {
+        let copy_node = unsafe { copy.deref() }.as_any_node().unwrap();
+        let mut value = self.value.load(Ordering::SeqCst, guard);
+        copy_node.value.store(value, Ordering::SeqCst);
+        self.forward.store(copy, Ordering::SeqCst);
+        while let Err(error) =
+            self.value
+                .compare_and_set(value, Shared::null(), Ordering::SeqCst, guard)
+        {
+            value = error.current;
+            copy_node.value.store(value, Ordering::SeqCst);
+        }
+        copy_node.inherit(self);
+    }
+
+    pub fn forwarded<'g>(&self, guard: &'g Guard) -> Option<&'g Node<K, V>> {
+        if !self.value.load(Ordering::SeqCst, guard).is_null() {
+            return None;
+        }
+        let forward = self.forward.load(Ordering::SeqCst, guard);
+        unsafe { forward.as_ref() }.map(|forward| forward.as_any_node().unwrap())
+    }
+
+    pub fn touch(&self, now: u64, guard: &Guard) {
+        self.ext_or_init().touched.fetch_max(now, Ordering::SeqCst);
+        let mut node = self.forwarded(guard);
+        while let Some(forward) = node {
+            forward
+                .ext_or_init()
+                .touched
+                .fetch_max(now, Ordering::SeqCst);
+            node = forward.forwarded(guard);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LazyExt {
+    ext: Atomic<NodeExt>,
+}
+
+impl LazyExt {
+    pub fn new() -> Self {
+        LazyExt {
+            ext: Atomic::null(),
+        }
+    }
+
+    pub fn get(&self) -> Option<&NodeExt> {
+        unsafe { self.ext.load(Ordering::SeqCst, unprotected()).as_ref() }
+    }
+
+    pub fn get_or_init(&self) -> &NodeExt {
+        if let Some(ext) = self.get() {
+            return ext;
+        }
+        match self.ext.compare_and_set(
+            Shared::null(),
+            Owned::new(NodeExt::new()),
+            Ordering::SeqCst,
+            unsafe { unprotected() },
+        ) {
+            Ok(ext) => unsafe { ext.deref() },
+            Err(error) => unsafe { error.current.deref() },
+        }
+    }
+}
+
+impl Default for LazyExt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for LazyExt {
+    fn drop(&mut self) {
+        let guard = unsafe { unprotected() };
+        let ext = self.ext.swap(Shared::null(), Ordering::SeqCst, guard);
+        if !ext.is_null() {
+            drop(unsafe { ext.into_owned() });
         }
     }
 }
@@ -0,0 +1,546 @@
+use crossbeam_utils::CachePadded;
+
+use crate::util::sync::epoch::{unprotected, Atomic, Guard, Owned, Shared};
+use crate::util::{
+    contention_policy::ContentionTracker,
+    equivalent::Equivalent,
+    lock::MutexGuard,
+    prefetch::prefetch,
+    reclaim::{Pending, Retire},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+use super::{
+    bin_entry::{tree_bin::TreeBin, tree_node::TreeNode, BinEntry},
+    node::Node,
+};
+
+const PROBE_SAMPLE: u64 = 32;
+#[cfg(feature = "tracing")]
+const LONG_SCAN_PROBES: u64 = 16;
+
+pub type LockedBin<'g, K, V> = (
+    MutexGuard<'g, ()>,
+    Shared<'g, BinEntry<K, V>>,
+    Shared<'g, BinEntry<K, V>>,
+    bool,
+);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TableStats {
+    pub capacity: usize,
+    pub len: usize,
+    pub chain_lengths: Vec<usize>,
+    pub tree_bins: usize,
+    pub moved_bins: usize,
+}
+
+#[derive(Debug)]
+pub struct Table<K, V> {
+    pub bins: Box<[Atomic<BinEntry<K, V>>]>,
+    pub probes: Option<Box<[AtomicU64]>>,
+    pub moved: Atomic<BinEntry<K, V>>,
+    pub pending: Pending,
+    pub next_table: CachePadded<Atomic<Table<K, V>>>,
+    pub transfer_index: CachePadded<AtomicUsize>,
+    pub transferred: CachePadded<AtomicUsize>,
+}
+
+impl<K, V> Table<K, V> {
+    pub fn new(capacity: usize, track_probes: bool, pending: Pending) -> Self {
+        Table {
+            bins: (0..capacity).map(|_| Atomic::null()).collect(),
+            probes: if track_probes {
+                Some((0..capacity).map(|_| AtomicU64::new(0)).collect())
+            } else {
+                None
+            },
+            moved: Atomic::null(),
+            pending,
+            next_table: CachePadded::new(Atomic::null()),
+            transfer_index: CachePadded::new(AtomicUsize::new(0)),
+            transferred: CachePadded::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.bins.len()
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.capacity() - (self.capacity() >> 2)
+    }
+
+    pub fn bin_index(&self, hash: u64) -> usize {
+        hash as usize & (self.bins.len() - 1)
+    }
+
+    pub fn bin<'g>(&self, i: usize, guard: &'g Guard) -> Shared<'g, BinEntry<K, V>> {
+        self.bins[i].load(Ordering::SeqCst, guard)
+    }
+
+    pub fn cas_bin<'g>(
+        &self,
+        i: usize,
+        current: Shared<'g, BinEntry<K, V>>,
+        new: Owned<BinEntry<K, V>>,
+        guard: &'g Guard,
+    ) -> Result<(), Owned<BinEntry<K, V>>> {
+        self.bins[i]
+            .compare_and_set(current, new, Ordering::SeqCst, guard)
+            .map(|_| ())
+            .map_err(|error| error.new)
+    }
+
+    pub fn store_bin(&self, i: usize, new: Shared<'_, BinEntry<K, V>>) {
+        self.bins[i].store(new, Ordering::SeqCst);
+    }
+
+    pub fn stats(&self, guard: &Guard) -> TableStats {
+        let mut stats = TableStats {
+            capacity: self.capacity(),
+            ..TableStats::default()
+        };
+        for i in 0..self.capacity() {
+            let bin = self.bin(i, guard);
+            let mut element = match unsafe { bin.as_ref() } {
+                Some(BinEntry::Moved) => {
+                    stats.moved_bins += 1;
+                    continue;
+                }
+                Some(BinEntry::Tree(tree_bin)) => {
+                    stats.tree_bins += 1;
+                    tree_bin.first.load(Ordering::SeqCst, guard)
+                }
+                _ => bin,
+            };
+            let mut length = 0;
+            while let Some(node) = unsafe { element.as_ref() }.and_then(BinEntry::as_any_node) {
+                length += 1;
+                element = node.next.load(Ordering::SeqCst, guard);
+            }
+            if stats.chain_lengths.len() <= length {
+                stats.chain_lengths.resize(length + 1, 0);
+            }
+            stats.chain_lengths[length] += 1;
+        }
+        stats
+    }
+
+    pub fn next_table<'g>(&self, guard: &'g Guard) -> &'g Table<K, V> {
+        unsafe { self.next_table.load(Ordering::SeqCst, guard).deref() }
+    }
+
+    pub fn record_probes(&self, i: usize, probes: u64) {
+        #[cfg(feature = "tracing")]
+        if probes >= LONG_SCAN_PROBES {
+            tracing::trace!(bin = i, probes, "long bin scan");
+        }
+        if let Some(counters) = &self.probes {
+            let counter = &counters[i];
+            if counter.load(Ordering::Relaxed) >> 32 < PROBE_SAMPLE {
+                counter.fetch_add(1 << 32 | probes, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn probe_length_exceeds(&self, i: usize, target: u64) -> bool {
+        let counter = match &self.probes {
+            Some(counters) => &counters[i],
+            None => return false,
+        };
+
+        let sample = counter.load(Ordering::Relaxed);
+        let lookups = sample >> 32;
+        if lookups < PROBE_SAMPLE {
+            return false;
+        }
+
+        counter.store(0, Ordering::Relaxed);
+        sample & 0xffff_ffff > target * lookups
+    }
+}
+
+impl<K, V> Table<K, V>
+where
+    K: Eq,
+{
+    pub fn find<'g, Q>(
+        &self,
+        i: usize,
+        hash: u64,
+        key: &Q,
+        read_mostly: bool,
+        guard: &'g Guard,
+    ) -> Shared<'g, BinEntry<K, V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        let bin = self.bin(i, guard);
+        if bin.is_null() {
+            return Shared::null();
+        }
+        prefetch(bin.as_raw());
+
+        match unsafe { bin.deref() } {
+            BinEntry::Node(_) => {
+                let mut probes = 0;
+                let mut element = bin;
+                while !element.is_null() {
+                    probes += 1;
+                    let node = unsafe { element.deref() }.as_node().unwrap();
+                    let next = node.next.load(Ordering::SeqCst, guard);
+                    prefetch(next.as_raw());
+                    if node.hash == hash && key.equivalent(&node.key) {
+                        self.record_probes(i, probes);
+                        return element;
+                    }
+                    element = next;
+                }
+                self.record_probes(i, probes);
+                Shared::null()
+            }
+            BinEntry::Tree(_) if read_mostly => TreeBin::find_read_mostly(bin, hash, key, guard),
+            BinEntry::Tree(_) => TreeBin::find(bin, hash, key, guard),
+            BinEntry::Moved => {
+                let next = self.next_table(guard);
+                next.find(next.bin_index(hash), hash, key, read_mostly, guard)
+            }
+            BinEntry::TreeNode(_) => unreachable!(),
+        }
+    }
+}
+
+impl<K, V> Table<K, V>
+where
+    K: Eq + Clone,
+{
+    pub fn treeify_bin(&self, i: usize, policy: &ContentionTracker, guard: &Guard) {
+        let bin = self.bin(i, guard);
+        let head = match unsafe { bin.as_ref() } {
+            Some(BinEntry::Node(head)) => head,
+            _ => return,
+        };
+
+        let lock = policy.lock(&head.lock);
+        if self.bin(i, guard) != bin {
+            return;
+        }
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::debug_span!("treeify_bin", bin = i, capacity = self.capacity()).entered();
+
+        let mut first = Shared::null();
+        let mut tail: Shared<'_, BinEntry<K, V>> = Shared::null();
+        let mut element = bin;
+        while !element.is_null() {
+            let node = unsafe { element.deref() }.as_node().unwrap();
+            let tree_node = Owned::new(BinEntry::TreeNode(TreeNode::new(
+                node.hash,
+                node.key.clone(),
+                Atomic::null(),
+                Atomic::null(),
+                Atomic::null(),
+            )))
+            .into_shared(guard);
+            node.forward_to(tree_node, guard);
+
+            if tail.is_null() {
+                first = tree_node;
+            } else {
+                let tail_deref = unsafe { TreeNode::get_tree_node(tail) };
+                tail_deref.node.next.store(tree_node, Ordering::SeqCst);
+                unsafe { TreeNode::get_tree_node(tree_node) }
+                    .prev
+                    .store(tail, Ordering::SeqCst);
+            }
+            tail = tree_node;
+            element = node.next.load(Ordering::SeqCst, guard);
+        }
+
+        let tree_bin = TreeBin::new(unsafe { first.into_owned() }, self.pending.clone(), guard);
+        self.store_bin(i, Owned::new(BinEntry::Tree(tree_bin)).into_shared(guard));
+        drop(lock);
+
+        let mut element = bin;
+        while !element.is_null() {
+            let next = unsafe { element.deref() }
+                .as_node()
+                .unwrap()
+                .next
+                .load(Ordering::SeqCst, guard);
+            unsafe { guard.retire(element, &self.pending) };
+            element = next;
+        }
+    }
+
+    pub fn untreeify<'g>(
+        first: Shared<'g, BinEntry<K, V>>,
+        guard: &'g Guard,
+    ) -> Shared<'g, BinEntry<K, V>> {
+        let mut head = Shared::null();
+        let mut tail: Shared<'_, BinEntry<K, V>> = Shared::null();
+        let mut element = first;
+        while !element.is_null() {
+            let tree_node = unsafe { TreeNode::get_tree_node(element) };
+            let node = Owned::new(BinEntry::Node(Node::new(
+                tree_node.node.hash,
+                tree_node.node.key.clone(),
+                Atomic::null(),
+                Atomic::null(),
+            )))
+            .into_shared(guard);
+            tree_node.node.forward_to(node, guard);
+
+            if tail.is_null() {
+                head = node;
+            } else {
+                unsafe { tail.deref() }
+                    .as_node()
+                    .unwrap()
+                    .next
+                    .store(node, Ordering::SeqCst);
+            }
+            tail = node;
+            element = tree_node.node.next.load(Ordering::SeqCst, guard);
+        }
+        head
+    }
+
+    pub fn transfer_bin(
+        &self,
+        i: usize,
+        next: &Table<K, V>,
+        untreeify_threshold: usize,
+        policy: &ContentionTracker,
+        guard: &Guard,
+    ) {
+        let moved = self.moved.load(Ordering::SeqCst, guard);
+        loop {
+            let bin = self.bin(i, guard);
+            if bin.is_null() {
+                if self.bins[i]
+                    .compare_and_set(bin, moved, Ordering::SeqCst, guard)
+                    .is_ok()
+                {
+                    return;
+                }
+                continue;
+            }
+
+            let (lock, first, tree) = match unsafe { bin.deref() } {
+                BinEntry::Node(head) => (policy.lock(&head.lock), bin, false),
+                BinEntry::Tree(tree_bin) => (
+                    policy.lock(&tree_bin.lock),
+                    tree_bin.first.load(Ordering::SeqCst, guard),
+                    true,
+                ),
+                BinEntry::Moved => return,
+                BinEntry::TreeNode(_) => unreachable!(),
+            };
+            if self.bin(i, guard) != bin {
+                continue;
+            }
+
+            let capacity = self.capacity();
+            let (mut low_count, mut high_count) = (0, 0);
+            let mut element = first;
+            while !element.is_null() {
+                let node = unsafe { element.deref() }.as_any_node().unwrap();
+                if node.hash as usize & capacity == 0 {
+                    low_count += 1;
+                } else {
+                    high_count += 1;
+                }
+                element = node.next.load(Ordering::SeqCst, guard);
+            }
+
+            let low_tree = tree && low_count > untreeify_threshold;
+            let high_tree = tree && high_count > untreeify_threshold;
+            let (mut low, mut high) = (Shared::null(), Shared::null());
+            let mut element = first;
+            while !element.is_null() {
+                let node = unsafe { element.deref() }.as_any_node().unwrap();
+                let (head, as_tree) = if node.hash as usize & capacity == 0 {
+                    (&mut low, low_tree)
+                } else {
+                    (&mut high, high_tree)
+                };
+                *head = Self::split_node(node, *head, as_tree, guard);
+                element = node.next.load(Ordering::SeqCst, guard);
+            }
+
+            next.store_bin(i, self.split_bin(low, low_tree, guard));
+            next.store_bin(i + capacity, self.split_bin(high, high_tree, guard));
+            self.store_bin(i, moved);
+            drop(lock);
+
+            self.retire_bin(bin, first, guard);
+            return;
+        }
+    }
+
+    pub fn merge_bins(
+        &self,
+        i: usize,
+        next: &Table<K, V>,
+        untreeify_threshold: usize,
+        policy: &ContentionTracker,
+        guard: &Guard,
+    ) {
+        let moved = self.moved.load(Ordering::SeqCst, guard);
+        let (low_lock, low_bin, low_first, low_tree) = self.lock_bin(i, policy, guard);
+        let (high_lock, high_bin, high_first, high_tree) =
+            self.lock_bin(i + next.capacity(), policy, guard);
+
+        let mut count = 0;
+        for &first in [low_first, high_first].iter() {
+            let mut element = first;
+            while !element.is_null() {
+                count += 1;
+                element = unsafe { element.deref() }
+                    .as_any_node()
+                    .unwrap()
+                    .next
+                    .load(Ordering::SeqCst, guard);
+            }
+        }
+
+        let tree = (low_tree || high_tree) && count > untreeify_threshold;
+        let mut head = Shared::null();
+        for &first in [low_first, high_first].iter() {
+            let mut element = first;
+            while !element.is_null() {
+                let node = unsafe { element.deref() }.as_any_node().unwrap();
+                head = Self::split_node(node, head, tree, guard);
+                element = node.next.load(Ordering::SeqCst, guard);
+            }
+        }
+
+        next.store_bin(i, self.split_bin(head, tree, guard));
+        self.store_bin(i, moved);
+        self.store_bin(i + next.capacity(), moved);
+        drop(high_lock);
+        drop(low_lock);
+
+        self.retire_bin(low_bin, low_first, guard);
+        self.retire_bin(high_bin, high_first, guard);
+    }
+
+    fn lock_bin<'g>(
+        &self,
+        i: usize,
+        policy: &ContentionTracker,
+        guard: &'g Guard,
+    ) -> LockedBin<'g, K, V> {
+        loop {
+            let bin = self.bin(i, guard);
+            if bin.is_null() {
+                let reservation = Owned::new(BinEntry::Tree(TreeBin::empty(self.pending.clone())))
+                    .into_shared(guard);
+                let lock = policy.lock(&unsafe { reservation.deref() }.as_tree_bin().unwrap().lock);
+                if self.bins[i]
+                    .compare_and_set(bin, reservation, Ordering::SeqCst, guard)
+                    .is_ok()
+                {
+                    return (lock, reservation, Shared::null(), true);
+                }
+                drop(lock);
+                drop(unsafe { reservation.into_owned() });
+                continue;
+            }
+
+            let (lock, first, tree) = match unsafe { bin.deref() } {
+                BinEntry::Node(head) => (policy.lock(&head.lock), bin, false),
+                BinEntry::Tree(tree_bin) => (
+                    policy.lock(&tree_bin.lock),
+                    tree_bin.first.load(Ordering::SeqCst, guard),
+                    true,
+                ),
+                BinEntry::TreeNode(_) | BinEntry::Moved => unreachable!(),
+            };
+            if self.bin(i, guard) == bin {
+                return (lock, bin, first, tree);
+            }
+        }
+    }
+
+    fn retire_bin(
+        &self,
+        bin: Shared<'_, BinEntry<K, V>>,
+        first: Shared<'_, BinEntry<K, V>>,
+        guard: &Guard,
+    ) {
+        let mut element = first;
+        while !element.is_null() {
+            let next = unsafe { element.deref() }
+                .as_any_node()
+                .unwrap()
+                .next
+                .load(Ordering::SeqCst, guard);
+            unsafe { guard.retire(element, &self.pending) };
+            element = next;
+        }
+        if first != bin {
+            unsafe { guard.retire(bin, &self.pending) };
+        }
+    }
+
+    fn split_node<'g>(
+        node: &Node<K, V>,
+        head: Shared<'g, BinEntry<K, V>>,
+        tree: bool,
+        guard: &'g Guard,
+    ) -> Shared<'g, BinEntry<K, V>> {
+        let value = Atomic::null();
+        let copy = if tree {
+            BinEntry::TreeNode(TreeNode::new(
+                node.hash,
+                node.key.clone(),
+                value,
+                Atomic::from(head),
+                Atomic::null(),
+            ))
+        } else {
+            BinEntry::Node(Node::new(
+                node.hash,
+                node.key.clone(),
+                value,
+                Atomic::from(head),
+            ))
+        };
+        let copy = Owned::new(copy).into_shared(guard);
+        node.forward_to(copy, guard);
+
+        if tree && !head.is_null() {
+            unsafe { TreeNode::get_tree_node(head) }
+                .prev
+                .store(copy, Ordering::SeqCst);
+        }
+        copy
+    }
+
+    fn split_bin<'g>(
+        &self,
+        head: Shared<'g, BinEntry<K, V>>,
+        tree: bool,
+        guard: &'g Guard,
+    ) -> Shared<'g, BinEntry<K, V>> {
+        if !tree {
+            return head;
+        }
+
+        let tree_bin = TreeBin::new(unsafe { head.into_owned() }, self.pending.clone(), guard);
+        Owned::new(BinEntry::Tree(tree_bin)).into_shared(guard)
+    }
+}
+
+impl<K, V> Drop for Table<K, V> {
+    fn drop(&mut self) {
+        let guard = unsafe { unprotected() };
+        let moved = self.moved.swap(Shared::null(), Ordering::SeqCst, guard);
+        if !moved.is_null() {
+            drop(unsafe { moved.into_owned() });
+        }
+    }
+}
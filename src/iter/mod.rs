@@ -0,0 +1,118 @@
+use std::vec;
+
+use self::traverser::NodeIter;
+use crate::util::sync::epoch::Guard;
+
+#[cfg(feature = "rayon")]
+pub mod par;
+pub mod traverser;
+
+#[derive(Debug)]
+pub struct HashedIter<'g, K, V> {
+    nodes: NodeIter<'g, K, V>,
+    guard: &'g Guard,
+}
+
+impl<'g, K, V> HashedIter<'g, K, V> {
+    pub fn new(nodes: NodeIter<'g, K, V>, guard: &'g Guard) -> Self {
+        HashedIter { nodes, guard }
+    }
+}
+
+impl<'g, K, V> Iterator for HashedIter<'g, K, V> {
+    type Item = (u64, &'g K, &'g V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for node in &mut self.nodes {
+            let value = node.load_value(self.guard);
+            if let Some(value) = unsafe { value.as_ref() } {
+                return Some((node.hash, &node.key, value));
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug)]
+pub struct Iter<'g, K, V> {
+    entries: HashedIter<'g, K, V>,
+}
+
+impl<'g, K, V> Iter<'g, K, V> {
+    pub fn new(entries: HashedIter<'g, K, V>) -> Self {
+        Iter { entries }
+    }
+}
+
+impl<'g, K, V> Iterator for Iter<'g, K, V> {
+    type Item = (&'g K, &'g V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(_, key, value)| (key, value))
+    }
+}
+
+#[derive(Debug)]
+pub struct Keys<'g, K, V> {
+    entries: Iter<'g, K, V>,
+}
+
+impl<'g, K, V> Keys<'g, K, V> {
+    pub fn new(entries: Iter<'g, K, V>) -> Self {
+        Keys { entries }
+    }
+}
+
+impl<'g, K, V> Iterator for Keys<'g, K, V> {
+    type Item = &'g K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(key, _)| key)
+    }
+}
+
+#[derive(Debug)]
+pub struct Values<'g, K, V> {
+    entries: Iter<'g, K, V>,
+}
+
+impl<'g, K, V> Values<'g, K, V> {
+    pub fn new(entries: Iter<'g, K, V>) -> Self {
+        Values { entries }
+    }
+}
+
+impl<'g, K, V> Iterator for Values<'g, K, V> {
+    type Item = &'g V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(_, value)| value)
+    }
+}
+
+#[derive(Debug)]
+pub struct IntoIter<K, V> {
+    entries: vec::IntoIter<(K, V)>,
+}
+
+impl<K, V> IntoIter<K, V> {
+    pub fn new(entries: Vec<(K, V)>) -> Self {
+        IntoIter {
+            entries: entries.into_iter(),
+        }
+    }
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
@@ -0,0 +1,138 @@
+use rayon::iter::{
+    plumbing::{bridge_unindexed, Folder, UnindexedConsumer, UnindexedProducer},
+    ParallelIterator,
+};
+
+use crate::{core::table::Table, util::sync::epoch};
+
+use super::{traverser::NodeIter, HashedIter, Iter};
+
+#[derive(Debug)]
+pub struct ParIter<'g, K, V> {
+    bins: BinRange<'g, K, V>,
+}
+
+impl<'g, K, V> ParIter<'g, K, V> {
+    pub fn new(table: Option<&'g Table<K, V>>) -> Self {
+        ParIter {
+            bins: BinRange {
+                table,
+                start: 0,
+                end: table.map_or(0, Table::capacity),
+            },
+        }
+    }
+}
+
+impl<'g, K, V> ParallelIterator for ParIter<'g, K, V>
+where
+    K: Send + Sync,
+    V: Send + Sync,
+{
+    type Item = (&'g K, &'g V);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge_unindexed(self.bins, consumer)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParKeys<'g, K, V> {
+    entries: ParIter<'g, K, V>,
+}
+
+impl<'g, K, V> ParKeys<'g, K, V> {
+    pub fn new(entries: ParIter<'g, K, V>) -> Self {
+        ParKeys { entries }
+    }
+}
+
+impl<'g, K, V> ParallelIterator for ParKeys<'g, K, V>
+where
+    K: Send + Sync,
+    V: Send + Sync,
+{
+    type Item = &'g K;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.entries.map(|(key, _)| key).drive_unindexed(consumer)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParValues<'g, K, V> {
+    entries: ParIter<'g, K, V>,
+}
+
+impl<'g, K, V> ParValues<'g, K, V> {
+    pub fn new(entries: ParIter<'g, K, V>) -> Self {
+        ParValues { entries }
+    }
+}
+
+impl<'g, K, V> ParallelIterator for ParValues<'g, K, V>
+where
+    K: Send + Sync,
+    V: Send + Sync,
+{
+    type Item = &'g V;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.entries
+            .map(|(_, value)| value)
+            .drive_unindexed(consumer)
+    }
+}
+
+#[derive(Debug)]
+pub struct BinRange<'g, K, V> {
+    table: Option<&'g Table<K, V>>,
+    start: usize,
+    end: usize,
+}
+
+impl<'g, K, V> UnindexedProducer for BinRange<'g, K, V>
+where
+    K: Send + Sync,
+    V: Send + Sync,
+{
+    type Item = (&'g K, &'g V);
+
+    fn split(self) -> (Self, Option<Self>) {
+        if self.end - self.start < 2 {
+            return (self, None);
+        }
+
+        let mid = self.start + (self.end - self.start) / 2;
+        (
+            BinRange {
+                table: self.table,
+                start: self.start,
+                end: mid,
+            },
+            Some(BinRange {
+                table: self.table,
+                start: mid,
+                end: self.end,
+            }),
+        )
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        let guard = unsafe { epoch::unprotected() };
+        let nodes = NodeIter::range(self.table, self.start, self.end, guard);
+        folder.consume_iter(Iter::new(HashedIter::new(nodes, guard)))
+    }
+}
@@ -0,0 +1,101 @@
+use crate::util::sync::epoch::{Guard, Shared};
+use crate::{
+    core::{bin_entry::BinEntry, node::Node, table::Table},
+    util::{expiry::Expiry, sync::atomic::Ordering},
+};
+
+pub type Filter = (usize, usize);
+
+#[derive(Debug)]
+pub struct NodeIter<'g, K, V> {
+    table: Option<&'g Table<K, V>>,
+    index: usize,
+    end: usize,
+    forwarded: Vec<(&'g Table<K, V>, usize, Filter)>,
+    element: Shared<'g, BinEntry<K, V>>,
+    filter: Filter,
+    expiry: Expiry,
+    guard: &'g Guard,
+}
+
+impl<'g, K, V> NodeIter<'g, K, V> {
+    pub fn new(table: Option<&'g Table<K, V>>, guard: &'g Guard) -> Self {
+        let end = table.map_or(0, Table::capacity);
+        Self::range(table, 0, end, guard)
+    }
+
+    pub fn range(
+        table: Option<&'g Table<K, V>>,
+        start: usize,
+        end: usize,
+        guard: &'g Guard,
+    ) -> Self {
+        NodeIter {
+            table,
+            index: start,
+            end,
+            forwarded: Vec::new(),
+            element: Shared::null(),
+            filter: (0, 0),
+            expiry: Expiry::default(),
+            guard,
+        }
+    }
+
+    pub fn skip_expired(mut self, expiry: Expiry) -> Self {
+        self.expiry = expiry;
+        self
+    }
+}
+
+impl<'g, K, V> Iterator for NodeIter<'g, K, V> {
+    type Item = &'g Node<K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if !self.element.is_null() {
+                let node = unsafe { self.element.deref() }.as_any_node().unwrap();
+                self.element = node.next.load(Ordering::SeqCst, self.guard);
+                if node.hash as usize & self.filter.0 != self.filter.1
+                    || node.is_expired(&self.expiry)
+                {
+                    continue;
+                }
+                return Some(node);
+            }
+
+            let (table, i, filter) = match self.forwarded.pop() {
+                Some(forwarded) => forwarded,
+                None => {
+                    let table = self.table?;
+                    if self.index >= self.end {
+                        return None;
+                    }
+                    self.index += 1;
+                    (table, self.index - 1, (0, 0))
+                }
+            };
+            self.filter = filter;
+
+            let bin = table.bin(i, self.guard);
+            self.element = match unsafe { bin.as_ref() } {
+                Some(BinEntry::Tree(tree_bin)) => tree_bin.first.load(Ordering::SeqCst, self.guard),
+                Some(BinEntry::Node(_)) | None => bin,
+                Some(BinEntry::Moved) => {
+                    let next = table.next_table(self.guard);
+                    if next.capacity() > table.capacity() {
+                        self.forwarded.push((next, i + table.capacity(), filter));
+                        self.forwarded.push((next, i, filter));
+                    } else {
+                        let mask = table.capacity() - 1;
+                        let filter = if mask > filter.0 { (mask, i) } else { filter };
+                        self.forwarded
+                            .push((next, i & (next.capacity() - 1), filter));
+                    }
+                    Shared::null()
+                }
+                Some(BinEntry::TreeNode(_)) => unreachable!(),
+            };
+        }
+    }
+}
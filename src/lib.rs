@@ -1,2 +1,11 @@
 pub mod core;
+pub mod iter;
+pub mod map;
 pub mod util;
+
+pub use map::{
+    bytes_map::BytesMap, counter_map::CounterMap, cuckoo::CuckooMap, frozen::FrozenMap,
+    int_map::IntMap, interner::Interner, multimap::HashMultiMap, pinned::HashMapRef, set::HashSet,
+    swiss::SwissMap, value_pool::ValuePool, ConcurrentHashMap,
+};
+pub use util::equivalent::Equivalent;
@@ -0,0 +1,75 @@
+use std::hash::Hash;
+
+use rkyv::{
+    collections::hash_map::{ArchivedHashMap, HashMapResolver},
+    ser::{ScratchSpace, Serializer},
+    Archive, Deserialize, Fallible, Serialize,
+};
+
+use crate::util::hashing::KeyHasher;
+
+use super::ConcurrentHashMap;
+
+pub struct MapResolver {
+    len: usize,
+    resolver: HashMapResolver,
+}
+
+impl<K, V, S> Archive for ConcurrentHashMap<K, V, S>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Archive,
+{
+    type Archived = ArchivedHashMap<K::Archived, V::Archived>;
+    type Resolver = MapResolver;
+
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedHashMap::resolve_from_len(resolver.len, pos, resolver.resolver, out);
+    }
+}
+
+impl<K, V, S, Z> Serialize<Z> for ConcurrentHashMap<K, V, S>
+where
+    K: Serialize<Z> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Serialize<Z>,
+    Z: Serializer + ScratchSpace + ?Sized,
+{
+    fn serialize(&self, serializer: &mut Z) -> Result<Self::Resolver, Z::Error> {
+        let guard = self.guard();
+        let entries: Vec<_> = self.iter(&guard).collect();
+        let resolver =
+            unsafe { ArchivedHashMap::serialize_from_iter(entries.iter().copied(), serializer)? };
+        Ok(MapResolver {
+            len: entries.len(),
+            resolver,
+        })
+    }
+}
+
+impl<K, V, S, D> Deserialize<ConcurrentHashMap<K, V, S>, D>
+    for ArchivedHashMap<K::Archived, V::Archived>
+where
+    K: Archive + Hash + Eq + Clone,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    S: KeyHasher<K> + Default,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<ConcurrentHashMap<K, V, S>, D::Error> {
+        let map = ConcurrentHashMap::with_capacity_and_hasher(self.len(), S::default());
+        {
+            let guard = map.guard();
+            for (key, value) in self.iter() {
+                map.insert(
+                    key.deserialize(deserializer)?,
+                    value.deserialize(deserializer)?,
+                    &guard,
+                );
+            }
+        }
+        Ok(map)
+    }
+}
@@ -0,0 +1,241 @@
+use crate::util::sync::epoch::{Collector, Guard};
+use crate::{
+    iter::Iter,
+    util::{equivalent::Equivalent, hashing::KeyHasher, reclaim},
+};
+
+use super::{
+    builder::Builder,
+    cuckoo::{CuckooIter, CuckooMap},
+    swiss::{SwissIter, SwissMap},
+    ConcurrentHashMap,
+};
+
+pub trait Backend<K, V, S>: Sized {
+    type Iter<'g>: Iterator<Item = (&'g K, &'g V)>
+    where
+        Self: 'g,
+        K: 'g,
+        V: 'g;
+
+    fn with_collector(capacity: usize, build_hasher: S, collector: Collector) -> Self;
+
+    fn collector(&self) -> &Collector;
+
+    fn guard(&self) -> Guard {
+        reclaim::pin(self.collector())
+    }
+
+    fn hasher(&self) -> &S;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn iter<'g>(&'g self, guard: &'g Guard) -> Self::Iter<'g>;
+
+    fn get_key_value<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<(&'g K, &'g V)>
+    where
+        K: Eq,
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>;
+
+    fn get<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        K: Eq + 'g,
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key, guard).map(|(_, value)| value)
+    }
+
+    fn contains_key<Q>(&self, key: &Q, guard: &Guard) -> bool
+    where
+        K: Eq,
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key, guard).is_some()
+    }
+
+    fn insert<'g>(&'g self, key: K, value: V, guard: &'g Guard) -> Option<&'g V>
+    where
+        K: Eq + Clone,
+        S: KeyHasher<K>;
+
+    fn remove<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        K: Eq + Clone,
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<K> + KeyHasher<Q>;
+}
+
+impl<K, V, S> Backend<K, V, S> for ConcurrentHashMap<K, V, S> {
+    type Iter<'g>
+        = Iter<'g, K, V>
+    where
+        Self: 'g,
+        K: 'g,
+        V: 'g;
+
+    fn with_collector(capacity: usize, build_hasher: S, collector: Collector) -> Self {
+        Builder::new()
+            .capacity(capacity)
+            .collector(collector)
+            .build_with_hasher(build_hasher)
+    }
+
+    fn collector(&self) -> &Collector {
+        self.collector()
+    }
+
+    fn hasher(&self) -> &S {
+        self.hasher()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn iter<'g>(&'g self, guard: &'g Guard) -> Self::Iter<'g> {
+        self.iter(guard)
+    }
+
+    fn get_key_value<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<(&'g K, &'g V)>
+    where
+        K: Eq,
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key, guard)
+    }
+
+    fn insert<'g>(&'g self, key: K, value: V, guard: &'g Guard) -> Option<&'g V>
+    where
+        K: Eq + Clone,
+        S: KeyHasher<K>,
+    {
+        self.insert(key, value, guard)
+    }
+
+    fn remove<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        K: Eq + Clone,
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<K> + KeyHasher<Q>,
+    {
+        self.remove(key, guard)
+    }
+}
+
+impl<K, V, S> Backend<K, V, S> for SwissMap<K, V, S> {
+    type Iter<'g>
+        = SwissIter<'g, K, V>
+    where
+        Self: 'g,
+        K: 'g,
+        V: 'g;
+
+    fn with_collector(capacity: usize, build_hasher: S, collector: Collector) -> Self {
+        SwissMap::with_capacity_and_hasher(capacity, build_hasher).with_collector(collector)
+    }
+
+    fn collector(&self) -> &Collector {
+        self.collector()
+    }
+
+    fn hasher(&self) -> &S {
+        self.hasher()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn iter<'g>(&'g self, guard: &'g Guard) -> Self::Iter<'g> {
+        self.iter(guard)
+    }
+
+    fn get_key_value<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<(&'g K, &'g V)>
+    where
+        K: Eq,
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key, guard)
+    }
+
+    fn insert<'g>(&'g self, key: K, value: V, guard: &'g Guard) -> Option<&'g V>
+    where
+        K: Eq + Clone,
+        S: KeyHasher<K>,
+    {
+        self.insert(key, value, guard)
+    }
+
+    fn remove<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        K: Eq + Clone,
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<K> + KeyHasher<Q>,
+    {
+        self.remove(key, guard)
+    }
+}
+
+impl<K, V, S> Backend<K, V, S> for CuckooMap<K, V, S> {
+    type Iter<'g>
+        = CuckooIter<'g, K, V>
+    where
+        Self: 'g,
+        K: 'g,
+        V: 'g;
+
+    fn with_collector(capacity: usize, build_hasher: S, collector: Collector) -> Self {
+        CuckooMap::with_capacity_and_hasher(capacity, build_hasher).with_collector(collector)
+    }
+
+    fn collector(&self) -> &Collector {
+        self.collector()
+    }
+
+    fn hasher(&self) -> &S {
+        self.hasher()
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn iter<'g>(&'g self, guard: &'g Guard) -> Self::Iter<'g> {
+        self.iter(guard)
+    }
+
+    fn get_key_value<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<(&'g K, &'g V)>
+    where
+        K: Eq,
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key, guard)
+    }
+
+    fn insert<'g>(&'g self, key: K, value: V, guard: &'g Guard) -> Option<&'g V>
+    where
+        K: Eq + Clone,
+        S: KeyHasher<K>,
+    {
+        self.insert(key, value, guard)
+    }
+
+    fn remove<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        K: Eq + Clone,
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<K> + KeyHasher<Q>,
+    {
+        self.remove(key, guard)
+    }
+}
@@ -0,0 +1,327 @@
+use std::{
+    fmt,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use crossbeam_utils::CachePadded;
+
+use crate::util::sync::epoch::{self, Atomic, Collector};
+use crate::util::{
+    contention_policy::{ContentionPolicy, ContentionTracker},
+    counter::Counter,
+    eviction_policy::EvictionPolicy,
+    expiry::Expiry,
+    hashing::SeededState,
+    lease::LeasePolicy,
+    pool::Pool,
+    reservation::Reservations,
+    sync::atomic::{AtomicU64, AtomicUsize},
+    treeify_policy::{CollisionEstimate, TreeifyPolicy},
+};
+
+use super::{
+    listener::{Event, Listeners},
+    ConcurrentHashMap, Weigher, DEFAULT_CAPACITY, DEFAULT_DEBUG_LIMIT, MAXIMUM_CAPACITY,
+    MIN_TREEIFY_CAPACITY, TREEIFY_THRESHOLD, UNTREEIFY_THRESHOLD,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    InvalidTreeifyThresholds { treeify: usize, untreeify: usize },
+}
+
+pub struct Builder<K, V> {
+    read_mostly: bool,
+    treeify_policy: TreeifyPolicy,
+    lease_policy: LeasePolicy,
+    contention_policy: ContentionPolicy,
+    versioned: bool,
+    capacity: usize,
+    debug_limit: Option<usize>,
+    collector: Option<Collector>,
+    resize_stride: Option<usize>,
+    treeify_threshold: Option<usize>,
+    untreeify_threshold: Option<usize>,
+    min_treeify_capacity: Option<usize>,
+    shrink_fraction: Option<f64>,
+    node_pool: Option<usize>,
+    expire_after_write: Option<Duration>,
+    expire_after_access: Option<Duration>,
+    max_capacity: Option<usize>,
+    eviction_policy: EvictionPolicy,
+    listeners: Listeners<K, V>,
+    weigher: Option<Weigher<K, V>>,
+    max_weight: u64,
+}
+
+impl<K, V> Builder<K, V> {
+    pub fn new() -> Self {
+        Builder {
+            read_mostly: false,
+            treeify_policy: TreeifyPolicy::default(),
+            lease_policy: LeasePolicy::default(),
+            contention_policy: ContentionPolicy::default(),
+            versioned: false,
+            capacity: 0,
+            debug_limit: None,
+            collector: None,
+            resize_stride: None,
+            treeify_threshold: None,
+            untreeify_threshold: None,
+            min_treeify_capacity: None,
+            shrink_fraction: None,
+            node_pool: None,
+            expire_after_write: None,
+            expire_after_access: None,
+            max_capacity: None,
+            eviction_policy: EvictionPolicy::default(),
+            listeners: Listeners::new(),
+            weigher: None,
+            max_weight: 0,
+        }
+    }
+
+    pub fn read_mostly(mut self, read_mostly: bool) -> Self {
+        self.read_mostly = read_mostly;
+        self
+    }
+
+    pub fn treeify_policy(mut self, treeify_policy: TreeifyPolicy) -> Self {
+        self.treeify_policy = treeify_policy;
+        self
+    }
+
+    pub fn lease_policy(mut self, lease_policy: LeasePolicy) -> Self {
+        self.lease_policy = lease_policy;
+        self
+    }
+
+    pub fn contention_policy(mut self, contention_policy: ContentionPolicy) -> Self {
+        self.contention_policy = contention_policy;
+        self
+    }
+
+    pub fn versioned(mut self, versioned: bool) -> Self {
+        self.versioned = versioned;
+        self
+    }
+
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn debug_limit(mut self, debug_limit: usize) -> Self {
+        self.debug_limit = Some(debug_limit);
+        self
+    }
+
+    pub fn collector(mut self, collector: Collector) -> Self {
+        self.collector = Some(collector);
+        self
+    }
+
+    pub fn incremental_resize(mut self, stride: usize) -> Self {
+        self.resize_stride = Some(stride.max(1));
+        self
+    }
+
+    pub fn treeify_threshold(mut self, treeify_threshold: usize) -> Self {
+        self.treeify_threshold = Some(treeify_threshold);
+        self
+    }
+
+    pub fn untreeify_threshold(mut self, untreeify_threshold: usize) -> Self {
+        self.untreeify_threshold = Some(untreeify_threshold);
+        self
+    }
+
+    pub fn min_treeify_capacity(mut self, min_treeify_capacity: usize) -> Self {
+        self.min_treeify_capacity = Some(min_treeify_capacity);
+        self
+    }
+
+    pub fn auto_shrink(mut self, fraction: f64) -> Self {
+        self.shrink_fraction = Some(fraction);
+        self
+    }
+
+    pub fn node_pool(mut self, capacity: usize) -> Self {
+        self.node_pool = Some(capacity);
+        self
+    }
+
+    pub fn expire_after_write(mut self, ttl: Duration) -> Self {
+        self.expire_after_write = Some(ttl);
+        self
+    }
+
+    pub fn expire_after_access(mut self, tti: Duration) -> Self {
+        self.expire_after_access = Some(tti);
+        self
+    }
+
+    pub fn max_capacity(mut self, max_capacity: usize) -> Self {
+        self.max_capacity = Some(max_capacity);
+        self
+    }
+
+    pub fn eviction_policy(mut self, eviction_policy: EvictionPolicy) -> Self {
+        self.eviction_policy = eviction_policy;
+        self
+    }
+
+    pub fn listener<F>(mut self, listener: F) -> Self
+    where
+        F: Fn(&Event<'_, K, V>) + Send + Sync + 'static,
+    {
+        self.listeners.push(Arc::new(listener));
+        self
+    }
+
+    pub fn weigher<F>(mut self, max_weight: u64, weigher: F) -> Self
+    where
+        F: Fn(&K, &V) -> u32 + Send + Sync + 'static,
+    {
+        self.weigher = Some(Arc::new(weigher));
+        self.max_weight = max_weight;
+        self
+    }
+
+    pub fn build(self) -> ConcurrentHashMap<K, V> {
+        self.build_with_hasher(SeededState::new())
+    }
+
+    pub fn build_with_hasher<S>(self, build_hasher: S) -> ConcurrentHashMap<K, V, S> {
+        match self.try_build_with_hasher(build_hasher) {
+            Ok(map) => map,
+            Err(error) => panic!("invalid map configuration: {:?}", error),
+        }
+    }
+
+    pub fn try_build(self) -> Result<ConcurrentHashMap<K, V>, BuildError> {
+        self.try_build_with_hasher(SeededState::new())
+    }
+
+    pub fn try_build_with_hasher<S>(
+        self,
+        build_hasher: S,
+    ) -> Result<ConcurrentHashMap<K, V, S>, BuildError> {
+        let treeify_threshold = self.treeify_threshold.unwrap_or(TREEIFY_THRESHOLD);
+        let untreeify_threshold = self.untreeify_threshold.unwrap_or(UNTREEIFY_THRESHOLD);
+        if treeify_threshold < 2 || untreeify_threshold >= treeify_threshold {
+            return Err(BuildError::InvalidTreeifyThresholds {
+                treeify: treeify_threshold,
+                untreeify: untreeify_threshold,
+            });
+        }
+        Ok(ConcurrentHashMap {
+            table: CachePadded::new(Atomic::null()),
+            initial_capacity: self.table_size(),
+            count: Counter::new(),
+            build_hasher,
+            read_mostly: self.read_mostly,
+            treeify_policy: self.treeify_policy,
+            lease_policy: self.lease_policy,
+            contention_policy: ContentionTracker::new(self.contention_policy),
+            versioned: self.versioned,
+            generations: CachePadded::new(AtomicU64::new(0)),
+            reservations: Reservations::new(),
+            debug_limit: self.debug_limit.unwrap_or(DEFAULT_DEBUG_LIMIT),
+            collector: self
+                .collector
+                .unwrap_or_else(|| epoch::default_collector().clone()),
+            resize_stride: self.resize_stride,
+            resize_helper: OnceLock::new(),
+            treeify_threshold,
+            untreeify_threshold,
+            min_treeify_capacity: self.min_treeify_capacity.unwrap_or(MIN_TREEIFY_CAPACITY),
+            shrink_fraction: self.shrink_fraction,
+            pending: OnceLock::new(),
+            node_pool: self.node_pool.map(|capacity| Arc::new(Pool::new(capacity))),
+            listeners: self.listeners,
+            expiry: Expiry::new(self.expire_after_write, self.expire_after_access),
+            sweep_cursor: AtomicUsize::new(0),
+            max_capacity: self.max_capacity,
+            eviction_policy: self.eviction_policy,
+            evict_cursor: AtomicUsize::new(0),
+            weigher: self.weigher,
+            max_weight: self.max_weight,
+            total_weight: Counter::new(),
+            collisions: CollisionEstimate::new(),
+        })
+    }
+
+    fn table_size(&self) -> usize {
+        if self.capacity == 0 {
+            return DEFAULT_CAPACITY;
+        }
+        self.capacity
+            .checked_add(self.capacity / 2 + 1)
+            .and_then(usize::checked_next_power_of_two)
+            .map_or(MAXIMUM_CAPACITY, |size| size.min(MAXIMUM_CAPACITY))
+    }
+}
+
+impl<K, V> Default for Builder<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Clone for Builder<K, V> {
+    fn clone(&self) -> Self {
+        Builder {
+            read_mostly: self.read_mostly,
+            treeify_policy: self.treeify_policy,
+            lease_policy: self.lease_policy,
+            contention_policy: self.contention_policy,
+            versioned: self.versioned,
+            capacity: self.capacity,
+            debug_limit: self.debug_limit,
+            collector: self.collector.clone(),
+            resize_stride: self.resize_stride,
+            treeify_threshold: self.treeify_threshold,
+            untreeify_threshold: self.untreeify_threshold,
+            min_treeify_capacity: self.min_treeify_capacity,
+            shrink_fraction: self.shrink_fraction,
+            node_pool: self.node_pool,
+            expire_after_write: self.expire_after_write,
+            expire_after_access: self.expire_after_access,
+            max_capacity: self.max_capacity,
+            eviction_policy: self.eviction_policy,
+            listeners: self.listeners.clone(),
+            weigher: self.weigher.clone(),
+            max_weight: self.max_weight,
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for Builder<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("read_mostly", &self.read_mostly)
+            .field("treeify_policy", &self.treeify_policy)
+            .field("lease_policy", &self.lease_policy)
+            .field("contention_policy", &self.contention_policy)
+            .field("versioned", &self.versioned)
+            .field("capacity", &self.capacity)
+            .field("debug_limit", &self.debug_limit)
+            .field("collector", &self.collector)
+            .field("resize_stride", &self.resize_stride)
+            .field("treeify_threshold", &self.treeify_threshold)
+            .field("untreeify_threshold", &self.untreeify_threshold)
+            .field("min_treeify_capacity", &self.min_treeify_capacity)
+            .field("shrink_fraction", &self.shrink_fraction)
+            .field("node_pool", &self.node_pool)
+            .field("expire_after_write", &self.expire_after_write)
+            .field("expire_after_access", &self.expire_after_access)
+            .field("max_capacity", &self.max_capacity)
+            .field("eviction_policy", &self.eviction_policy)
+            .field("listeners", &self.listeners)
+            .field("weighted", &self.weigher.is_some())
+            .field("max_weight", &self.max_weight)
+            .finish()
+    }
+}
@@ -0,0 +1,10 @@
+use crate::util::{
+    bytes_key::{BytesKey, InlineBytesKey},
+    hashing::SeededState,
+};
+
+use super::ConcurrentHashMap;
+
+pub type BytesMap<V, S = SeededState> = ConcurrentHashMap<BytesKey, V, S>;
+
+pub type InlineBytesMap<V, S = SeededState> = ConcurrentHashMap<InlineBytesKey, V, S>;
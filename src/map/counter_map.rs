@@ -0,0 +1,244 @@
+use std::fmt;
+
+use crate::util::sync::epoch::Guard;
+use crate::{
+    iter::Iter,
+    util::{
+        equivalent::Equivalent,
+        hashing::{KeyHasher, SeededState},
+        sync::{
+            atomic::{AtomicI64, AtomicU64, Ordering},
+            thread,
+        },
+    },
+};
+
+use super::{entry::Entry, ConcurrentHashMap};
+
+const REMOVED: u64 = 1 << 63;
+
+#[derive(Debug)]
+struct Slot {
+    value: AtomicI64,
+    writers: AtomicU64,
+}
+
+impl Slot {
+    fn new(value: i64) -> Self {
+        Slot {
+            value: AtomicI64::new(value),
+            writers: AtomicU64::new(0),
+        }
+    }
+
+    fn load(&self) -> i64 {
+        self.value.load(Ordering::SeqCst)
+    }
+
+    fn update<R>(&self, f: impl FnOnce(&AtomicI64) -> R) -> Option<R> {
+        if self.writers.fetch_add(1, Ordering::SeqCst) & REMOVED != 0 {
+            self.writers.fetch_sub(1, Ordering::SeqCst);
+            return None;
+        }
+        let result = f(&self.value);
+        self.writers.fetch_sub(1, Ordering::SeqCst);
+        Some(result)
+    }
+
+    fn retire(&self) -> i64 {
+        self.writers.fetch_or(REMOVED, Ordering::SeqCst);
+        while self.writers.load(Ordering::SeqCst) != REMOVED {
+            thread::yield_now();
+        }
+        self.load()
+    }
+}
+
+pub struct CounterMap<K, S = SeededState> {
+    map: ConcurrentHashMap<K, Slot, S>,
+}
+
+impl<K> CounterMap<K> {
+    pub fn new() -> Self {
+        CounterMap {
+            map: ConcurrentHashMap::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        CounterMap {
+            map: ConcurrentHashMap::with_capacity(capacity),
+        }
+    }
+}
+
+impl<K, S> CounterMap<K, S> {
+    pub fn with_hasher(build_hasher: S) -> Self {
+        CounterMap {
+            map: ConcurrentHashMap::with_hasher(build_hasher),
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, build_hasher: S) -> Self {
+        CounterMap {
+            map: ConcurrentHashMap::with_capacity_and_hasher(capacity, build_hasher),
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+
+    pub fn guard(&self) -> Guard {
+        self.map.guard()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn iter<'g>(&'g self, guard: &'g Guard) -> CounterIter<'g, K> {
+        CounterIter {
+            iter: self.map.iter(guard),
+        }
+    }
+
+    pub fn sum(&self, guard: &Guard) -> i64 {
+        self.iter(guard)
+            .fold(0, |sum, (_, value)| sum.wrapping_add(value))
+    }
+
+    pub fn clear(&self, guard: &Guard) {
+        self.map.clear(guard);
+    }
+}
+
+impl<K, S> CounterMap<K, S>
+where
+    K: Eq,
+{
+    pub fn get<Q>(&self, key: &Q, guard: &Guard) -> Option<i64>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.get(key, guard).map(Slot::load)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.contains_key(key, guard)
+    }
+
+    pub fn reset<Q>(&self, key: &Q, guard: &Guard) -> Option<i64>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        loop {
+            let slot = self.map.get(key, guard)?;
+            if let Some(value) = slot.update(|value| value.swap(0, Ordering::SeqCst)) {
+                return Some(value);
+            }
+        }
+    }
+}
+
+impl<K, S> CounterMap<K, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    pub fn increment(&self, key: K, delta: i64, guard: &Guard) -> i64 {
+        let add = |value: &AtomicI64| value.fetch_add(delta, Ordering::SeqCst).wrapping_add(delta);
+        loop {
+            if let Some(slot) = self.map.get(&key, guard) {
+                if let Some(value) = slot.update(add) {
+                    return value;
+                }
+            }
+
+            match self.map.entry(key.clone(), guard) {
+                Entry::Occupied(entry) => {
+                    if let Some(value) = entry.get().update(add) {
+                        return value;
+                    }
+                }
+                Entry::Vacant(entry) => return entry.insert(Slot::new(delta)).load(),
+            }
+        }
+    }
+
+    pub fn decrement(&self, key: K, delta: i64, guard: &Guard) -> i64 {
+        self.increment(key, delta.wrapping_neg(), guard)
+    }
+
+    pub fn insert(&self, key: K, value: i64, guard: &Guard) -> Option<i64> {
+        let swap = |current: &AtomicI64| current.swap(value, Ordering::SeqCst);
+        loop {
+            if let Some(slot) = self.map.get(&key, guard) {
+                if let Some(old) = slot.update(swap) {
+                    return Some(old);
+                }
+            }
+
+            match self.map.entry(key.clone(), guard) {
+                Entry::Occupied(entry) => {
+                    if let Some(old) = entry.get().update(swap) {
+                        return Some(old);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(Slot::new(value));
+                    return None;
+                }
+            }
+        }
+    }
+
+    pub fn remove<Q>(&self, key: &Q, guard: &Guard) -> Option<i64>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.remove(key, guard).map(Slot::retire)
+    }
+}
+
+#[derive(Debug)]
+pub struct CounterIter<'g, K> {
+    iter: Iter<'g, K, Slot>,
+}
+
+impl<'g, K> Iterator for CounterIter<'g, K> {
+    type Item = (&'g K, i64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, slot)| (key, slot.load()))
+    }
+}
+
+impl<K, S> Default for CounterMap<K, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, S> fmt::Debug for CounterMap<K, S>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter(&self.guard())).finish()
+    }
+}
@@ -0,0 +1,500 @@
+use std::{fmt, hint};
+
+use crossbeam_utils::CachePadded;
+
+use crate::util::sync::epoch::{self, unprotected, Atomic, Collector, Guard, Owned, Shared};
+use crate::util::{
+    counter::Counter,
+    equivalent::Equivalent,
+    hashing::{self, KeyHasher, SeededState},
+    lock::{Mutex, MutexGuard},
+    reclaim,
+    sync::atomic::{fence, AtomicU64, Ordering},
+};
+
+const SLOTS: usize = 4;
+const MAX_SEARCH: usize = 1024;
+
+type Located<'g, K, V> = (usize, usize, Shared<'g, Entry<K, V>>);
+
+struct Entry<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+struct Bucket<K, V> {
+    version: AtomicU64,
+    lock: Mutex<()>,
+    slots: [Atomic<Entry<K, V>>; SLOTS],
+}
+
+impl<K, V> Bucket<K, V> {
+    fn new() -> Self {
+        Bucket {
+            version: AtomicU64::new(0),
+            lock: Mutex::new(()),
+            slots: Default::default(),
+        }
+    }
+
+    fn stable_version(&self) -> u64 {
+        loop {
+            let version = self.version.load(Ordering::Acquire);
+            if version & 1 == 0 {
+                return version;
+            }
+            hint::spin_loop();
+        }
+    }
+
+    fn free_slot(&self, guard: &Guard) -> Option<usize> {
+        (0..SLOTS).find(|&slot| self.slots[slot].load(Ordering::Acquire, guard).is_null())
+    }
+
+    fn find<'g>(
+        &self,
+        hash: u64,
+        eq: &mut impl FnMut(&K) -> bool,
+        guard: &'g Guard,
+    ) -> Option<(usize, Shared<'g, Entry<K, V>>)> {
+        self.slots.iter().enumerate().find_map(|(slot, atomic)| {
+            let entry = atomic.load(Ordering::Acquire, guard);
+            match unsafe { entry.as_ref() } {
+                Some(found) if found.hash == hash && eq(&found.key) => Some((slot, entry)),
+                _ => None,
+            }
+        })
+    }
+}
+
+struct CuckooTable<K, V> {
+    buckets: Box<[Bucket<K, V>]>,
+}
+
+impl<K, V> CuckooTable<K, V> {
+    fn new(buckets: usize) -> Self {
+        CuckooTable {
+            buckets: (0..buckets).map(|_| Bucket::new()).collect(),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.buckets.len() * SLOTS
+    }
+
+    fn index(&self, hash: u64) -> usize {
+        hashing::spread(hash) as usize & (self.buckets.len() - 1)
+    }
+
+    fn alt(&self, hash: u64, bucket: usize) -> usize {
+        (bucket ^ ((hash >> 32) | 1) as usize) & (self.buckets.len() - 1)
+    }
+
+    fn lock_pair(&self, a: usize, b: usize) -> (MutexGuard<'_, ()>, Option<MutexGuard<'_, ()>>) {
+        let (low, high) = (a.min(b), a.max(b));
+        let first = self.buckets[low].lock.lock();
+        let second = if low == high {
+            None
+        } else {
+            Some(self.buckets[high].lock.lock())
+        };
+        (first, second)
+    }
+
+    fn find<'g>(
+        &self,
+        hash: u64,
+        mut eq: impl FnMut(&K) -> bool,
+        guard: &'g Guard,
+    ) -> Option<Located<'g, K, V>> {
+        let first = self.index(hash);
+        let second = self.alt(hash, first);
+        loop {
+            let versions = (
+                self.buckets[first].stable_version(),
+                self.buckets[second].stable_version(),
+            );
+            let found = self.buckets[first]
+                .find(hash, &mut eq, guard)
+                .map(|(slot, entry)| (first, slot, entry))
+                .or_else(|| {
+                    self.buckets[second]
+                        .find(hash, &mut eq, guard)
+                        .map(|(slot, entry)| (second, slot, entry))
+                });
+            fence(Ordering::Acquire);
+            if self.buckets[first].version.load(Ordering::Relaxed) == versions.0
+                && self.buckets[second].version.load(Ordering::Relaxed) == versions.1
+            {
+                return found;
+            }
+        }
+    }
+
+    fn cuckoo_path(&self, hash: u64, guard: &Guard) -> Option<Vec<(usize, usize)>> {
+        let first = self.index(hash);
+        let second = self.alt(hash, first);
+        let mut queue = vec![(first, usize::MAX, 0)];
+        if second != first {
+            queue.push((second, usize::MAX, 0));
+        }
+        let mut next = 0;
+        while next < queue.len() {
+            let (bucket, _, _) = queue[next];
+            if let Some(free) = self.buckets[bucket].free_slot(guard) {
+                let mut path = vec![(bucket, free)];
+                let mut node = next;
+                while queue[node].1 != usize::MAX {
+                    let (_, parent, slot) = queue[node];
+                    path.push((queue[parent].0, slot));
+                    node = parent;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if queue.len() < MAX_SEARCH {
+                for slot in 0..SLOTS {
+                    let entry = self.buckets[bucket].slots[slot].load(Ordering::Acquire, guard);
+                    if let Some(victim) = unsafe { entry.as_ref() } {
+                        queue.push((self.alt(victim.hash, bucket), next, slot));
+                    }
+                }
+            }
+            next += 1;
+        }
+        None
+    }
+
+    fn place<'g>(&self, entry: Shared<'g, Entry<K, V>>, guard: &'g Guard) -> bool {
+        let path = match self.cuckoo_path(unsafe { entry.deref() }.hash, guard) {
+            Some(path) => path,
+            None => return false,
+        };
+        for step in path.windows(2).rev() {
+            let ((from, from_slot), (to, to_slot)) = (step[0], step[1]);
+            let moved = self.buckets[from].slots[from_slot].load(Ordering::Relaxed, guard);
+            self.buckets[to].slots[to_slot].store(moved, Ordering::Relaxed);
+        }
+        let (bucket, slot) = path[0];
+        self.buckets[bucket].slots[slot].store(entry, Ordering::Relaxed);
+        true
+    }
+
+    fn entries<'g>(&'g self, guard: &'g Guard) -> impl Iterator<Item = Shared<'g, Entry<K, V>>> {
+        self.buckets
+            .iter()
+            .flat_map(|bucket| bucket.slots.iter())
+            .map(move |slot| slot.load(Ordering::Acquire, guard))
+            .filter(|entry| !entry.is_null())
+    }
+}
+
+fn buckets_for(len: usize) -> usize {
+    (len / SLOTS + 1).next_power_of_two().max(2)
+}
+
+pub struct CuckooMap<K, V, S = SeededState> {
+    table: CachePadded<Atomic<CuckooTable<K, V>>>,
+    count: Counter,
+    build_hasher: S,
+    collector: Collector,
+}
+
+impl<K, V> CuckooMap<K, V> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, SeededState::new())
+    }
+}
+
+impl<K, V> Default for CuckooMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> CuckooMap<K, V, S> {
+    pub fn with_hasher(build_hasher: S) -> Self {
+        Self::with_capacity_and_hasher(0, build_hasher)
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, build_hasher: S) -> Self {
+        CuckooMap {
+            table: CachePadded::new(Atomic::new(CuckooTable::new(buckets_for(capacity)))),
+            count: Counter::new(),
+            build_hasher,
+            collector: epoch::default_collector().clone(),
+        }
+    }
+
+    pub fn with_collector(mut self, collector: Collector) -> Self {
+        self.collector = collector;
+        self
+    }
+
+    pub fn collector(&self) -> &Collector {
+        &self.collector
+    }
+
+    pub fn guard(&self) -> Guard {
+        reclaim::pin(&self.collector)
+    }
+
+    fn check_guard(&self, guard: &Guard) {
+        if let Some(collector) = guard.collector() {
+            assert!(
+                *collector == self.collector,
+                "guard was not created from this map's collector"
+            );
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.build_hasher
+    }
+
+    pub fn len(&self) -> usize {
+        self.count.sum().max(0) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.count.is_positive()
+    }
+
+    pub fn capacity(&self, guard: &Guard) -> usize {
+        self.check_guard(guard);
+        self.table(guard).capacity()
+    }
+
+    fn table<'g>(&self, guard: &'g Guard) -> &'g CuckooTable<K, V> {
+        unsafe { self.table.load(Ordering::Acquire, guard).deref() }
+    }
+
+    pub fn iter<'g>(&'g self, guard: &'g Guard) -> CuckooIter<'g, K, V> {
+        self.check_guard(guard);
+        CuckooIter {
+            table: self.table(guard),
+            index: 0,
+            guard,
+        }
+    }
+
+    pub fn keys<'g>(&'g self, guard: &'g Guard) -> impl Iterator<Item = &'g K> {
+        self.iter(guard).map(|(key, _)| key)
+    }
+
+    pub fn values<'g>(&'g self, guard: &'g Guard) -> impl Iterator<Item = &'g V> {
+        self.iter(guard).map(|(_, value)| value)
+    }
+
+    fn displace(
+        &self,
+        shared: Shared<'_, CuckooTable<K, V>>,
+        path: &[(usize, usize)],
+        guard: &Guard,
+    ) {
+        let table = unsafe { shared.deref() };
+        for step in path.windows(2).rev() {
+            let ((from, from_slot), (to, to_slot)) = (step[0], step[1]);
+            let _locks = table.lock_pair(from, to);
+            if self.table.load(Ordering::Acquire, guard) != shared {
+                return;
+            }
+
+            let entry = table.buckets[from].slots[from_slot].load(Ordering::Acquire, guard);
+            let victim = match unsafe { entry.as_ref() } {
+                Some(victim) => victim,
+                None => continue,
+            };
+            if table.alt(victim.hash, from) != to
+                || !table.buckets[to].slots[to_slot]
+                    .load(Ordering::Acquire, guard)
+                    .is_null()
+            {
+                return;
+            }
+
+            table.buckets[from].version.fetch_add(1, Ordering::AcqRel);
+            table.buckets[to].version.fetch_add(1, Ordering::AcqRel);
+            table.buckets[to].slots[to_slot].store(entry, Ordering::Release);
+            table.buckets[from].slots[from_slot].store(Shared::null(), Ordering::Release);
+            table.buckets[from].version.fetch_add(1, Ordering::Release);
+            table.buckets[to].version.fetch_add(1, Ordering::Release);
+        }
+    }
+
+    fn resize(&self, shared: Shared<'_, CuckooTable<K, V>>, guard: &Guard) {
+        let table = unsafe { shared.deref() };
+        let _locks: Vec<_> = table
+            .buckets
+            .iter()
+            .map(|bucket| bucket.lock.lock())
+            .collect();
+        if self.table.load(Ordering::Acquire, guard) != shared {
+            return;
+        }
+
+        let mut buckets = table.buckets.len() * 2;
+        let new = loop {
+            let new = CuckooTable::new(buckets);
+            if table.entries(guard).all(|entry| new.place(entry, guard)) {
+                break new;
+            }
+            buckets *= 2;
+        };
+
+        self.table.store(Owned::new(new), Ordering::Release);
+        unsafe { guard.defer_destroy(shared) };
+    }
+}
+
+impl<K, V, S> CuckooMap<K, V, S>
+where
+    K: Eq,
+{
+    pub fn get_key_value<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<(&'g K, &'g V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.check_guard(guard);
+        let hash = self.build_hasher.hash_key(key);
+        let (_, _, entry) = self
+            .table(guard)
+            .find(hash, |other| key.equivalent(other), guard)?;
+        let entry = unsafe { entry.deref() };
+        Some((&entry.key, &entry.value))
+    }
+
+    pub fn get<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key, guard).map(|(_, value)| value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key, guard).is_some()
+    }
+
+    pub fn remove<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.check_guard(guard);
+        let hash = self.build_hasher.hash_key(key);
+        loop {
+            let shared = self.table.load(Ordering::Acquire, guard);
+            let table = unsafe { shared.deref() };
+            let first = table.index(hash);
+            let _locks = table.lock_pair(first, table.alt(hash, first));
+            if self.table.load(Ordering::Acquire, guard) != shared {
+                continue;
+            }
+
+            let (bucket, slot, entry) = table.find(hash, |other| key.equivalent(other), guard)?;
+            table.buckets[bucket].slots[slot].store(Shared::null(), Ordering::Release);
+            self.count.add(-1);
+            unsafe { guard.defer_destroy(entry) };
+            return Some(unsafe { &entry.deref().value });
+        }
+    }
+}
+
+impl<K, V, S> CuckooMap<K, V, S>
+where
+    K: Eq,
+    S: KeyHasher<K>,
+{
+    pub fn insert<'g>(&'g self, key: K, value: V, guard: &'g Guard) -> Option<&'g V> {
+        self.check_guard(guard);
+        let hash = self.build_hasher.hash_key(&key);
+        let new = Owned::new(Entry { hash, key, value });
+        loop {
+            let shared = self.table.load(Ordering::Acquire, guard);
+            let table = unsafe { shared.deref() };
+            let first = table.index(hash);
+            let second = table.alt(hash, first);
+            let locks = table.lock_pair(first, second);
+            if self.table.load(Ordering::Acquire, guard) != shared {
+                continue;
+            }
+
+            if let Some((bucket, slot, old)) = table.find(hash, |other| *other == new.key, guard) {
+                table.buckets[bucket].slots[slot].store(new, Ordering::Release);
+                unsafe { guard.defer_destroy(old) };
+                return Some(unsafe { &old.deref().value });
+            }
+
+            for bucket in [first, second] {
+                if let Some(slot) = table.buckets[bucket].free_slot(guard) {
+                    table.buckets[bucket].slots[slot].store(new, Ordering::Release);
+                    self.count.add(1);
+                    return None;
+                }
+            }
+
+            drop(locks);
+            match table.cuckoo_path(hash, guard) {
+                Some(path) => self.displace(shared, &path, guard),
+                None => self.resize(shared, guard),
+            }
+        }
+    }
+}
+
+impl<K, V, S> Drop for CuckooMap<K, V, S> {
+    fn drop(&mut self) {
+        unsafe {
+            let guard = unprotected();
+            let table = self.table.load(Ordering::Relaxed, guard);
+            for entry in table.deref().entries(guard) {
+                drop(entry.into_owned());
+            }
+            drop(table.into_owned());
+        }
+        reclaim::release(&self.collector);
+    }
+}
+
+impl<K, V, S> fmt::Debug for CuckooMap<K, V, S>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guard = self.guard();
+        f.debug_map().entries(self.iter(&guard)).finish()
+    }
+}
+
+pub struct CuckooIter<'g, K, V> {
+    table: &'g CuckooTable<K, V>,
+    index: usize,
+    guard: &'g Guard,
+}
+
+impl<'g, K, V> Iterator for CuckooIter<'g, K, V> {
+    type Item = (&'g K, &'g V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.table.capacity() {
+            let slot = &self.table.buckets[self.index / SLOTS].slots[self.index % SLOTS];
+            self.index += 1;
+            if let Some(entry) = unsafe { slot.load(Ordering::Acquire, self.guard).as_ref() } {
+                return Some((&entry.key, &entry.value));
+            }
+        }
+        None
+    }
+}
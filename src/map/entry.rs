@@ -0,0 +1,488 @@
+use crate::util::sync::epoch::{Guard, Owned, Shared};
+use crate::{
+    core::{
+        bin_entry::{tree_node::TreeNode, BinEntry},
+        node::Node,
+        table::Table,
+    },
+    util::{hashing::KeyHasher, lock::MutexGuard, reclaim::Retire, sync::atomic::Ordering},
+};
+
+use super::{listener::Event, ConcurrentHashMap};
+
+pub enum Entry<'g, K, V, S> {
+    Occupied(OccupiedEntry<'g, K, V, S>),
+    Vacant(VacantEntry<'g, K, V, S>),
+}
+
+pub struct OccupiedEntry<'g, K, V, S> {
+    map: &'g ConcurrentHashMap<K, V, S>,
+    table: &'g Table<K, V>,
+    i: usize,
+    bin: Shared<'g, BinEntry<K, V>>,
+    pred: Shared<'g, BinEntry<K, V>>,
+    element: Shared<'g, BinEntry<K, V>>,
+    guard: &'g Guard,
+    lock: Option<MutexGuard<'g, ()>>,
+    replaced: Option<(Shared<'g, V>, Shared<'g, V>)>,
+}
+
+pub struct KeyGuard<'g, K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    state: Option<KeyState<'g, K, V, S>>,
+}
+
+enum KeyState<'g, K, V, S> {
+    Occupied(OccupiedEntry<'g, K, V, S>),
+    Vacant(VacantEntry<'g, K, V, S>, Option<Shared<'g, V>>),
+}
+
+pub struct VacantEntry<'g, K, V, S> {
+    map: &'g ConcurrentHashMap<K, V, S>,
+    hash: u64,
+    key: K,
+    slot: Slot<'g, K, V>,
+    guard: &'g Guard,
+}
+
+#[derive(Debug)]
+pub struct OccupiedError<'g, V> {
+    pub current: &'g V,
+    pub value: V,
+}
+
+#[derive(Debug)]
+pub struct CompareExchangeError<'g, V> {
+    pub current: Option<&'g V>,
+    pub new: V,
+}
+
+pub struct Slot<'g, K, V> {
+    table: &'g Table<K, V>,
+    i: usize,
+    bin: Shared<'g, BinEntry<K, V>>,
+    tail: Shared<'g, BinEntry<K, V>>,
+    bin_count: usize,
+    reserved: bool,
+    guard: &'g Guard,
+    lock: Option<MutexGuard<'g, ()>>,
+}
+
+impl<'g, K, V> Slot<'g, K, V> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        table: &'g Table<K, V>,
+        i: usize,
+        bin: Shared<'g, BinEntry<K, V>>,
+        tail: Shared<'g, BinEntry<K, V>>,
+        bin_count: usize,
+        reserved: bool,
+        guard: &'g Guard,
+        lock: Option<MutexGuard<'g, ()>>,
+    ) -> Self {
+        Slot {
+            table,
+            i,
+            bin,
+            tail,
+            bin_count,
+            reserved,
+            guard,
+            lock,
+        }
+    }
+}
+
+impl<K, V> Drop for Slot<'_, K, V> {
+    fn drop(&mut self) {
+        if self.reserved {
+            self.table.store_bin(self.i, Shared::null());
+            unsafe { self.guard.retire(self.bin, &self.table.pending) };
+        }
+    }
+}
+
+impl<'g, K, V, S> Entry<'g, K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, value: V) -> &'g V {
+        self.or_insert_with(|| value)
+    }
+
+    pub fn or_insert_with<F>(self, f: F) -> &'g V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.get(),
+            Entry::Vacant(entry) => entry.insert(f()),
+        }
+    }
+
+    pub fn or_insert_with_key<F>(self, f: F) -> &'g V
+    where
+        F: FnOnce(&K) -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.get(),
+            Entry::Vacant(entry) => {
+                let value = f(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    pub fn or_default(self) -> &'g V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        V: Clone,
+        F: FnOnce(&mut V),
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                let mut value = entry.get().clone();
+                f(&mut value);
+                entry.insert(value);
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+impl<'g, K, V, S> OccupiedEntry<'g, K, V, S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        map: &'g ConcurrentHashMap<K, V, S>,
+        table: &'g Table<K, V>,
+        i: usize,
+        bin: Shared<'g, BinEntry<K, V>>,
+        pred: Shared<'g, BinEntry<K, V>>,
+        element: Shared<'g, BinEntry<K, V>>,
+        guard: &'g Guard,
+        lock: MutexGuard<'g, ()>,
+    ) -> Self {
+        OccupiedEntry {
+            map,
+            table,
+            i,
+            bin,
+            pred,
+            element,
+            guard,
+            lock: Some(lock),
+            replaced: None,
+        }
+    }
+
+    pub fn node(&self) -> &'g Node<K, V> {
+        unsafe { self.element.deref() }.as_any_node().unwrap()
+    }
+
+    pub fn key(&self) -> &'g K {
+        &self.node().key
+    }
+
+    pub fn get(&self) -> &'g V {
+        unsafe { self.node().value.load(Ordering::SeqCst, self.guard).deref() }
+    }
+}
+
+impl<'g, K, V, S> OccupiedEntry<'g, K, V, S>
+where
+    K: Eq + Clone,
+{
+    pub fn insert(&mut self, value: V) -> &'g V {
+        let value = Owned::new(value).into_shared(self.guard);
+        let old = self.replace(value);
+        unsafe { old.deref() }
+    }
+
+    pub fn replace(&mut self, value: Shared<'g, V>) -> Shared<'g, V> {
+        let node = self.node();
+        self.map.bump_generation(node);
+        let old = node.value.swap(value, Ordering::SeqCst, self.guard);
+        self.map.bump_generation(node);
+        self.map.record_write(node);
+        self.map.reweigh(node, unsafe { value.deref() });
+        unsafe { self.guard.retire(old, self.map.pending()) };
+        self.replaced = match self.replaced {
+            Some((first, _)) => Some((first, value)),
+            None => Some((old, value)),
+        };
+        old
+    }
+
+    pub fn remove(self) -> &'g V {
+        self.remove_entry().1
+    }
+
+    pub fn remove_entry(self) -> (&'g K, &'g V) {
+        let map = self.map;
+        let (key, value) = self.unlink();
+        map.listeners.notify(&Event::Remove { key, value });
+        (key, value)
+    }
+
+    pub fn unlink(mut self) -> (&'g K, &'g V) {
+        let guard = self.guard;
+        let node = self.node();
+        let value = node.value.swap(Shared::null(), Ordering::SeqCst, guard);
+        unsafe { guard.retire(value, self.map.pending()) };
+        self.map.discharge(node);
+
+        match unsafe { self.bin.deref() } {
+            BinEntry::Node(_) => {
+                let next = node.next.load(Ordering::SeqCst, guard);
+                if self.pred.is_null() {
+                    self.table.store_bin(self.i, next);
+                } else {
+                    unsafe { self.pred.deref() }
+                        .as_node()
+                        .unwrap()
+                        .next
+                        .store(next, Ordering::SeqCst);
+                }
+                unsafe { self.map.retire_node(self.element, guard) };
+            }
+            BinEntry::Tree(tree_bin) => {
+                if unsafe {
+                    tree_bin.remove_tree_node(
+                        self.element,
+                        false,
+                        self.map.untreeify_threshold,
+                        &self.map.contention_policy,
+                        guard,
+                    )
+                } {
+                    let first = tree_bin.first.load(Ordering::SeqCst, guard);
+                    self.table.store_bin(self.i, Table::untreeify(first, guard));
+
+                    let mut element = first;
+                    while !element.is_null() {
+                        let next = unsafe { TreeNode::get_tree_node(element) }
+                            .node
+                            .next
+                            .load(Ordering::SeqCst, guard);
+                        unsafe { guard.retire(element, self.map.pending()) };
+                        element = next;
+                    }
+                    unsafe { guard.retire(self.bin, self.map.pending()) };
+                }
+            }
+            BinEntry::TreeNode(_) | BinEntry::Moved => unreachable!(),
+        }
+
+        let map = self.map;
+        self.replaced = None;
+        drop(self);
+        map.add_count(-1, guard);
+        (&node.key, unsafe { value.deref() })
+    }
+}
+
+impl<K, V, S> Drop for OccupiedEntry<'_, K, V, S> {
+    fn drop(&mut self) {
+        drop(self.lock.take());
+        if let Some((old, new)) = self.replaced.take() {
+            self.map.listeners.notify(&Event::Replace {
+                key: &self.node().key,
+                old: unsafe { old.deref() },
+                new: unsafe { new.deref() },
+            });
+        }
+    }
+}
+
+impl<'g, K, V, S> VacantEntry<'g, K, V, S> {
+    pub fn new(
+        map: &'g ConcurrentHashMap<K, V, S>,
+        hash: u64,
+        key: K,
+        slot: Slot<'g, K, V>,
+        guard: &'g Guard,
+    ) -> Self {
+        VacantEntry {
+            map,
+            hash,
+            key,
+            slot,
+            guard,
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+}
+
+impl<'g, K, V, S> VacantEntry<'g, K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    pub fn insert(self, value: V) -> &'g V {
+        let value = Owned::new(value).into_shared(self.guard);
+        self.insert_shared(value);
+        unsafe { value.deref() }
+    }
+
+    pub fn insert_shared(self, value: Shared<'g, V>) {
+        let map = self.map;
+        let guard = self.guard;
+        let event_key = if map.listeners.is_empty() {
+            None
+        } else {
+            Some(self.key.clone())
+        };
+        self.link(value);
+        map.maintain(guard);
+        if let Some(key) = event_key {
+            map.listeners.notify(&Event::Insert {
+                key: &key,
+                value: unsafe { value.deref() },
+            });
+        }
+    }
+
+    fn link(self, value: Shared<'g, V>) {
+        let VacantEntry {
+            map,
+            hash,
+            key,
+            mut slot,
+            guard,
+        } = self;
+        let table = slot.table;
+        let i = slot.i;
+        let node = map.new_node(hash, key, value);
+        let weight = map.node_weight(node.as_node().unwrap());
+
+        if slot.reserved {
+            table.store_bin(i, node.into_shared(guard));
+            unsafe { guard.retire(slot.bin, map.pending()) };
+            slot.reserved = false;
+            drop(slot);
+            map.charge(weight);
+            map.add_count(1, guard);
+            return;
+        }
+
+        match unsafe { slot.bin.deref() } {
+            BinEntry::Node(_) => {
+                unsafe { slot.tail.deref() }
+                    .as_node()
+                    .unwrap()
+                    .next
+                    .store(node, Ordering::SeqCst);
+                drop(slot.lock.take());
+
+                table.record_probes(i, slot.bin_count as u64);
+                if map.should_treeify(table, i, slot.bin_count) {
+                    map.treeify_or_resize(table, i, guard);
+                }
+            }
+            BinEntry::Tree(tree_bin) => {
+                let node = match *node.into_box() {
+                    BinEntry::Node(node) => node,
+                    _ => unreachable!(),
+                };
+                let p = tree_bin.put_tree_val(node, &map.contention_policy, guard);
+                assert!(p.is_null());
+                drop(slot);
+            }
+            BinEntry::TreeNode(_) | BinEntry::Moved => unreachable!(),
+        }
+        map.charge(weight);
+        map.add_count(1, guard);
+    }
+}
+
+impl<'g, K, V, S> KeyGuard<'g, K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    pub fn new(entry: Entry<'g, K, V, S>) -> Self {
+        let state = match entry {
+            Entry::Occupied(entry) => KeyState::Occupied(entry),
+            Entry::Vacant(entry) => KeyState::Vacant(entry, None),
+        };
+        KeyGuard { state: Some(state) }
+    }
+
+    fn state(&self) -> &KeyState<'g, K, V, S> {
+        self.state.as_ref().unwrap()
+    }
+
+    pub fn key(&self) -> &K {
+        match self.state() {
+            KeyState::Occupied(entry) => entry.key(),
+            KeyState::Vacant(entry, _) => entry.key(),
+        }
+    }
+
+    pub fn get(&self) -> Option<&'g V> {
+        match self.state() {
+            KeyState::Occupied(entry) => Some(entry.get()),
+            KeyState::Vacant(_, pending) => pending.map(|value| unsafe { value.deref() }),
+        }
+    }
+
+    pub fn insert(&mut self, value: V) -> Option<&'g V> {
+        match self.state.as_mut().unwrap() {
+            KeyState::Occupied(entry) => Some(entry.insert(value)),
+            KeyState::Vacant(entry, pending) => {
+                let guard = entry.guard;
+                let old = pending.replace(Owned::new(value).into_shared(guard))?;
+                unsafe { guard.retire(old, entry.map.pending()) };
+                Some(unsafe { old.deref() })
+            }
+        }
+    }
+
+    pub fn remove(mut self) -> Option<&'g V> {
+        match self.state.take().unwrap() {
+            KeyState::Occupied(entry) => Some(entry.remove()),
+            KeyState::Vacant(entry, pending) => {
+                let old = pending?;
+                unsafe { entry.guard.retire(old, entry.map.pending()) };
+                Some(unsafe { old.deref() })
+            }
+        }
+    }
+}
+
+impl<K, V, S> Drop for KeyGuard<'_, K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    fn drop(&mut self) {
+        if let Some(KeyState::Vacant(entry, Some(value))) = self.state.take() {
+            entry.insert_shared(value);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::{fmt, slice};
+
+use crate::util::{
+    equivalent::Equivalent,
+    hashing::{self, KeyHasher, SeededState},
+};
+
+pub struct FrozenMap<K, V, S = SeededState> {
+    offsets: Box<[usize]>,
+    entries: Box<[(u64, K, V)]>,
+    build_hasher: S,
+}
+
+impl<K, V, S> FrozenMap<K, V, S> {
+    pub fn new(mut entries: Vec<(u64, K, V)>, build_hasher: S) -> Self {
+        let mask = entries.len().next_power_of_two() - 1;
+        entries.sort_unstable_by_key(|entry| entry.0 as usize & mask);
+
+        let mut offsets = vec![0; mask + 2];
+        for entry in &entries {
+            offsets[(entry.0 as usize & mask) + 1] += 1;
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        FrozenMap {
+            offsets: offsets.into_boxed_slice(),
+            entries: entries.into_boxed_slice(),
+            build_hasher,
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.build_hasher
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> FrozenIter<'_, K, V> {
+        FrozenIter {
+            entries: self.entries.iter(),
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    fn bin(&self, hash: u64) -> &[(u64, K, V)] {
+        let i = hash as usize & (self.offsets.len() - 2);
+        &self.entries[self.offsets[i]..self.offsets[i + 1]]
+    }
+}
+
+impl<K, V, S> FrozenMap<K, V, S>
+where
+    K: Eq,
+{
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        let hash = hashing::spread(self.build_hasher.hash_key(key));
+        self.bin(hash)
+            .iter()
+            .find(|entry| entry.0 == hash && key.equivalent(&entry.1))
+            .map(|entry| (&entry.1, &entry.2))
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key).is_some()
+    }
+}
+
+impl<K, V, S> fmt::Debug for FrozenMap<K, V, S>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a FrozenMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = FrozenIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[derive(Debug)]
+pub struct FrozenIter<'a, K, V> {
+    entries: slice::Iter<'a, (u64, K, V)>,
+}
+
+impl<'a, K, V> Iterator for FrozenIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|entry| (&entry.1, &entry.2))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for FrozenIter<'_, K, V> {}
@@ -0,0 +1,5 @@
+use crate::util::hashing::IntHasher;
+
+use super::ConcurrentHashMap;
+
+pub type IntMap<V, K = u64> = ConcurrentHashMap<K, V, IntHasher>;
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::util::hashing::{KeyHasher, SeededState};
+use crate::util::sync::epoch::Guard;
+
+use super::{builder::Builder, ConcurrentHashMap};
+
+pub struct Interner<S = SeededState> {
+    strings: ConcurrentHashMap<Arc<str>, (), S>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::with_hasher(SeededState::new())
+    }
+}
+
+impl<S> Interner<S> {
+    pub fn with_hasher(build_hasher: S) -> Self {
+        Interner {
+            strings: Builder::new()
+                .read_mostly(true)
+                .build_with_hasher(build_hasher),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl<S> Interner<S>
+where
+    S: KeyHasher<str> + KeyHasher<Arc<str>>,
+{
+    pub fn intern(&self, string: &str) -> Arc<str> {
+        let guard = self.strings.guard();
+        self.intern_with_guard(string, &guard)
+    }
+
+    pub fn intern_with_guard(&self, string: &str, guard: &Guard) -> Arc<str> {
+        if let Some(node) = self.strings.find_node(string, guard) {
+            return node.key.clone();
+        }
+
+        self.strings.insert(Arc::from(string), (), guard);
+        self.strings.find_node(string, guard).unwrap().key.clone()
+    }
+
+    pub fn intern_in_map<V, T>(
+        &self,
+        map: &ConcurrentHashMap<Arc<str>, V, T>,
+        key: &str,
+        value: V,
+        guard: &Guard,
+    ) -> Arc<str>
+    where
+        T: KeyHasher<Arc<str>>,
+    {
+        let key = self.intern_with_guard(key, guard);
+        map.insert(key.clone(), value, guard);
+        key
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,62 @@
+use std::{fmt, sync::Arc};
+
+#[derive(Debug)]
+pub enum Event<'a, K, V> {
+    Insert { key: &'a K, value: &'a V },
+    Replace { key: &'a K, old: &'a V, new: &'a V },
+    Remove { key: &'a K, value: &'a V },
+}
+
+pub type Listener<K, V> = Arc<dyn Fn(&Event<'_, K, V>) + Send + Sync>;
+
+pub struct Listeners<K, V> {
+    listeners: Vec<Listener<K, V>>,
+}
+
+impl<K, V> Listeners<K, V> {
+    pub fn new() -> Self {
+        Listeners {
+            listeners: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, listener: Listener<K, V>) {
+        self.listeners.push(listener);
+    }
+
+    pub fn len(&self) -> usize {
+        self.listeners.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+
+    pub fn notify(&self, event: &Event<'_, K, V>) {
+        for listener in &self.listeners {
+            listener(event);
+        }
+    }
+}
+
+impl<K, V> Clone for Listeners<K, V> {
+    fn clone(&self) -> Self {
+        Listeners {
+            listeners: self.listeners.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for Listeners<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> fmt::Debug for Listeners<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Listeners")
+            .field("len", &self.len())
+            .finish()
+    }
+}
@@ -0,0 +1,210 @@
+use crate::util::sync::epoch::{unprotected, Owned, Shared};
+use crate::{
+    core::{
+        bin_entry::{tree_node::TreeNode, BinEntry},
+        table::Table,
+    },
+    util::{hashing::KeyHasher, sync::atomic::Ordering},
+};
+
+use super::{ConcurrentHashMap, MAXIMUM_CAPACITY};
+
+pub struct Loader<'m, K, V, S> {
+    map: &'m mut ConcurrentHashMap<K, V, S>,
+    existing: usize,
+    loaded: isize,
+}
+
+impl<'m, K, V, S> Loader<'m, K, V, S> {
+    pub fn new(map: &'m mut ConcurrentHashMap<K, V, S>) -> Self {
+        let mut loader = Loader {
+            existing: map.len(),
+            map,
+            loaded: 0,
+        };
+        let guard = unsafe { unprotected() };
+        if let Some(table) = unsafe { loader.map.table.load(Ordering::SeqCst, guard).as_ref() } {
+            let next = table.next_table.load(Ordering::SeqCst, guard);
+            if let Some(next) = unsafe { next.as_ref() } {
+                loader.rebuild(next.capacity());
+            }
+        }
+        loader
+    }
+
+    pub fn loaded(&self) -> usize {
+        self.loaded.max(0) as usize
+    }
+
+    fn grow(&mut self) {
+        let guard = unsafe { unprotected() };
+        let table = unsafe { self.map.table.load(Ordering::SeqCst, guard).deref() };
+        let size = self.existing + self.loaded();
+        let mut capacity = table.capacity();
+        while size >= capacity - (capacity >> 2) && capacity < MAXIMUM_CAPACITY {
+            capacity <<= 1;
+        }
+        if capacity != table.capacity() {
+            self.rebuild(capacity);
+        }
+    }
+
+    fn rebuild(&mut self, capacity: usize) {
+        let guard = unsafe { unprotected() };
+        let map = &*self.map;
+        let mut table = map.table.swap(Shared::null(), Ordering::SeqCst, guard);
+        let new = Table::new(
+            capacity,
+            unsafe { table.deref() }.probes.is_some(),
+            map.pending().clone(),
+        );
+        while !table.is_null() {
+            let owned = unsafe { table.into_owned() };
+            table = owned
+                .next_table
+                .swap(Shared::null(), Ordering::SeqCst, guard);
+            for bin in owned.bins.iter() {
+                let mut element = bin.swap(Shared::null(), Ordering::Relaxed, guard);
+                while !element.is_null() {
+                    let (node, next) = match unsafe { element.deref() } {
+                        BinEntry::Moved => break,
+                        BinEntry::Tree(tree_bin) => {
+                            let first = tree_bin.first.load(Ordering::Relaxed, guard);
+                            drop(unsafe { element.into_owned() });
+                            element = first;
+                            continue;
+                        }
+                        BinEntry::Node(node) => (
+                            unsafe { element.into_owned() },
+                            node.next.swap(Shared::null(), Ordering::Relaxed, guard),
+                        ),
+                        BinEntry::TreeNode(_) => {
+                            match *unsafe { element.into_owned() }.into_box() {
+                                BinEntry::TreeNode(tree_node) => {
+                                    let next = tree_node.node.next.swap(
+                                        Shared::null(),
+                                        Ordering::Relaxed,
+                                        guard,
+                                    );
+                                    (Owned::new(BinEntry::Node(tree_node.node)), next)
+                                }
+                                _ => unreachable!(),
+                            }
+                        }
+                    };
+                    let i = new.bin_index(node.as_node().unwrap().hash);
+                    node.as_node()
+                        .unwrap()
+                        .next
+                        .store(new.bin(i, guard), Ordering::Relaxed);
+                    new.bins[i].store(node, Ordering::Relaxed);
+                    element = next;
+                }
+            }
+        }
+        map.table.store(Owned::new(new), Ordering::SeqCst);
+    }
+}
+
+impl<'m, K, V, S> Loader<'m, K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    pub fn insert(&mut self, key: K, value: V) {
+        let guard = unsafe { unprotected() };
+        let hash = self.map.hash(&key);
+        let node = self
+            .map
+            .new_node(hash, key, Owned::new(value).into_shared(guard));
+        self.insert_node(node);
+    }
+
+    pub fn insert_node(&mut self, node: Owned<BinEntry<K, V>>) {
+        let guard = unsafe { unprotected() };
+        let map = &*self.map;
+        let hash = node.as_node().unwrap().hash;
+        let value = node.as_node().unwrap().value.load(Ordering::Relaxed, guard);
+        let weight = map.node_weight(node.as_node().unwrap());
+        let table = map.init_table(guard);
+        let i = table.bin_index(hash);
+        let bin = table.bin(i, guard);
+
+        match unsafe { bin.as_ref() } {
+            None => table.bins[i].store(node, Ordering::Relaxed),
+            Some(BinEntry::Node(_)) => {
+                let new_node = node.as_node().unwrap();
+                let mut element = bin;
+                loop {
+                    let element_deref = unsafe { element.deref() }.as_node().unwrap();
+                    if element_deref.hash == hash && element_deref.key == new_node.key {
+                        map.bump_generation(element_deref);
+                        let old = element_deref.value.swap(value, Ordering::Relaxed, guard);
+                        map.bump_generation(element_deref);
+                        map.record_write(element_deref);
+                        map.recharge(element_deref, weight);
+                        unsafe { drop(old.into_owned()) };
+                        return;
+                    }
+
+                    let next = element_deref.next.load(Ordering::Relaxed, guard);
+                    if next.is_null() {
+                        element_deref.next.store(node, Ordering::Relaxed);
+                        break;
+                    }
+                    element = next;
+                }
+            }
+            Some(BinEntry::Tree(tree_bin)) => {
+                let node = match *node.into_box() {
+                    BinEntry::Node(node) => node,
+                    _ => unreachable!(),
+                };
+                let p = tree_bin.put_tree_val(node, &map.contention_policy, guard);
+                if !p.is_null() {
+                    let existing = &unsafe { TreeNode::get_tree_node(p) }.node;
+                    map.bump_generation(existing);
+                    let old = existing.value.swap(value, Ordering::Relaxed, guard);
+                    map.bump_generation(existing);
+                    map.record_write(existing);
+                    map.recharge(existing, weight);
+                    unsafe { drop(old.into_owned()) };
+                    return;
+                }
+            }
+            Some(BinEntry::TreeNode(_)) | Some(BinEntry::Moved) => unreachable!(),
+        }
+        map.charge(weight);
+        self.loaded += 1;
+        self.grow();
+    }
+
+    pub fn seal(self) {
+        let guard = unsafe { unprotected() };
+        let map = &*self.map;
+        map.count.add(self.loaded);
+
+        let table = match unsafe { map.table.load(Ordering::SeqCst, guard).as_ref() } {
+            Some(table) => table,
+            None => return,
+        };
+        for i in 0..table.capacity() {
+            let mut bin_count = 0;
+            let mut element = table.bin(i, guard);
+            while let Some(BinEntry::Node(node)) = unsafe { element.as_ref() } {
+                bin_count += 1;
+                element = node.next.load(Ordering::Relaxed, guard);
+            }
+            if bin_count == 0 {
+                continue;
+            }
+
+            table.record_probes(i, bin_count as u64);
+            if map.should_treeify(table, i, bin_count)
+                && table.capacity() >= map.min_treeify_capacity
+            {
+                table.treeify_bin(i, &map.contention_policy, guard);
+            }
+        }
+    }
+}
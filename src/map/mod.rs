@@ -0,0 +1,2839 @@
+use std::{
+    borrow::Borrow,
+    collections::VecDeque,
+    convert::Infallible,
+    fmt,
+    hash::Hash,
+    iter::FromIterator,
+    ptr,
+    sync::{
+        mpsc::{SendError, Sender},
+        Arc, OnceLock,
+    },
+    time::Duration,
+};
+
+use crossbeam_utils::CachePadded;
+#[cfg(feature = "rayon")]
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator};
+
+use crate::util::sync::epoch::{self, Atomic, Collector, Guard, Owned, Shared};
+use crate::{
+    core::{
+        bin_entry::{tree_bin::TreeBin, tree_node::TreeNode, BinEntry},
+        node::Node,
+        table::{Table, TableStats},
+    },
+    iter::{traverser::NodeIter, HashedIter, IntoIter, Iter, Keys, Values},
+    util::{
+        clock,
+        contention_policy::ContentionTracker,
+        counter::Counter,
+        equivalent::Equivalent,
+        eviction_policy::EvictionPolicy,
+        expiry::Expiry,
+        hashing::{self, KeyHasher, SeededState},
+        lease::{Lease, LeaseError, LeasePolicy},
+        lock::{Mutex, MutexGuard},
+        pool::Pool,
+        reclaim::{self, Pending, Retire},
+        reservation::Reservations,
+        sync::{
+            atomic::{AtomicU64, AtomicUsize, Ordering},
+            thread,
+        },
+        treeify_policy::{CollisionEstimate, TreeifyPolicy},
+        version::VersionError,
+    },
+};
+
+#[cfg(feature = "rayon")]
+use crate::iter::par::{ParIter, ParKeys, ParValues};
+#[cfg(feature = "contention-stats")]
+use crate::util::contention_policy::ContentionStats;
+
+use self::{
+    builder::Builder,
+    entry::{
+        CompareExchangeError, Entry, KeyGuard, OccupiedEntry, OccupiedError, Slot, VacantEntry,
+    },
+    frozen::FrozenMap,
+    listener::{Event, Listeners},
+    loader::Loader,
+    pinned::{HashMapRef, Pinned},
+    raw_entry::{RawEntryBuilder, RawEntryBuilderMut},
+};
+
+#[cfg(feature = "rkyv")]
+pub mod archive;
+pub mod backend;
+pub mod builder;
+pub mod bytes_map;
+pub mod counter_map;
+pub mod cuckoo;
+pub mod entry;
+pub mod frozen;
+pub mod int_map;
+pub mod interner;
+pub mod listener;
+pub mod loader;
+pub mod multimap;
+pub mod pinned;
+pub mod raw_entry;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod set;
+pub mod swiss;
+pub mod value_pool;
+
+const DEFAULT_CAPACITY: usize = 16;
+const TREEIFY_THRESHOLD: usize = 8;
+const UNTREEIFY_THRESHOLD: usize = 6;
+const MIN_TREEIFY_CAPACITY: usize = 64;
+const MAXIMUM_CAPACITY: usize = 1 << 30;
+const MIN_TRANSFER_STRIDE: usize = 16;
+const DEFAULT_DEBUG_LIMIT: usize = 64;
+const FLUSH_STALL_ROUNDS: usize = 4;
+const DRAIN_STALL_TIMEOUT: Duration = Duration::from_millis(100);
+const EVICTION_SAMPLES: usize = 8;
+const ADVERSARIAL_TREEIFY_THRESHOLD: usize = 2;
+
+pub type ResizeHelper<K, V, S> =
+    for<'g> fn(&'g ConcurrentHashMap<K, V, S>, &'g Table<K, V>, &'g Guard) -> &'g Table<K, V>;
+
+pub type Weigher<K, V> = Arc<dyn Fn(&K, &V) -> u32 + Send + Sync>;
+
+pub type BinLock<'g, K, V> = (&'g Table<K, V>, usize, MutexGuard<'g, ()>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrainError<T> {
+    Disconnected(SendError<T>),
+    Stalled { sent: usize, pending: usize },
+}
+
+struct Drained<T> {
+    ready: VecDeque<T>,
+    sender: Option<Sender<T>>,
+}
+
+impl<T> Drained<T> {
+    fn new() -> Self {
+        Self {
+            ready: VecDeque::new(),
+            sender: None,
+        }
+    }
+
+    fn deliver(&mut self, entry: T) {
+        match self.sender {
+            Some(ref sender) => {
+                let _ = sender.send(entry);
+            }
+            None => self.ready.push_back(entry),
+        }
+    }
+}
+
+pub struct ConcurrentHashMap<K, V, S = SeededState> {
+    table: CachePadded<Atomic<Table<K, V>>>,
+    initial_capacity: usize,
+    count: Counter,
+    build_hasher: S,
+    read_mostly: bool,
+    treeify_policy: TreeifyPolicy,
+    lease_policy: LeasePolicy,
+    contention_policy: ContentionTracker,
+    versioned: bool,
+    generations: CachePadded<AtomicU64>,
+    reservations: Reservations<K>,
+    debug_limit: usize,
+    collector: Collector,
+    resize_stride: Option<usize>,
+    resize_helper: OnceLock<ResizeHelper<K, V, S>>,
+    treeify_threshold: usize,
+    untreeify_threshold: usize,
+    min_treeify_capacity: usize,
+    shrink_fraction: Option<f64>,
+    pending: OnceLock<Pending>,
+    node_pool: Option<Arc<Pool<BinEntry<K, V>>>>,
+    listeners: Listeners<K, V>,
+    expiry: Expiry,
+    sweep_cursor: AtomicUsize,
+    max_capacity: Option<usize>,
+    eviction_policy: EvictionPolicy,
+    evict_cursor: AtomicUsize,
+    weigher: Option<Weigher<K, V>>,
+    max_weight: u64,
+    total_weight: Counter,
+    collisions: CollisionEstimate,
+}
+
+impl<K, V> ConcurrentHashMap<K, V> {
+    pub fn new() -> Self {
+        Builder::new().build()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Builder::new().capacity(capacity).build()
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_hasher(SeededState::with_seed(seed))
+    }
+}
+
+impl<K, V, S> ConcurrentHashMap<K, V, S> {
+    pub fn with_hasher(build_hasher: S) -> Self {
+        Builder::new().build_with_hasher(build_hasher)
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, build_hasher: S) -> Self {
+        Builder::new()
+            .capacity(capacity)
+            .build_with_hasher(build_hasher)
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.build_hasher
+    }
+
+    pub fn collector(&self) -> &Collector {
+        &self.collector
+    }
+
+    pub fn guard(&self) -> Guard {
+        reclaim::pin(&self.collector)
+    }
+
+    fn help_resize(&self, table: &Table<K, V>, guard: &Guard) {
+        if table.next_table.load(Ordering::SeqCst, guard).is_null() {
+            return;
+        }
+        if let Some(helper) = self.resize_helper.get() {
+            helper(self, table, guard);
+        }
+    }
+
+    pub fn try_advance(&self) {
+        self.guard().flush();
+    }
+
+    pub fn flush(&self) -> usize {
+        let mut pending = self.pending_reclamation();
+        let mut stalled = 0;
+        while pending > 0 && stalled < FLUSH_STALL_ROUNDS {
+            self.try_advance();
+            let remaining = self.pending_reclamation();
+            if remaining < pending {
+                stalled = 0;
+            } else {
+                stalled += 1;
+            }
+            pending = remaining;
+        }
+        pending
+    }
+
+    #[cfg(feature = "contention-stats")]
+    pub fn contention_stats(&self) -> ContentionStats {
+        self.contention_policy.stats()
+    }
+
+    pub fn pending_reclamation(&self) -> usize {
+        self.pending.get().map_or(0, Pending::get)
+    }
+
+    fn pending(&self) -> &Pending {
+        self.pending.get_or_init(Pending::new)
+    }
+
+    unsafe fn retire_node(&self, node: Shared<'_, BinEntry<K, V>>, guard: &Guard) {
+        match self.node_pool {
+            Some(ref pool) => guard.recycle(node, pool, self.pending()),
+            None => guard.retire(node, self.pending()),
+        }
+    }
+
+    fn check_guard(&self, guard: &Guard) {
+        if let Some(collector) = guard.collector() {
+            assert!(
+                *collector == self.collector,
+                "guard was not created from this map's collector"
+            );
+        }
+    }
+
+    pub fn pinned<G>(&self, guard: G) -> Pinned<'_, K, V, S, G>
+    where
+        G: Borrow<Guard>,
+    {
+        Pinned::new(self, guard)
+    }
+
+    pub fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&Pinned<'_, K, V, S, &Guard>) -> R,
+    {
+        let guard = self.guard();
+        f(&Pinned::new(self, &guard))
+    }
+
+    pub fn pin(&self) -> HashMapRef<'_, K, V, S> {
+        Pinned::new(self, self.guard())
+    }
+
+    pub fn with_flushed<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&Pinned<'_, K, V, S, &Guard>) -> R,
+    {
+        let guard = self.guard();
+        let result = f(&Pinned::new(self, &guard));
+        guard.flush();
+        result
+    }
+
+    pub fn hashed_iter<'g>(&'g self, guard: &'g Guard) -> HashedIter<'g, K, V> {
+        HashedIter::new(self.nodes(guard), guard)
+    }
+
+    pub fn iter<'g>(&'g self, guard: &'g Guard) -> Iter<'g, K, V> {
+        Iter::new(self.hashed_iter(guard))
+    }
+
+    pub fn weighted_size(&self) -> u64 {
+        self.total_weight.sum().max(0) as u64
+    }
+
+    pub fn freeze(mut self) -> FrozenMap<K, V, S>
+    where
+        S: KeyHasher<K> + Clone,
+    {
+        let mut entries = Vec::with_capacity(self.len());
+        self.teardown(|key, value| entries.push((key, value)));
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| (self.hash(&key), key, value))
+            .collect();
+        FrozenMap::new(entries, self.build_hasher.clone())
+    }
+
+    pub fn keys<'g>(&'g self, guard: &'g Guard) -> Keys<'g, K, V> {
+        Keys::new(self.iter(guard))
+    }
+
+    pub fn values<'g>(&'g self, guard: &'g Guard) -> Values<'g, K, V> {
+        Values::new(self.iter(guard))
+    }
+
+    #[cfg(feature = "rayon")]
+    pub fn par_iter<'g>(&'g self, guard: &'g Guard) -> ParIter<'g, K, V> {
+        self.check_guard(guard);
+        ParIter::new(unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() })
+    }
+
+    #[cfg(feature = "rayon")]
+    pub fn par_keys<'g>(&'g self, guard: &'g Guard) -> ParKeys<'g, K, V> {
+        ParKeys::new(self.par_iter(guard))
+    }
+
+    #[cfg(feature = "rayon")]
+    pub fn par_values<'g>(&'g self, guard: &'g Guard) -> ParValues<'g, K, V> {
+        ParValues::new(self.par_iter(guard))
+    }
+
+    fn nodes<'g>(&'g self, guard: &'g Guard) -> NodeIter<'g, K, V> {
+        self.check_guard(guard);
+        NodeIter::new(
+            unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() },
+            guard,
+        )
+        .skip_expired(self.expiry)
+    }
+
+    pub fn len(&self) -> usize {
+        self.count.sum().max(0) as usize
+    }
+
+    #[cfg(any(debug_assertions, feature = "validate"))]
+    pub fn validate(&self, guard: &Guard) {
+        self.check_guard(guard);
+        let mut table = self.table.load(Ordering::SeqCst, guard);
+        while !table.is_null() {
+            let t = unsafe { table.deref() };
+            let mut i = 0;
+            while i < t.capacity() {
+                let bin = t.bin(i, guard);
+                match unsafe { bin.as_ref() } {
+                    Some(BinEntry::Node(head)) => {
+                        let _lock = self.contention_policy.lock(&head.lock);
+                        if t.bin(i, guard) != bin {
+                            continue;
+                        }
+
+                        let mut element = bin;
+                        while !element.is_null() {
+                            let node = match unsafe { element.deref() } {
+                                BinEntry::Node(node) => node,
+                                _ => panic!("linked bin contains a non-node entry"),
+                            };
+                            assert_eq!(t.bin_index(node.hash), i, "node stored in the wrong bin");
+                            element = node.next.load(Ordering::SeqCst, guard);
+                        }
+                    }
+                    Some(BinEntry::Tree(tree_bin)) => {
+                        let _lock = self.contention_policy.lock(&tree_bin.lock);
+                        if t.bin(i, guard) != bin {
+                            continue;
+                        }
+
+                        tree_bin.validate(guard);
+                    }
+                    Some(BinEntry::TreeNode(_)) => panic!("tree node stored directly in a bin"),
+                    Some(BinEntry::Moved) | None => {}
+                }
+                i += 1;
+            }
+            table = t.next_table.load(Ordering::SeqCst, guard);
+        }
+    }
+
+    fn teardown<F>(&mut self, mut f: F)
+    where
+        F: FnMut(K, V),
+    {
+        let guard = unsafe { epoch::unprotected() };
+        let mut table = self.table.swap(Shared::null(), Ordering::SeqCst, guard);
+        while !table.is_null() {
+            let owned = unsafe { table.into_owned() };
+            table = owned
+                .next_table
+                .swap(Shared::null(), Ordering::SeqCst, guard);
+            Self::teardown_table(&owned, &mut f);
+        }
+    }
+
+    fn teardown_table<F>(table: &Table<K, V>, f: &mut F)
+    where
+        F: FnMut(K, V),
+    {
+        let guard = unsafe { epoch::unprotected() };
+        for bin in table.bins.iter() {
+            let mut element = bin.load(Ordering::SeqCst, guard);
+            while !element.is_null() {
+                if let BinEntry::Moved = unsafe { element.deref() } {
+                    break;
+                }
+
+                let entry = unsafe { element.into_owned() }.into_box();
+                element = match *entry {
+                    BinEntry::Node(node) => {
+                        let value = unsafe { node.value.into_owned() }.into_box();
+                        f(node.key, *value);
+                        node.next.load(Ordering::SeqCst, guard)
+                    }
+                    BinEntry::Tree(tree_bin) => tree_bin.first.load(Ordering::SeqCst, guard),
+                    BinEntry::TreeNode(tree_node) => {
+                        let value = unsafe { tree_node.node.value.into_owned() }.into_box();
+                        f(tree_node.node.key, *value);
+                        tree_node.node.next.load(Ordering::SeqCst, guard)
+                    }
+                    BinEntry::Moved => unreachable!(),
+                };
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.count.is_positive()
+    }
+
+    pub fn stats(&self, guard: &Guard) -> TableStats {
+        self.check_guard(guard);
+        let mut stats = match unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() } {
+            Some(table) => table.stats(guard),
+            None => TableStats {
+                capacity: self.initial_capacity,
+                ..TableStats::default()
+            },
+        };
+        stats.len = self.len();
+        stats
+    }
+
+    fn hash<Q>(&self, key: &Q) -> u64
+    where
+        Q: ?Sized,
+        S: KeyHasher<Q>,
+    {
+        hashing::spread(self.build_hasher.hash_key(key))
+    }
+
+    fn init_table<'g>(&self, guard: &'g Guard) -> &'g Table<K, V> {
+        let table = self.table.load(Ordering::SeqCst, guard);
+        if !table.is_null() {
+            return unsafe { table.deref() };
+        }
+
+        let track_probes = matches!(self.treeify_policy, TreeifyPolicy::ProbeLength(_));
+        let new = Owned::new(Table::new(
+            self.initial_capacity,
+            track_probes,
+            self.pending().clone(),
+        ));
+        match self
+            .table
+            .compare_and_set(Shared::null(), new, Ordering::SeqCst, guard)
+        {
+            Ok(table) => unsafe { table.deref() },
+            Err(error) => unsafe { error.current.deref() },
+        }
+    }
+
+    pub fn clear(&self, guard: &Guard) {
+        self.check_guard(guard);
+        let table = match unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() } {
+            Some(table) => table,
+            None => return,
+        };
+        for i in 0..table.capacity() {
+            self.clear_bin(
+                table,
+                i,
+                &mut |element, value| unsafe {
+                    guard.retire(value, self.pending());
+                    guard.retire(element, self.pending());
+                },
+                guard,
+            );
+        }
+    }
+
+    fn clear_bin<'g, F>(
+        &self,
+        table: &'g Table<K, V>,
+        i: usize,
+        f: &mut F,
+        guard: &'g Guard,
+    ) -> usize
+    where
+        F: FnMut(Shared<'g, BinEntry<K, V>>, Shared<'g, V>),
+    {
+        loop {
+            let bin = table.bin(i, guard);
+            if bin.is_null() {
+                return 0;
+            }
+
+            let (lock, first) = match unsafe { bin.deref() } {
+                BinEntry::Node(head) => (self.contention_policy.lock(&head.lock), bin),
+                BinEntry::Tree(tree_bin) => (
+                    self.contention_policy.lock(&tree_bin.lock),
+                    tree_bin.first.load(Ordering::SeqCst, guard),
+                ),
+                BinEntry::Moved => {
+                    let next = table.next_table(guard);
+                    if next.capacity() < table.capacity() {
+                        return self.clear_bin(next, i & (next.capacity() - 1), f, guard);
+                    }
+                    return self.clear_bin(next, i, f, guard)
+                        + self.clear_bin(next, i + table.capacity(), f, guard);
+                }
+                BinEntry::TreeNode(_) => unreachable!(),
+            };
+            if table.bin(i, guard) != bin {
+                continue;
+            }
+
+            if self.is_bin_leased(first, guard) {
+                drop(lock);
+                thread::yield_now();
+                continue;
+            }
+
+            table.store_bin(i, Shared::null());
+            let mut count = 0;
+            let mut element = first;
+            while !element.is_null() {
+                let node = unsafe { element.deref() }.as_any_node().unwrap();
+                let next = node.next.load(Ordering::SeqCst, guard);
+                let value = node.value.swap(Shared::null(), Ordering::SeqCst, guard);
+                self.discharge(node);
+                f(element, value);
+                count += 1;
+                element = next;
+            }
+            if first != bin {
+                unsafe { guard.retire(bin, self.pending()) };
+            }
+
+            self.count.add(-count);
+            return count as usize;
+        }
+    }
+
+    fn should_treeify(&self, table: &Table<K, V>, i: usize, bin_count: usize) -> bool {
+        match self.treeify_policy {
+            TreeifyPolicy::Fixed => bin_count >= self.treeify_threshold,
+            TreeifyPolicy::ProbeLength(target) => {
+                bin_count > 1 && table.probe_length_exceeds(i, target)
+            }
+            TreeifyPolicy::Adaptive if self.collisions.is_adversarial() => {
+                bin_count >= ADVERSARIAL_TREEIFY_THRESHOLD
+            }
+            TreeifyPolicy::Adaptive => bin_count >= self.treeify_threshold,
+        }
+    }
+
+    fn record_collision(&self, collided: bool) {
+        if self.treeify_policy == TreeifyPolicy::Adaptive {
+            self.collisions.record(collided);
+        }
+    }
+
+    fn holds_lease(node: &Node<K, V>) -> bool {
+        let deadline = node.ext().map_or(0, |ext| ext.lease.load(Ordering::SeqCst));
+        deadline != 0 && deadline > clock::now()
+    }
+
+    fn is_leased(&self, node: &Node<K, V>) -> bool {
+        if !Self::holds_lease(node) {
+            return false;
+        }
+        match self.lease_policy {
+            LeasePolicy::Wait => true,
+            LeasePolicy::Steal => {
+                node.ext_or_init().lease.store(0, Ordering::SeqCst);
+                false
+            }
+        }
+    }
+
+    fn is_bin_leased(&self, first: Shared<'_, BinEntry<K, V>>, guard: &Guard) -> bool {
+        if self.lease_policy != LeasePolicy::Wait {
+            return false;
+        }
+        let mut element = first;
+        while !element.is_null() {
+            let node = unsafe { element.deref() }.as_any_node().unwrap();
+            if Self::holds_lease(node) {
+                return true;
+            }
+            element = node.next.load(Ordering::SeqCst, guard);
+        }
+        false
+    }
+
+    fn is_expired(&self, node: &Node<K, V>) -> bool {
+        node.is_expired(&self.expiry)
+    }
+
+    fn tracks_recency(&self) -> bool {
+        self.expiry.is_enabled() || self.is_bounded()
+    }
+
+    fn is_bounded(&self) -> bool {
+        self.max_capacity.is_some() || self.weigher.is_some()
+    }
+
+    fn exceeds_bound(&self) -> bool {
+        self.max_capacity
+            .is_some_and(|max_capacity| self.len() > max_capacity)
+            || (self.weigher.is_some() && self.weighted_size() > self.max_weight)
+    }
+
+    fn weigh(&self, key: &K, value: &V) -> u64 {
+        self.weigher
+            .as_ref()
+            .map_or(0, |weigher| u64::from(weigher(key, value)))
+    }
+
+    fn node_weight(&self, node: &Node<K, V>) -> u64 {
+        node.ext()
+            .map_or(0, |ext| ext.weight.load(Ordering::Relaxed))
+    }
+
+    fn charge(&self, weight: u64) {
+        if self.weigher.is_some() {
+            self.total_weight.add(weight as isize);
+        }
+    }
+
+    fn recharge(&self, node: &Node<K, V>, weight: u64) {
+        if self.weigher.is_some() {
+            let old = node.ext_or_init().weight.swap(weight, Ordering::Relaxed);
+            self.total_weight.add(weight as isize - old as isize);
+        }
+    }
+
+    fn reweigh(&self, node: &Node<K, V>, value: &V) {
+        self.recharge(node, self.weigh(&node.key, value));
+    }
+
+    fn discharge(&self, node: &Node<K, V>) {
+        if self.weigher.is_some() {
+            let old = node
+                .ext()
+                .map_or(0, |ext| ext.weight.swap(0, Ordering::Relaxed));
+            self.total_weight.add(-(old as isize));
+        }
+    }
+
+    fn record_write(&self, node: &Node<K, V>) {
+        if self.tracks_recency() {
+            node.ext_or_init()
+                .written
+                .store(clock::now(), Ordering::Relaxed);
+        }
+        if self.is_bounded() && self.eviction_policy == EvictionPolicy::Lfu {
+            node.ext_or_init().frequency.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn record_access(&self, node: &Node<K, V>, guard: &Guard) {
+        if self.expiry.tracks_access() || self.is_bounded() {
+            node.touch(clock::now(), guard);
+        }
+        if self.is_bounded() && self.eviction_policy == EvictionPolicy::Lfu {
+            node.ext_or_init().frequency.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn eviction_rank(&self, node: &Node<K, V>) -> (u64, u64) {
+        if self.is_expired(node) {
+            return (0, 0);
+        }
+        let ext = match node.ext() {
+            Some(ext) => ext,
+            None => return (0, 0),
+        };
+        let recency = ext
+            .written
+            .load(Ordering::Relaxed)
+            .max(ext.touched.load(Ordering::Relaxed));
+        match self.eviction_policy {
+            EvictionPolicy::Lru => (recency, 0),
+            EvictionPolicy::Lfu => (ext.frequency.load(Ordering::Relaxed), recency),
+        }
+    }
+}
+
+impl<K, V, S> ConcurrentHashMap<K, V, S>
+where
+    K: Eq,
+{
+    pub fn get<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key, guard).map(|(_, value)| value)
+    }
+
+    pub fn get_cloned<Q>(&self, key: &Q) -> Option<V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        V: Clone,
+    {
+        let guard = self.guard();
+        self.get(key, &guard).cloned()
+    }
+
+    pub fn get_key_value<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<(&'g K, &'g V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value_hashed(self.hash(key), key, guard)
+    }
+
+    fn get_key_value_hashed<'g, Q>(
+        &'g self,
+        hash: u64,
+        key: &Q,
+        guard: &'g Guard,
+    ) -> Option<(&'g K, &'g V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        loop {
+            let node = self.find_hashed_node(hash, key, guard)?;
+            let value = node.value.load(Ordering::SeqCst, guard);
+            if !value.is_null() {
+                if self.is_expired(node) {
+                    return None;
+                }
+                self.record_access(node, guard);
+                return Some((&node.key, unsafe { value.deref() }));
+            }
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get(key, guard).is_some()
+    }
+
+    pub fn raw_entry<'g>(&'g self, guard: &'g Guard) -> RawEntryBuilder<'g, K, V, S> {
+        RawEntryBuilder::new(self, guard)
+    }
+
+    pub fn contains_all<Q>(&self, keys: &[&Q], guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.for_each_batched(keys, guard, |_, value| value.is_some())
+    }
+
+    pub fn get_all_present<'g, Q>(&'g self, keys: &[&Q], guard: &'g Guard) -> Vec<(usize, &'g V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        let mut present = Vec::new();
+        self.for_each_batched(keys, guard, |n, value| {
+            if let Some(value) = value {
+                present.push((n, value));
+            }
+            true
+        });
+        present.sort_unstable_by_key(|&(n, _)| n);
+        present
+    }
+
+    fn for_each_batched<'g, Q, F>(&'g self, keys: &[&Q], guard: &'g Guard, mut f: F) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        F: FnMut(usize, Option<&'g V>) -> bool,
+    {
+        self.check_guard(guard);
+        let table = match unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() } {
+            Some(table) => table,
+            None => return (0..keys.len()).all(|n| f(n, None)),
+        };
+        let mut order: Vec<(usize, u64, usize)> = keys
+            .iter()
+            .enumerate()
+            .map(|(n, key)| {
+                let hash = self.hash(*key);
+                (table.bin_index(hash), hash, n)
+            })
+            .collect();
+        order.sort_unstable();
+
+        for (i, hash, n) in order {
+            let node = table.find(i, hash, keys[n], self.read_mostly, guard);
+            let value = match unsafe { node.as_ref() }.and_then(BinEntry::as_any_node) {
+                Some(node) if self.is_expired(node) => None,
+                Some(node) => match unsafe { node.value.load(Ordering::SeqCst, guard).as_ref() } {
+                    Some(value) => Some(value),
+                    None => self.get(keys[n], guard),
+                },
+                None => None,
+            };
+            if !f(n, value) {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn get_and_touch<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        loop {
+            let node = self.find_node(key, guard)?;
+            let value = node.value.load(Ordering::SeqCst, guard);
+            if !value.is_null() {
+                if self.is_expired(node) {
+                    return None;
+                }
+                node.touch(clock::now(), guard);
+                return Some(unsafe { value.deref() });
+            }
+        }
+    }
+
+    pub fn last_touched<Q>(&self, key: &Q, guard: &Guard) -> Option<u64>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        let node = self.find_node(key, guard)?;
+        Some(
+            node.ext()
+                .map_or(0, |ext| ext.touched.load(Ordering::Relaxed)),
+        )
+    }
+
+    pub fn lease<Q>(&self, key: &Q, duration: Duration, guard: &Guard) -> Result<Lease, LeaseError>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        if !clock::TRACKS_TIME {
+            return Err(LeaseError::Unsupported);
+        }
+        self.with_locked_node(key, guard, |node| {
+            let now = clock::now();
+            let deadline = node.ext().map_or(0, |ext| ext.lease.load(Ordering::SeqCst));
+            if deadline > now {
+                return Err(LeaseError::Held(Lease { deadline }));
+            }
+
+            let deadline = now.saturating_add(duration.as_nanos() as u64).max(1);
+            node.ext_or_init().lease.store(deadline, Ordering::SeqCst);
+            Ok(Lease { deadline })
+        })
+        .unwrap_or(Err(LeaseError::Absent))
+    }
+
+    pub fn lease_wait<Q>(
+        &self,
+        key: &Q,
+        duration: Duration,
+        guard: &Guard,
+    ) -> Result<Lease, LeaseError>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        loop {
+            match self.lease(key, duration, guard) {
+                Err(LeaseError::Held(_)) => thread::yield_now(),
+                result => return result,
+            }
+        }
+    }
+
+    pub fn release_lease<Q>(&self, key: &Q, lease: Lease, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.with_locked_node(key, guard, |node| {
+            if node.ext().map_or(0, |ext| ext.lease.load(Ordering::SeqCst)) != lease.deadline {
+                return false;
+            }
+            node.ext_or_init().lease.store(0, Ordering::SeqCst);
+            true
+        })
+        .unwrap_or(false)
+    }
+
+    pub fn pin_entry<Q>(&self, key: &Q, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.with_locked_node(key, guard, |node| {
+            node.ext_or_init()
+                .flags
+                .fetch_or(Node::<K, V>::PINNED, Ordering::SeqCst);
+        })
+        .is_some()
+    }
+
+    pub fn unpin_entry<Q>(&self, key: &Q, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.with_locked_node(key, guard, |node| {
+            if let Some(ext) = node.ext() {
+                ext.flags.fetch_and(!Node::<K, V>::PINNED, Ordering::SeqCst);
+            }
+        })
+        .is_some()
+    }
+
+    pub fn is_pinned<Q>(&self, key: &Q, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        match self.find_node(key, guard) {
+            Some(node) => node.is_pinned(),
+            None => false,
+        }
+    }
+
+    pub fn get_meta<Q>(&self, key: &Q, guard: &Guard) -> Option<u64>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        let node = self.find_node(key, guard)?;
+        Some(node.ext().map_or(0, |ext| ext.meta.load(Ordering::SeqCst)))
+    }
+
+    pub fn set_meta<Q>(&self, key: &Q, meta: u64, guard: &Guard) -> Option<u64>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.with_locked_node(key, guard, |node| {
+            node.ext_or_init().meta.swap(meta, Ordering::SeqCst)
+        })
+    }
+
+    pub fn fetch_update_meta<Q, F>(&self, key: &Q, f: F, guard: &Guard) -> Option<Result<u64, u64>>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        F: FnMut(u64) -> Option<u64>,
+    {
+        self.with_locked_node(key, guard, |node| {
+            node.ext_or_init()
+                .meta
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, f)
+        })
+    }
+
+    pub fn update_cow<'g, Q, F>(&'g self, key: &Q, mut f: F, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        V: Clone,
+        F: FnMut(&mut V),
+    {
+        loop {
+            let node = self.find_node(key, guard)?;
+            let current = node.value.load(Ordering::SeqCst, guard);
+            if current.is_null() {
+                continue;
+            }
+            if self.is_expired(node) {
+                return None;
+            }
+
+            if self.is_leased(node) {
+                thread::yield_now();
+                continue;
+            }
+
+            let mut value = unsafe { current.deref() }.clone();
+            f(&mut value);
+            self.bump_generation(node);
+            if let Ok(new) =
+                node.value
+                    .compare_and_set(current, Owned::new(value), Ordering::SeqCst, guard)
+            {
+                self.bump_generation(node);
+                self.record_write(node);
+                unsafe { guard.retire(current, self.pending()) };
+                let new = unsafe { new.deref() };
+                self.reweigh(node, new);
+                self.listeners.notify(&Event::Replace {
+                    key: &node.key,
+                    old: unsafe { current.deref() },
+                    new,
+                });
+                return Some(new);
+            }
+        }
+    }
+
+    pub fn get_versioned<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<(u64, &'g V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        if !self.versioned {
+            return None;
+        }
+
+        loop {
+            let node = self.find_node(key, guard)?;
+            if self.is_expired(node) {
+                return None;
+            }
+            let generation = node
+                .ext()
+                .map_or(0, |ext| ext.generation.load(Ordering::SeqCst));
+            let value = node.value.load(Ordering::SeqCst, guard);
+            if !value.is_null()
+                && node
+                    .ext()
+                    .map_or(0, |ext| ext.generation.load(Ordering::SeqCst))
+                    == generation
+            {
+                return Some((generation, unsafe { value.deref() }));
+            }
+        }
+    }
+
+    pub fn insert_if_version<Q>(
+        &self,
+        key: &Q,
+        generation: u64,
+        value: V,
+        guard: &Guard,
+    ) -> Result<u64, VersionError<V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        if !self.versioned {
+            return Err(VersionError::Unsupported(value));
+        }
+
+        let mut value = Some(value);
+        let result = self.with_locked_node(key, guard, |node| {
+            let current = node.value.load(Ordering::SeqCst, guard);
+            if current.is_null()
+                || self.is_leased(node)
+                || node
+                    .ext_or_init()
+                    .generation
+                    .compare_exchange(
+                        generation,
+                        generation + 1,
+                        Ordering::SeqCst,
+                        Ordering::SeqCst,
+                    )
+                    .is_err()
+            {
+                return None;
+            }
+
+            let new = Owned::new(value.take().unwrap());
+            let result = node
+                .value
+                .compare_and_set(current, new, Ordering::SeqCst, guard);
+            let generation = node.ext_or_init().generation.fetch_add(1, Ordering::SeqCst) + 1;
+            match result {
+                Ok(new) => {
+                    self.record_write(node);
+                    self.reweigh(node, unsafe { new.deref() });
+                    unsafe { guard.retire(current, self.pending()) };
+                    Some(generation)
+                }
+                Err(error) => {
+                    value = Some(*error.new.into_box());
+                    None
+                }
+            }
+        });
+
+        match result {
+            Some(Some(generation)) => Ok(generation),
+            _ => Err(VersionError::Conflict(value.unwrap())),
+        }
+    }
+
+    fn bump_generation(&self, node: &Node<K, V>) {
+        if self.versioned {
+            node.ext_or_init().generation.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn find_node<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g Node<K, V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.find_hashed_node(self.hash(key), key, guard)
+    }
+
+    fn find_hashed_node<'g, Q>(
+        &'g self,
+        hash: u64,
+        key: &Q,
+        guard: &'g Guard,
+    ) -> Option<&'g Node<K, V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        self.check_guard(guard);
+        let table = unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() }?;
+        if self.contention_policy.policy.readers_help_resize {
+            self.help_resize(table, guard);
+        }
+        let node = table.find(table.bin_index(hash), hash, key, self.read_mostly, guard);
+        if node.is_null() {
+            return None;
+        }
+
+        unsafe { node.deref() }.as_any_node()
+    }
+
+    fn lock_leased_entry<'g, Q>(
+        &'g self,
+        hash: u64,
+        key: &Q,
+        reserve: bool,
+        enforce: bool,
+        guard: &'g Guard,
+    ) -> Result<OccupiedEntry<'g, K, V, S>, Slot<'g, K, V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        self.check_guard(guard);
+        let mut table = self.init_table(guard);
+        loop {
+            let i = table.bin_index(hash);
+            let bin = table.bin(i, guard);
+            if bin.is_null() && !reserve {
+                return Err(Slot::new(
+                    table,
+                    i,
+                    bin,
+                    Shared::null(),
+                    0,
+                    false,
+                    guard,
+                    None,
+                ));
+            }
+            if bin.is_null() {
+                let reservation =
+                    Owned::new(BinEntry::Tree(TreeBin::empty(self.pending().clone())))
+                        .into_shared(guard);
+                let lock = self
+                    .contention_policy
+                    .lock(&unsafe { reservation.deref() }.as_tree_bin().unwrap().lock);
+                if table.bins[i]
+                    .compare_and_set(bin, reservation, Ordering::SeqCst, guard)
+                    .is_err()
+                {
+                    drop(lock);
+                    drop(unsafe { reservation.into_owned() });
+                    continue;
+                }
+                self.record_collision(false);
+                return Err(Slot::new(
+                    table,
+                    i,
+                    reservation,
+                    Shared::null(),
+                    0,
+                    true,
+                    guard,
+                    Some(lock),
+                ));
+            }
+
+            match unsafe { bin.deref() } {
+                BinEntry::Node(head) => {
+                    let lock = self.contention_policy.lock(&head.lock);
+                    if table.bin(i, guard) != bin {
+                        continue;
+                    }
+
+                    let mut bin_count = 0;
+                    let mut collided = false;
+                    let mut pred = Shared::null();
+                    let mut element = bin;
+                    while !element.is_null() {
+                        let node = unsafe { element.deref() }.as_node().unwrap();
+                        if node.hash == hash && key.equivalent(&node.key) {
+                            break;
+                        }
+                        collided |= node.hash == hash;
+                        bin_count += 1;
+                        pred = element;
+                        element = node.next.load(Ordering::SeqCst, guard);
+                    }
+                    if let Some(node) = unsafe { element.as_ref() } {
+                        if enforce && self.is_leased(node.as_node().unwrap()) {
+                            drop(lock);
+                            thread::yield_now();
+                            continue;
+                        }
+                        return Ok(OccupiedEntry::new(
+                            self, table, i, bin, pred, element, guard, lock,
+                        ));
+                    }
+                    if reserve {
+                        self.record_collision(collided);
+                    }
+                    return Err(Slot::new(
+                        table,
+                        i,
+                        bin,
+                        pred,
+                        bin_count,
+                        false,
+                        guard,
+                        Some(lock),
+                    ));
+                }
+                BinEntry::Tree(tree_bin) => {
+                    let lock = self.contention_policy.lock(&tree_bin.lock);
+                    if table.bin(i, guard) != bin {
+                        continue;
+                    }
+
+                    let root = tree_bin.root.load(Ordering::SeqCst, guard);
+                    let p = if root.is_null() {
+                        root
+                    } else {
+                        TreeNode::find_tree_node(root, hash, key, guard)
+                    };
+                    if p.is_null() {
+                        return Err(Slot::new(table, i, bin, p, 0, false, guard, Some(lock)));
+                    }
+                    if enforce && self.is_leased(&unsafe { TreeNode::get_tree_node(p) }.node) {
+                        drop(lock);
+                        thread::yield_now();
+                        continue;
+                    }
+                    return Ok(OccupiedEntry::new(
+                        self,
+                        table,
+                        i,
+                        bin,
+                        Shared::null(),
+                        p,
+                        guard,
+                        lock,
+                    ));
+                }
+                BinEntry::Moved => {
+                    table = table.next_table(guard);
+                    continue;
+                }
+                BinEntry::TreeNode(_) => unreachable!(),
+            }
+        }
+    }
+
+    pub fn update_many<Q, F>(&self, keys: &[&Q], f: F, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Eq + Equivalent<K>,
+        S: KeyHasher<Q>,
+        V: Clone,
+        F: FnOnce(&mut [V]),
+    {
+        self.check_guard(guard);
+        let hashes: Vec<u64> = keys.iter().map(|key| self.hash(*key)).collect();
+        let mut order: Vec<usize> = (0..keys.len()).collect();
+        order.sort_unstable_by_key(|&k| (hashes[k].reverse_bits(), hashes[k]));
+
+        for (start, &k) in order.iter().enumerate() {
+            let duplicate = order[start + 1..]
+                .iter()
+                .take_while(|&&other| hashes[other] == hashes[k])
+                .any(|&other| keys[other] == keys[k]);
+            if duplicate {
+                return false;
+            }
+        }
+
+        let (nodes, locks) = loop {
+            let mut locks = Vec::new();
+            let mut nodes = vec![None; keys.len()];
+            for &k in &order {
+                match self.lock_node(keys[k], hashes[k], &mut locks, guard) {
+                    Some(node) => nodes[k] = Some(node),
+                    None => return false,
+                }
+            }
+            let nodes: Vec<&Node<K, V>> = nodes.into_iter().map(Option::unwrap).collect();
+            if nodes.iter().any(|node| self.is_leased(node)) {
+                drop(locks);
+                thread::yield_now();
+                continue;
+            }
+            break (nodes, locks);
+        };
+
+        let mut values: Vec<V> = nodes
+            .iter()
+            .map(|node| unsafe { node.value.load(Ordering::SeqCst, guard).deref() }.clone())
+            .collect();
+        f(&mut values);
+
+        let mut replaced = Vec::with_capacity(nodes.len());
+        for (node, value) in nodes.into_iter().zip(values) {
+            self.bump_generation(node);
+            let new = Owned::new(value).into_shared(guard);
+            let old = node.value.swap(new, Ordering::SeqCst, guard);
+            self.record_write(node);
+            self.reweigh(node, unsafe { new.deref() });
+            unsafe { guard.retire(old, self.pending()) };
+            replaced.push((node, old, new));
+        }
+        drop(locks);
+
+        for (node, old, new) in replaced {
+            self.listeners.notify(&Event::Replace {
+                key: &node.key,
+                old: unsafe { old.deref() },
+                new: unsafe { new.deref() },
+            });
+        }
+        true
+    }
+
+    fn lock_node<'g, Q>(
+        &'g self,
+        key: &Q,
+        hash: u64,
+        locks: &mut Vec<BinLock<'g, K, V>>,
+        guard: &'g Guard,
+    ) -> Option<&'g Node<K, V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        let mut table = unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() }?;
+        loop {
+            let i = table.bin_index(hash);
+            let bin = table.bin(i, guard);
+            if bin.is_null() {
+                return None;
+            }
+
+            let held = locks
+                .iter()
+                .any(|&(locked, j, _)| std::ptr::eq(locked, table) && j == i);
+            match unsafe { bin.deref() } {
+                BinEntry::Node(head) => {
+                    if !held {
+                        let lock = self.contention_policy.lock(&head.lock);
+                        if table.bin(i, guard) != bin {
+                            continue;
+                        }
+                        locks.push((table, i, lock));
+                    }
+
+                    let mut element = bin;
+                    while !element.is_null() {
+                        let node = unsafe { element.deref() }.as_node().unwrap();
+                        if node.hash == hash && key.equivalent(&node.key) {
+                            return Some(node);
+                        }
+                        element = node.next.load(Ordering::SeqCst, guard);
+                    }
+                    return None;
+                }
+                BinEntry::Tree(tree_bin) => {
+                    if !held {
+                        let lock = self.contention_policy.lock(&tree_bin.lock);
+                        if table.bin(i, guard) != bin {
+                            continue;
+                        }
+                        locks.push((table, i, lock));
+                    }
+
+                    let root = tree_bin.root.load(Ordering::SeqCst, guard);
+                    if root.is_null() {
+                        return None;
+                    }
+                    let p = TreeNode::find_tree_node(root, hash, key, guard);
+                    if p.is_null() {
+                        return None;
+                    }
+                    return Some(&unsafe { TreeNode::get_tree_node(p) }.node);
+                }
+                BinEntry::Moved => {
+                    table = table.next_table(guard);
+                    continue;
+                }
+                BinEntry::TreeNode(_) => unreachable!(),
+            }
+        }
+    }
+
+    fn with_locked_node<'g, Q, F, R>(&'g self, key: &Q, guard: &'g Guard, f: F) -> Option<R>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        F: FnOnce(&'g Node<K, V>) -> R,
+    {
+        self.check_guard(guard);
+        let hash = self.hash(key);
+
+        let mut table = unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() }?;
+        loop {
+            let i = table.bin_index(hash);
+            let bin = table.bin(i, guard);
+            if bin.is_null() {
+                return None;
+            }
+
+            match unsafe { bin.deref() } {
+                BinEntry::Node(head) => {
+                    let _lock = self.contention_policy.lock(&head.lock);
+                    if table.bin(i, guard) != bin {
+                        continue;
+                    }
+
+                    let mut element = bin;
+                    while !element.is_null() {
+                        let node = unsafe { element.deref() }.as_node().unwrap();
+                        if node.hash == hash && key.equivalent(&node.key) {
+                            return Some(f(node));
+                        }
+                        element = node.next.load(Ordering::SeqCst, guard);
+                    }
+                    return None;
+                }
+                BinEntry::Tree(tree_bin) => {
+                    let _lock = self.contention_policy.lock(&tree_bin.lock);
+                    if table.bin(i, guard) != bin {
+                        continue;
+                    }
+
+                    let root = tree_bin.root.load(Ordering::SeqCst, guard);
+                    if root.is_null() {
+                        return None;
+                    }
+                    let p = TreeNode::find_tree_node(root, hash, key, guard);
+                    if p.is_null() {
+                        return None;
+                    }
+                    return Some(f(&unsafe { TreeNode::get_tree_node(p) }.node));
+                }
+                BinEntry::Moved => {
+                    table = table.next_table(guard);
+                    continue;
+                }
+                BinEntry::TreeNode(_) => unreachable!(),
+            }
+        }
+    }
+}
+
+impl<K, V, S> ConcurrentHashMap<K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    pub fn drain_to(&self, sender: &Sender<(K, V)>) -> Result<usize, DrainError<(K, V)>>
+    where
+        K: Send,
+        V: Send,
+    {
+        assert!(
+            !reclaim::is_pinned(&self.collector),
+            "drain_to cannot wait for reclamation while this thread is pinned"
+        );
+        let drained = Arc::new(Mutex::new(Drained::new()));
+        let mut unlinked = 0;
+        let mut sent = 0;
+        let mut table = ptr::null();
+        let mut i = 0;
+        let mut failed = None;
+        loop {
+            let guard = self.guard();
+            let current = match unsafe { self.table.load(Ordering::SeqCst, &guard).as_ref() } {
+                Some(current) => current,
+                None => break,
+            };
+            if !ptr::eq(current, table) {
+                table = current;
+                i = 0;
+            }
+            if i == current.capacity() {
+                break;
+            }
+            unlinked += self.clear_bin(
+                current,
+                i,
+                &mut |element, value| {
+                    let drained = Arc::clone(&drained);
+                    unsafe {
+                        guard.defer_unchecked(move || {
+                            let key = match *element.into_owned().into_box() {
+                                BinEntry::Node(node) => node.key,
+                                BinEntry::TreeNode(tree_node) => tree_node.node.key,
+                                BinEntry::Tree(_) | BinEntry::Moved => unreachable!(),
+                            };
+                            let value = *value.into_owned().into_box();
+                            drained.lock().deliver((key, value));
+                        })
+                    };
+                },
+                &guard,
+            );
+            i += 1;
+            drop(guard);
+            if let Err(error) = send_ready(&drained, sender, &mut sent) {
+                failed = Some(error);
+                break;
+            }
+        }
+
+        let mut progress = clock::now();
+        while failed.is_none() && sent < unlinked {
+            let before = sent;
+            self.try_advance();
+            thread::yield_now();
+            if let Err(error) = send_ready(&drained, sender, &mut sent) {
+                failed = Some(error);
+            } else if sent > before {
+                progress = clock::now();
+            } else if drain_stalled(progress) {
+                let mut drained = drained.lock();
+                if drained.ready.is_empty() {
+                    drained.sender = Some(sender.clone());
+                    return Err(DrainError::Stalled {
+                        sent,
+                        pending: unlinked - sent,
+                    });
+                }
+            }
+        }
+
+        let error = match failed {
+            Some(error) => error,
+            None => return Ok(sent),
+        };
+        let mut restored = sent + 1;
+        let mut progress = clock::now();
+        loop {
+            let guard = self.guard();
+            let ready: Vec<_> = drained.lock().ready.drain(..).collect();
+            if !ready.is_empty() {
+                progress = clock::now();
+            }
+            for (key, value) in ready {
+                let _ = self.try_insert(key, value, &guard);
+                restored += 1;
+            }
+            drop(guard);
+            if restored == unlinked || drain_stalled(progress) {
+                return Err(DrainError::Disconnected(error));
+            }
+            self.try_advance();
+            thread::yield_now();
+        }
+    }
+
+    pub fn insert<'g>(&'g self, key: K, value: V, guard: &'g Guard) -> Option<&'g V> {
+        let old = self.put_and_notify(key, Owned::new(value).into_shared(guard), guard);
+        unsafe { old.as_ref() }
+    }
+
+    pub fn insert_owned(&self, key: K, value: V, guard: &Guard) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.insert(key, value, guard).cloned()
+    }
+
+    pub fn insert_leased<'g, Q>(
+        &'g self,
+        key: &Q,
+        value: V,
+        lease: Lease,
+        guard: &'g Guard,
+    ) -> Result<&'g V, LeaseError>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        let hash = self.hash(key);
+        let mut entry = self
+            .lock_leased_entry(hash, key, false, false, guard)
+            .map_err(|_| LeaseError::Absent)?;
+        let deadline = entry
+            .node()
+            .ext()
+            .map_or(0, |ext| ext.lease.load(Ordering::SeqCst));
+        if deadline != lease.deadline {
+            return Err(LeaseError::Held(Lease { deadline }));
+        }
+        if !Self::holds_lease(entry.node()) {
+            return Err(LeaseError::Expired);
+        }
+        Ok(entry.insert(value))
+    }
+
+    pub fn get_with<'g, F>(&'g self, key: K, loader: F, guard: &'g Guard) -> &'g V
+    where
+        K: Hash,
+        F: FnOnce() -> V,
+    {
+        match self.try_get_with(key, || Ok::<V, Infallible>(loader()), guard) {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    pub fn try_get_with<'g, F, E>(&'g self, key: K, loader: F, guard: &'g Guard) -> Result<&'g V, E>
+    where
+        K: Hash,
+        F: FnOnce() -> Result<V, E>,
+    {
+        loop {
+            if let Some(value) = self.get(&key, guard) {
+                return Ok(value);
+            }
+
+            let _reservation = match self.reservations.reserve(&key) {
+                Ok(reservation) => reservation,
+                Err(reservation) => {
+                    reservation.wait();
+                    continue;
+                }
+            };
+            if let Some(value) = self.get(&key, guard) {
+                return Ok(value);
+            }
+
+            let value = Owned::new(loader()?).into_shared(guard);
+            self.put_and_notify(key, value, guard);
+            return Ok(unsafe { value.deref() });
+        }
+    }
+
+    fn put_and_notify<'g>(
+        &'g self,
+        key: K,
+        value: Shared<'g, V>,
+        guard: &'g Guard,
+    ) -> Shared<'g, V> {
+        if self.listeners.is_empty() {
+            let old = self.put(key, value, guard);
+            self.maintain(guard);
+            return old;
+        }
+
+        let event_key = key.clone();
+        let old = self.put(key, value, guard);
+        self.maintain(guard);
+        let new = unsafe { value.deref() };
+        match unsafe { old.as_ref() } {
+            Some(old) => self.listeners.notify(&Event::Replace {
+                key: &event_key,
+                old,
+                new,
+            }),
+            None => self.listeners.notify(&Event::Insert {
+                key: &event_key,
+                value: new,
+            }),
+        }
+        old
+    }
+
+    fn put<'g>(&'g self, key: K, value: Shared<'g, V>, guard: &'g Guard) -> Shared<'g, V> {
+        self.check_guard(guard);
+        let hash = self.hash(&key);
+        let mut node = self.new_node(hash, key, value);
+        let weight = self.node_weight(node.as_node().unwrap());
+
+        let mut table = self.init_table(guard);
+        loop {
+            let i = table.bin_index(hash);
+            let bin = table.bin(i, guard);
+
+            if bin.is_null() {
+                match table.cas_bin(i, bin, node, guard) {
+                    Ok(_) => {
+                        self.record_collision(false);
+                        self.charge(weight);
+                        self.add_count(1, guard);
+                        return Shared::null();
+                    }
+                    Err(new) => {
+                        node = new;
+                        continue;
+                    }
+                }
+            }
+
+            let bin_count = match unsafe { bin.deref() } {
+                BinEntry::Node(head) => {
+                    let lock = self.contention_policy.lock(&head.lock);
+                    if table.bin(i, guard) != bin {
+                        continue;
+                    }
+
+                    let new_node = node.as_node().unwrap();
+                    let mut bin_count = 1;
+                    let mut collided = false;
+                    let mut element = bin;
+                    let tail = loop {
+                        let element_deref = unsafe { element.deref() }.as_node().unwrap();
+                        if element_deref.hash == hash && element_deref.key == new_node.key {
+                            if self.is_leased(element_deref) {
+                                break None;
+                            }
+                            let expired = self.is_expired(element_deref);
+                            let value = new_node.value.load(Ordering::SeqCst, guard);
+                            self.bump_generation(element_deref);
+                            let old = element_deref.value.swap(value, Ordering::SeqCst, guard);
+                            self.bump_generation(element_deref);
+                            self.record_write(element_deref);
+                            self.recharge(element_deref, weight);
+                            unsafe { guard.retire(old, self.pending()) };
+                            return if expired { Shared::null() } else { old };
+                        }
+                        collided |= element_deref.hash == hash;
+
+                        let next = element_deref.next.load(Ordering::SeqCst, guard);
+                        if next.is_null() {
+                            break Some(element_deref);
+                        }
+                        element = next;
+                        bin_count += 1;
+                    };
+                    match tail {
+                        Some(tail) => {
+                            self.record_collision(collided);
+                            tail.next.store(node, Ordering::SeqCst)
+                        }
+                        None => {
+                            drop(lock);
+                            thread::yield_now();
+                            continue;
+                        }
+                    }
+                    Some(bin_count)
+                }
+                BinEntry::Tree(tree_bin) => {
+                    let lock = self.contention_policy.lock(&tree_bin.lock);
+                    if table.bin(i, guard) != bin {
+                        continue;
+                    }
+
+                    let root = tree_bin.root.load(Ordering::SeqCst, guard);
+                    if !root.is_null() {
+                        let key = &node.as_node().unwrap().key;
+                        let p = TreeNode::find_tree_node(root, hash, key, guard);
+                        if !p.is_null()
+                            && self.is_leased(&unsafe { TreeNode::get_tree_node(p) }.node)
+                        {
+                            drop(lock);
+                            thread::yield_now();
+                            continue;
+                        }
+                    }
+
+                    let node = match *node.into_box() {
+                        BinEntry::Node(node) => node,
+                        _ => unreachable!(),
+                    };
+                    let value = node.value.load(Ordering::SeqCst, guard);
+                    let p = tree_bin.put_tree_val(node, &self.contention_policy, guard);
+                    if !p.is_null() {
+                        let existing = &unsafe { TreeNode::get_tree_node(p) }.node;
+                        let expired = self.is_expired(existing);
+                        self.bump_generation(existing);
+                        let old = existing.value.swap(value, Ordering::SeqCst, guard);
+                        self.bump_generation(existing);
+                        self.record_write(existing);
+                        self.recharge(existing, weight);
+                        unsafe { guard.retire(old, self.pending()) };
+                        return if expired { Shared::null() } else { old };
+                    }
+                    None
+                }
+                BinEntry::Moved => {
+                    table = self.help_transfer(table, guard);
+                    continue;
+                }
+                BinEntry::TreeNode(_) => unreachable!(),
+            };
+
+            if let Some(bin_count) = bin_count {
+                table.record_probes(i, bin_count as u64);
+                if self.should_treeify(table, i, bin_count) {
+                    self.treeify_or_resize(table, i, guard);
+                }
+            }
+            self.charge(weight);
+            self.add_count(1, guard);
+            return Shared::null();
+        }
+    }
+
+    fn new_node(&self, hash: u64, key: K, value: Shared<'_, V>) -> Owned<BinEntry<K, V>> {
+        let weight = self.weigh(&key, unsafe { value.deref() });
+        let node = BinEntry::Node(Node::new(hash, key, value, Atomic::null()));
+        let node = match self.node_pool {
+            Some(ref pool) => pool.alloc(node),
+            None => Owned::new(node),
+        };
+        if self.versioned {
+            node.as_node().unwrap().ext_or_init().generation.store(
+                self.generations.fetch_add(1, Ordering::Relaxed) << 32,
+                Ordering::Relaxed,
+            );
+        }
+        if self.weigher.is_some() {
+            node.as_node()
+                .unwrap()
+                .ext_or_init()
+                .weight
+                .store(weight, Ordering::Relaxed);
+        }
+        self.record_write(node.as_node().unwrap());
+        node
+    }
+
+    pub fn load<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Loader<'_, K, V, S>) -> R,
+    {
+        let mut loader = Loader::new(self);
+        let result = f(&mut loader);
+        loader.seal();
+        result
+    }
+
+    fn lock_entry<'g, Q>(
+        &'g self,
+        hash: u64,
+        key: &Q,
+        reserve: bool,
+        guard: &'g Guard,
+    ) -> Result<OccupiedEntry<'g, K, V, S>, Slot<'g, K, V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        loop {
+            match self.lock_leased_entry(hash, key, reserve, true, guard) {
+                Ok(entry) if self.is_expired(entry.node()) => {
+                    entry.unlink();
+                }
+                result => return result,
+            }
+        }
+    }
+
+    pub fn entry<'g>(&'g self, key: K, guard: &'g Guard) -> Entry<'g, K, V, S> {
+        let hash = self.hash(&key);
+        match self.lock_entry(hash, &key, true, guard) {
+            Ok(entry) => Entry::Occupied(entry),
+            Err(slot) => Entry::Vacant(VacantEntry::new(self, hash, key, slot, guard)),
+        }
+    }
+
+    pub fn raw_entry_mut<'g>(&'g self, guard: &'g Guard) -> RawEntryBuilderMut<'g, K, V, S> {
+        RawEntryBuilderMut::new(self, guard)
+    }
+
+    pub fn lock_key<'g>(&'g self, key: K, guard: &'g Guard) -> KeyGuard<'g, K, V, S> {
+        KeyGuard::new(self.entry(key, guard))
+    }
+
+    pub fn try_insert<'g>(
+        &'g self,
+        key: K,
+        value: V,
+        guard: &'g Guard,
+    ) -> Result<&'g V, OccupiedError<'g, V>> {
+        match self.entry(key, guard) {
+            Entry::Occupied(entry) => Err(OccupiedError {
+                current: entry.get(),
+                value,
+            }),
+            Entry::Vacant(entry) => Ok(entry.insert(value)),
+        }
+    }
+
+    pub fn get_or_insert_with<'g, F>(&'g self, key: K, f: F, guard: &'g Guard) -> &'g V
+    where
+        F: FnOnce() -> V,
+    {
+        if let Some(value) = self.get(&key, guard) {
+            return value;
+        }
+        self.entry(key, guard).or_insert_with(f)
+    }
+
+    pub fn compute<'g, F>(&'g self, key: K, f: F, guard: &'g Guard) -> Option<&'g V>
+    where
+        F: FnOnce(&K, Option<&V>) -> Option<V>,
+    {
+        match self.entry(key, guard) {
+            Entry::Occupied(mut entry) => match f(entry.key(), Some(entry.get())) {
+                Some(value) => {
+                    entry.insert(value);
+                    Some(entry.get())
+                }
+                None => {
+                    entry.remove();
+                    None
+                }
+            },
+            Entry::Vacant(entry) => match f(entry.key(), None) {
+                Some(value) => Some(entry.insert(value)),
+                None => None,
+            },
+        }
+    }
+
+    pub fn compute_if_present<'g, Q, F>(&'g self, key: &Q, f: F, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        F: FnOnce(&K, &V) -> Option<V>,
+    {
+        let hash = self.hash(key);
+        let mut entry = self.lock_entry(hash, key, false, guard).ok()?;
+        match f(entry.key(), entry.get()) {
+            Some(value) => {
+                entry.insert(value);
+                Some(entry.get())
+            }
+            None => {
+                entry.remove();
+                None
+            }
+        }
+    }
+
+    pub fn update<'g, Q, F>(&'g self, key: &Q, f: F, guard: &'g Guard) -> Option<(&'g V, &'g V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        F: FnOnce(&V) -> V,
+    {
+        let hash = self.hash(key);
+        let mut entry = self.lock_entry(hash, key, false, guard).ok()?;
+        let old = entry.insert(f(entry.get()));
+        Some((old, entry.get()))
+    }
+
+    pub fn remove_if<Q, F>(&self, key: &Q, f: F, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        F: FnOnce(&V) -> bool,
+    {
+        let hash = self.hash(key);
+        match self.lock_entry(hash, key, false, guard) {
+            Ok(entry) if f(entry.get()) => {
+                entry.remove();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn compare_exchange<'g, Q>(
+        &'g self,
+        key: &Q,
+        expected: &V,
+        new: V,
+        guard: &'g Guard,
+    ) -> Result<&'g V, CompareExchangeError<'g, V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        V: PartialEq,
+    {
+        let hash = self.hash(key);
+        match self.lock_entry(hash, key, false, guard) {
+            Ok(mut entry) if entry.get() == expected => Ok(entry.insert(new)),
+            Ok(entry) => Err(CompareExchangeError {
+                current: Some(entry.get()),
+                new,
+            }),
+            Err(_) => Err(CompareExchangeError { current: None, new }),
+        }
+    }
+
+    pub fn retain<F>(&self, mut f: F, guard: &Guard)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.check_guard(guard);
+        let table = match unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() } {
+            Some(table) => table,
+            None => return,
+        };
+        for i in 0..table.capacity() {
+            self.retain_bin(table, i, (0, 0), &mut f, guard);
+        }
+        self.maybe_shrink(guard);
+    }
+
+    pub fn purge_expired(&self, guard: &Guard) {
+        if self.expiry.is_enabled() {
+            self.retain(|_, _| true, guard);
+        }
+    }
+
+    fn maintain(&self, guard: &Guard) {
+        self.sweep_step(guard);
+        while self.exceeds_bound() && self.evict_one(guard) {}
+    }
+
+    fn evict_one(&self, guard: &Guard) -> bool {
+        let table = match unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() } {
+            Some(table) => table,
+            None => return false,
+        };
+        let mut victim: Option<&Node<K, V>> = None;
+        let mut sampled = 0;
+        for _ in 0..table.capacity() {
+            if sampled >= EVICTION_SAMPLES {
+                break;
+            }
+            let i = self.evict_cursor.fetch_add(1, Ordering::Relaxed) & (table.capacity() - 1);
+            for node in NodeIter::range(Some(table), i, i + 1, guard) {
+                if node.is_pinned() || Self::holds_lease(node) {
+                    continue;
+                }
+                sampled += 1;
+                if victim.is_none_or(|victim| self.eviction_rank(node) < self.eviction_rank(victim))
+                {
+                    victim = Some(node);
+                }
+            }
+        }
+
+        let victim = match victim {
+            Some(victim) => victim,
+            None => return false,
+        };
+        if let Ok(entry) = self.lock_leased_entry(victim.hash, &victim.key, false, false, guard) {
+            if !entry.node().is_pinned() && !Self::holds_lease(entry.node()) {
+                entry.unlink();
+            }
+        }
+        true
+    }
+
+    fn sweep_step(&self, guard: &Guard) {
+        if !self.expiry.is_enabled() {
+            return;
+        }
+        let table = match unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() } {
+            Some(table) => table,
+            None => return,
+        };
+        let i = self.sweep_cursor.fetch_add(1, Ordering::Relaxed) & (table.capacity() - 1);
+        self.retain_bin(table, i, (0, 0), &mut |_, _| true, guard);
+    }
+
+    #[cfg(feature = "rayon")]
+    pub fn par_retain<F>(&self, f: F, guard: &Guard)
+    where
+        K: Send + Sync,
+        V: Send + Sync,
+        S: Sync,
+        F: Fn(&K, &V) -> bool + Sync,
+    {
+        self.check_guard(guard);
+        let table = match unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() } {
+            Some(table) => table,
+            None => return,
+        };
+        (0..table.capacity()).into_par_iter().for_each_init(
+            || self.guard(),
+            |guard, i| self.retain_bin(table, i, (0, 0), &mut &f, guard),
+        );
+        self.maybe_shrink(guard);
+    }
+
+    #[cfg(feature = "rayon")]
+    pub fn par_for_each<F>(&self, f: F, guard: &Guard)
+    where
+        K: Send + Sync,
+        V: Send + Sync,
+        S: Sync,
+        F: Fn(&K, &V) + Sync,
+    {
+        self.par_retain(
+            |key, value| {
+                f(key, value);
+                true
+            },
+            guard,
+        );
+    }
+
+    fn retain_bin<F>(
+        &self,
+        table: &Table<K, V>,
+        i: usize,
+        filter: (usize, usize),
+        f: &mut F,
+        guard: &Guard,
+    ) where
+        F: FnMut(&K, &V) -> bool,
+    {
+        loop {
+            let bin = table.bin(i, guard);
+            if bin.is_null() {
+                return;
+            }
+
+            match unsafe { bin.deref() } {
+                BinEntry::Node(head) => {
+                    let lock = self.contention_policy.lock(&head.lock);
+                    if table.bin(i, guard) != bin {
+                        continue;
+                    }
+                    if self.is_bin_leased(bin, guard) {
+                        drop(lock);
+                        thread::yield_now();
+                        continue;
+                    }
+
+                    let mut removed = 0;
+                    let mut pred: Shared<'_, BinEntry<K, V>> = Shared::null();
+                    let mut element = bin;
+                    while !element.is_null() {
+                        let node = unsafe { element.deref() }.as_node().unwrap();
+                        let next = node.next.load(Ordering::SeqCst, guard);
+                        let value = node.value.load(Ordering::SeqCst, guard);
+                        if node.hash as usize & filter.0 != filter.1
+                            || (!self.is_expired(node) && f(&node.key, unsafe { value.deref() }))
+                        {
+                            pred = element;
+                            element = next;
+                            continue;
+                        }
+
+                        if pred.is_null() {
+                            table.store_bin(i, next);
+                        } else {
+                            unsafe { pred.deref() }
+                                .as_node()
+                                .unwrap()
+                                .next
+                                .store(next, Ordering::SeqCst);
+                        }
+                        self.discharge(node);
+                        unsafe {
+                            guard.retire(
+                                node.value.swap(Shared::null(), Ordering::SeqCst, guard),
+                                self.pending(),
+                            );
+                            self.retire_node(element, guard);
+                        }
+                        removed += 1;
+                        if pred.is_null() {
+                            break;
+                        }
+                        element = next;
+                    }
+                    self.count.add(-removed);
+                    if pred.is_null() && removed > 0 {
+                        continue;
+                    }
+                }
+                BinEntry::Tree(tree_bin) => {
+                    let lock = self.contention_policy.lock(&tree_bin.lock);
+                    if table.bin(i, guard) != bin {
+                        continue;
+                    }
+                    let first = tree_bin.first.load(Ordering::SeqCst, guard);
+                    if self.is_bin_leased(first, guard) {
+                        drop(lock);
+                        thread::yield_now();
+                        continue;
+                    }
+
+                    let mut removed = 0;
+                    let mut retained = 0;
+                    let mut element = first;
+                    while !element.is_null() {
+                        let node = unsafe { element.deref() }.as_any_node().unwrap();
+                        let value = node.value.load(Ordering::SeqCst, guard);
+                        if node.hash as usize & filter.0 != filter.1
+                            || (!self.is_expired(node) && f(&node.key, unsafe { value.deref() }))
+                        {
+                            retained += 1;
+                        } else {
+                            self.discharge(node);
+                            unsafe {
+                                guard.retire(
+                                    node.value.swap(Shared::null(), Ordering::SeqCst, guard),
+                                    self.pending(),
+                                )
+                            };
+                            removed += 1;
+                        }
+                        element = node.next.load(Ordering::SeqCst, guard);
+                    }
+                    if removed == 0 {
+                        return;
+                    }
+
+                    let mut head = Table::untreeify(first, guard);
+                    let mut pred: Shared<'_, BinEntry<K, V>> = Shared::null();
+                    let mut element = head;
+                    while !element.is_null() {
+                        let node = unsafe { element.deref() }.as_node().unwrap();
+                        let next = node.next.load(Ordering::SeqCst, guard);
+                        if node.value.load(Ordering::SeqCst, guard).is_null() {
+                            if pred.is_null() {
+                                head = next;
+                            } else {
+                                unsafe { pred.deref() }
+                                    .as_node()
+                                    .unwrap()
+                                    .next
+                                    .store(next, Ordering::SeqCst);
+                            }
+                            unsafe { guard.retire(element, self.pending()) };
+                        } else {
+                            pred = element;
+                        }
+                        element = next;
+                    }
+                    table.store_bin(i, head);
+
+                    let mut element = first;
+                    while !element.is_null() {
+                        let next = unsafe { element.deref() }
+                            .as_any_node()
+                            .unwrap()
+                            .next
+                            .load(Ordering::SeqCst, guard);
+                        unsafe { guard.retire(element, self.pending()) };
+                        element = next;
+                    }
+                    unsafe { guard.retire(bin, self.pending()) };
+                    self.count.add(-removed);
+                    drop(lock);
+
+                    if self.should_treeify(table, i, retained)
+                        && table.capacity() >= self.min_treeify_capacity
+                    {
+                        table.treeify_bin(i, &self.contention_policy, guard);
+                    }
+                }
+                BinEntry::Moved => {
+                    let next = table.next_table(guard);
+                    if next.capacity() > table.capacity() {
+                        self.retain_bin(next, i, filter, f, guard);
+                        self.retain_bin(next, i + table.capacity(), filter, f, guard);
+                    } else {
+                        let mask = table.capacity() - 1;
+                        let filter = if mask > filter.0 { (mask, i) } else { filter };
+                        self.retain_bin(next, i & (next.capacity() - 1), filter, f, guard);
+                    }
+                }
+                BinEntry::TreeNode(_) => unreachable!(),
+            }
+            return;
+        }
+    }
+
+    pub fn remove<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        let hash = self.hash(key);
+        self.lock_entry(hash, key, false, guard)
+            .ok()
+            .map(OccupiedEntry::remove)
+    }
+}
+
+impl<K, V, S> ConcurrentHashMap<K, V, S>
+where
+    K: Eq + Clone,
+{
+    fn treeify_or_resize(&self, table: &Table<K, V>, i: usize, guard: &Guard) {
+        if table.capacity() >= self.min_treeify_capacity
+            || (self.treeify_policy == TreeifyPolicy::Adaptive && self.collisions.is_adversarial())
+        {
+            table.treeify_bin(i, &self.contention_policy, guard);
+        } else if std::ptr::eq(self.table.load(Ordering::SeqCst, guard).as_raw(), table) {
+            self.transfer(table, self.resize_stride, guard);
+        }
+    }
+
+    pub fn reserve(&self, additional: usize, guard: &Guard) {
+        self.check_guard(guard);
+        self.init_table(guard);
+        let size = self.len().saturating_add(additional);
+        loop {
+            let table = self.table.load(Ordering::SeqCst, guard);
+            let table_deref = unsafe { table.deref() };
+            if size < table_deref.threshold() || table_deref.capacity() >= MAXIMUM_CAPACITY {
+                return;
+            }
+
+            self.transfer(table_deref, None, guard);
+            if self.table.load(Ordering::SeqCst, guard) == table {
+                thread::yield_now();
+            }
+        }
+    }
+
+    fn add_count(&self, delta: isize, guard: &Guard) {
+        self.count.add(delta);
+        if delta < 0 {
+            self.maybe_shrink(guard);
+        } else {
+            self.grow(self.len(), self.resize_stride, guard);
+        }
+    }
+
+    fn grow(&self, size: usize, stride: Option<usize>, guard: &Guard) {
+        let mut table = self.table.load(Ordering::SeqCst, guard);
+        loop {
+            let table_deref = unsafe { table.deref() };
+            if size < table_deref.threshold() || table_deref.capacity() >= MAXIMUM_CAPACITY {
+                return;
+            }
+
+            self.transfer(table_deref, stride, guard);
+            let current = self.table.load(Ordering::SeqCst, guard);
+            if current == table {
+                return;
+            }
+            table = current;
+        }
+    }
+
+    pub fn shrink_to_fit(&self, guard: &Guard) {
+        self.check_guard(guard);
+        self.shrink(self.len(), 1, guard);
+    }
+
+    fn maybe_shrink(&self, guard: &Guard) {
+        let fraction = match self.shrink_fraction {
+            Some(fraction) => fraction,
+            None => return,
+        };
+
+        let capacity = match unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() } {
+            Some(table) => table.capacity(),
+            None => return,
+        };
+        let len = self.len();
+        if capacity > DEFAULT_CAPACITY && (len as f64) < capacity as f64 * fraction {
+            self.shrink(len, DEFAULT_CAPACITY, guard);
+        }
+    }
+
+    fn shrink(&self, size: usize, min_capacity: usize, guard: &Guard) {
+        loop {
+            self.finish_resize(guard);
+            let table = match unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() } {
+                Some(table) => table,
+                None => return,
+            };
+            let capacity = table.capacity() >> 1;
+            if capacity < min_capacity || size >= capacity - (capacity >> 2) {
+                return;
+            }
+
+            let _ = table.moved.compare_and_set(
+                Shared::null(),
+                Owned::new(BinEntry::Moved),
+                Ordering::SeqCst,
+                guard,
+            );
+            let new = Owned::new(Table::new(
+                capacity,
+                table.probes.is_some(),
+                self.pending().clone(),
+            ));
+            if table
+                .next_table
+                .compare_and_set(Shared::null(), new, Ordering::SeqCst, guard)
+                .is_ok()
+            {
+                self.transfer(table, None, guard);
+            }
+        }
+    }
+
+    fn help_transfer<'g>(&self, table: &'g Table<K, V>, guard: &'g Guard) -> &'g Table<K, V> {
+        self.transfer(table, self.resize_stride, guard)
+    }
+
+    fn finish_resize(&self, guard: &Guard) {
+        loop {
+            let table = match unsafe { self.table.load(Ordering::SeqCst, guard).as_ref() } {
+                Some(table) => table,
+                None => return,
+            };
+            if table.next_table.load(Ordering::SeqCst, guard).is_null() {
+                return;
+            }
+            self.transfer(table, None, guard);
+        }
+    }
+
+    fn transfer<'g>(
+        &self,
+        table: &'g Table<K, V>,
+        stride: Option<usize>,
+        guard: &'g Guard,
+    ) -> &'g Table<K, V> {
+        self.resize_helper.get_or_init(|| Self::help_transfer);
+        let mut next = table.next_table.load(Ordering::SeqCst, guard);
+        if next.is_null() {
+            let _ = table.moved.compare_and_set(
+                Shared::null(),
+                Owned::new(BinEntry::Moved),
+                Ordering::SeqCst,
+                guard,
+            );
+            let new = Owned::new(Table::new(
+                table.capacity() << 1,
+                table.probes.is_some(),
+                self.pending().clone(),
+            ));
+            next =
+                match table
+                    .next_table
+                    .compare_and_set(Shared::null(), new, Ordering::SeqCst, guard)
+                {
+                    Ok(next) => next,
+                    Err(error) => error.current,
+                };
+        }
+        let next = unsafe { next.deref() };
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("transfer", from = table.capacity(), to = next.capacity())
+            .entered();
+
+        let units = table.capacity().min(next.capacity());
+        let step = stride.unwrap_or(MIN_TRANSFER_STRIDE);
+        loop {
+            let start = table.transfer_index.fetch_add(step, Ordering::SeqCst);
+            if start >= units {
+                return next;
+            }
+
+            let end = (start + step).min(units);
+            for i in start..end {
+                if next.capacity() > table.capacity() {
+                    table.transfer_bin(
+                        i,
+                        next,
+                        self.untreeify_threshold,
+                        &self.contention_policy,
+                        guard,
+                    );
+                } else {
+                    table.merge_bins(
+                        i,
+                        next,
+                        self.untreeify_threshold,
+                        &self.contention_policy,
+                        guard,
+                    );
+                }
+            }
+            if table.transferred.fetch_add(end - start, Ordering::SeqCst) + end - start == units {
+                self.table
+                    .store(Shared::from(next as *const Table<K, V>), Ordering::SeqCst);
+                unsafe { guard.retire(Shared::from(table as *const Table<K, V>), self.pending()) };
+                return next;
+            }
+            if stride.is_some() {
+                return next;
+            }
+        }
+    }
+}
+
+impl<K, V, S> ConcurrentHashMap<K, Box<V>, S>
+where
+    K: Eq,
+    V: ?Sized,
+{
+    pub fn get_unsized<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get(key, guard).map(|value| &**value)
+    }
+
+    pub fn insert_boxed<'g, B>(&'g self, key: K, value: B, guard: &'g Guard) -> Option<&'g V>
+    where
+        K: Clone,
+        S: KeyHasher<K>,
+        B: Into<Box<V>>,
+    {
+        self.insert(key, value.into(), guard).map(|value| &**value)
+    }
+}
+
+impl<K, V, S> ConcurrentHashMap<K, Arc<V>, S>
+where
+    K: Eq,
+    V: ?Sized,
+{
+    pub fn get_arc<Q>(&self, key: &Q) -> Option<Arc<V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_cloned(key)
+    }
+
+    pub fn insert_arc<A>(&self, key: K, value: A) -> Option<Arc<V>>
+    where
+        K: Clone,
+        S: KeyHasher<K>,
+        A: Into<Arc<V>>,
+    {
+        let guard = self.guard();
+        self.insert(key, value.into(), &guard).cloned()
+    }
+
+    pub fn remove_arc<Q>(&self, key: &Q) -> Option<Arc<V>>
+    where
+        K: Clone,
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<K> + KeyHasher<Q>,
+    {
+        let guard = self.guard();
+        self.remove(key, &guard).cloned()
+    }
+}
+
+impl<K, V, S> Default for ConcurrentHashMap<K, V, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> Clone for ConcurrentHashMap<K, V, S>
+where
+    K: Eq + Clone,
+    V: Clone,
+    S: KeyHasher<K> + Clone,
+{
+    fn clone(&self) -> Self {
+        let guard = self.guard();
+        let initial_capacity = match unsafe { self.table.load(Ordering::SeqCst, &guard).as_ref() } {
+            Some(table) => table.capacity(),
+            None => self.initial_capacity,
+        };
+        let mut map = ConcurrentHashMap {
+            table: CachePadded::new(Atomic::null()),
+            initial_capacity,
+            count: Counter::new(),
+            build_hasher: self.build_hasher.clone(),
+            read_mostly: self.read_mostly,
+            treeify_policy: self.treeify_policy,
+            contention_policy: ContentionTracker::new(self.contention_policy.policy),
+            lease_policy: self.lease_policy,
+            versioned: self.versioned,
+            generations: CachePadded::new(AtomicU64::new(self.generations.load(Ordering::Relaxed))),
+            reservations: Reservations::new(),
+            debug_limit: self.debug_limit,
+            collector: self.collector.clone(),
+            resize_stride: self.resize_stride,
+            resize_helper: OnceLock::new(),
+            treeify_threshold: self.treeify_threshold,
+            untreeify_threshold: self.untreeify_threshold,
+            min_treeify_capacity: self.min_treeify_capacity,
+            shrink_fraction: self.shrink_fraction,
+            pending: OnceLock::new(),
+            node_pool: self.node_pool.clone(),
+            listeners: self.listeners.clone(),
+            expiry: self.expiry,
+            sweep_cursor: AtomicUsize::new(0),
+            max_capacity: self.max_capacity,
+            eviction_policy: self.eviction_policy,
+            evict_cursor: AtomicUsize::new(0),
+            weigher: self.weigher.clone(),
+            max_weight: self.max_weight,
+            total_weight: Counter::new(),
+            collisions: CollisionEstimate::new(),
+        };
+
+        map.load(|loader| {
+            for node in self.nodes(&guard) {
+                let value = node.value.load(Ordering::SeqCst, &guard);
+                if let Some(value) = unsafe { value.as_ref() } {
+                    let copy = Node::new(
+                        node.hash,
+                        node.key.clone(),
+                        Owned::new(value.clone()),
+                        Atomic::null(),
+                    );
+                    copy.inherit(node);
+                    loader.insert_node(Owned::new(BinEntry::Node(copy)));
+                }
+            }
+        });
+        map
+    }
+}
+
+impl<K, V, S> PartialEq for ConcurrentHashMap<K, V, S>
+where
+    K: Eq,
+    V: PartialEq,
+    S: KeyHasher<K>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        let guard = self.guard();
+        let other_guard = other.guard();
+        self.iter(&guard)
+            .all(|(key, value)| other.get(key, &other_guard) == Some(value))
+    }
+}
+
+impl<K, V, S> Eq for ConcurrentHashMap<K, V, S>
+where
+    K: Eq,
+    V: Eq,
+    S: KeyHasher<K>,
+{
+}
+
+impl<K, V, S> fmt::Debug for ConcurrentHashMap<K, V, S>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guard = self.guard();
+        let mut entries = self.iter(&guard);
+        let mut map = f.debug_map();
+        map.entries(entries.by_ref().take(self.debug_limit));
+        if entries.next().is_some() {
+            return map.finish_non_exhaustive();
+        }
+        map.finish()
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for ConcurrentHashMap<K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K> + Default,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let iter = iter.into_iter();
+        let mut map = Builder::new()
+            .capacity(iter.size_hint().0)
+            .build_with_hasher(S::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for ConcurrentHashMap<K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.load(|loader| {
+            for (key, value) in iter {
+                loader.insert(key, value);
+            }
+        });
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V, S> FromParallelIterator<(K, V)> for ConcurrentHashMap<K, V, S>
+where
+    K: Eq + Clone + Send + Sync,
+    V: Send + Sync,
+    S: KeyHasher<K> + Default + Send + Sync,
+{
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        let mut map = Self::with_hasher(S::default());
+        map.par_extend(par_iter);
+        map
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V, S> ParallelExtend<(K, V)> for ConcurrentHashMap<K, V, S>
+where
+    K: Eq + Clone + Send + Sync,
+    V: Send + Sync,
+    S: KeyHasher<K> + Send + Sync,
+{
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        (&*self).par_extend(par_iter);
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V, S> ParallelExtend<(K, V)> for &ConcurrentHashMap<K, V, S>
+where
+    K: Eq + Clone + Send + Sync,
+    V: Send + Sync,
+    S: KeyHasher<K> + Send + Sync,
+{
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        let map = *self;
+        let par_iter = par_iter.into_par_iter();
+        if let Some(len) = par_iter.opt_len() {
+            map.reserve(len, &map.guard());
+        }
+
+        par_iter.for_each_init(
+            || map.guard(),
+            |guard, (key, value)| {
+                map.insert(key, value, guard);
+            },
+        );
+    }
+}
+
+impl<K, V, S> IntoIterator for ConcurrentHashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let mut entries = Vec::with_capacity(self.len());
+        self.teardown(|key, value| entries.push((key, value)));
+        IntoIter::new(entries)
+    }
+}
+
+impl<K, V, S> Drop for ConcurrentHashMap<K, V, S> {
+    fn drop(&mut self) {
+        self.teardown(|_, _| {});
+        reclaim::release(&self.collector);
+    }
+}
+
+fn send_ready<T>(
+    drained: &Mutex<Drained<T>>,
+    sender: &Sender<T>,
+    sent: &mut usize,
+) -> Result<(), SendError<T>> {
+    loop {
+        let entry = match drained.lock().ready.pop_front() {
+            Some(entry) => entry,
+            None => return Ok(()),
+        };
+        sender.send(entry)?;
+        *sent += 1;
+    }
+}
+
+fn drain_stalled(progress: u64) -> bool {
+    clock::now().saturating_sub(progress) >= DRAIN_STALL_TIMEOUT.as_nanos() as u64
+}
@@ -0,0 +1,319 @@
+use std::fmt;
+
+use crate::util::sync::epoch::{self, Atomic, Guard, Owned, Shared};
+use crate::{
+    iter::Keys,
+    util::{
+        equivalent::Equivalent,
+        hashing::{KeyHasher, SeededState},
+        reclaim::{Pending, Retire},
+        sync::atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use super::{entry::Entry, ConcurrentHashMap};
+
+pub struct HashMultiMap<K, V, S = SeededState> {
+    map: ConcurrentHashMap<K, Values<V>, S>,
+}
+
+pub struct Values<V> {
+    head: Atomic<ValueNode<V>>,
+    tail: Atomic<ValueNode<V>>,
+    len: AtomicUsize,
+}
+
+pub struct ValueNode<V> {
+    value: V,
+    next: Atomic<ValueNode<V>>,
+}
+
+impl<V> Values<V> {
+    pub fn new(value: V) -> Self {
+        let node = Atomic::new(ValueNode {
+            value,
+            next: Atomic::null(),
+        });
+        Values {
+            head: node.clone(),
+            tail: node,
+            len: AtomicUsize::new(1),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::SeqCst)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter<'g>(&'g self, guard: &'g Guard) -> ValuesIter<'g, V> {
+        ValuesIter {
+            node: self.head.load(Ordering::SeqCst, guard),
+            guard,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The caller must hold the lock of the bin that stores this collection.
+    pub unsafe fn push(&self, value: V, guard: &Guard) {
+        let node = Owned::new(ValueNode {
+            value,
+            next: Atomic::null(),
+        })
+        .into_shared(guard);
+
+        let tail = self.tail.load(Ordering::SeqCst, guard);
+        if tail.is_null() {
+            self.head.store(node, Ordering::SeqCst);
+        } else {
+            tail.deref().next.store(node, Ordering::SeqCst);
+        }
+        self.tail.store(node, Ordering::SeqCst);
+        self.len.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// # Safety
+    ///
+    /// The caller must hold the lock of the bin that stores this collection.
+    pub unsafe fn remove_first<F>(&self, mut f: F, pending: &Pending, guard: &Guard) -> bool
+    where
+        F: FnMut(&V) -> bool,
+    {
+        let mut pred: Shared<'_, ValueNode<V>> = Shared::null();
+        let mut node = self.head.load(Ordering::SeqCst, guard);
+        while !node.is_null() {
+            let node_deref = node.deref();
+            let next = node_deref.next.load(Ordering::SeqCst, guard);
+            if f(&node_deref.value) {
+                if pred.is_null() {
+                    self.head.store(next, Ordering::SeqCst);
+                } else {
+                    pred.deref().next.store(next, Ordering::SeqCst);
+                }
+                if next.is_null() {
+                    self.tail.store(pred, Ordering::SeqCst);
+                }
+                self.len.fetch_sub(1, Ordering::SeqCst);
+                guard.retire(node, pending);
+                return true;
+            }
+            pred = node;
+            node = next;
+        }
+        false
+    }
+}
+
+impl<V> Drop for Values<V> {
+    fn drop(&mut self) {
+        let guard = unsafe { epoch::unprotected() };
+        let mut node = self.head.swap(Shared::null(), Ordering::SeqCst, guard);
+        while !node.is_null() {
+            let owned = unsafe { node.into_owned() };
+            node = owned.next.load(Ordering::SeqCst, guard);
+            drop(owned);
+        }
+    }
+}
+
+impl<V> fmt::Debug for Values<V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guard = epoch::pin();
+        f.debug_list().entries(self.iter(&guard)).finish()
+    }
+}
+
+#[derive(Debug)]
+pub struct ValuesIter<'g, V> {
+    node: Shared<'g, ValueNode<V>>,
+    guard: &'g Guard,
+}
+
+impl<'g, V> ValuesIter<'g, V> {
+    pub fn empty(guard: &'g Guard) -> Self {
+        ValuesIter {
+            node: Shared::null(),
+            guard,
+        }
+    }
+}
+
+impl<'g, V> Iterator for ValuesIter<'g, V> {
+    type Item = &'g V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.node.is_null() {
+            return None;
+        }
+
+        let node = unsafe { self.node.deref() };
+        self.node = node.next.load(Ordering::SeqCst, self.guard);
+        Some(&node.value)
+    }
+}
+
+impl<V> fmt::Debug for ValueNode<V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueNode")
+            .field("value", &self.value)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K, V> HashMultiMap<K, V> {
+    pub fn new() -> Self {
+        HashMultiMap {
+            map: ConcurrentHashMap::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        HashMultiMap {
+            map: ConcurrentHashMap::with_capacity(capacity),
+        }
+    }
+}
+
+impl<K, V, S> HashMultiMap<K, V, S> {
+    pub fn with_hasher(build_hasher: S) -> Self {
+        HashMultiMap {
+            map: ConcurrentHashMap::with_hasher(build_hasher),
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, build_hasher: S) -> Self {
+        HashMultiMap {
+            map: ConcurrentHashMap::with_capacity_and_hasher(capacity, build_hasher),
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+
+    pub fn guard(&self) -> Guard {
+        self.map.guard()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn keys<'g>(&'g self, guard: &'g Guard) -> Keys<'g, K, Values<V>> {
+        self.map.keys(guard)
+    }
+
+    pub fn clear(&self, guard: &Guard) {
+        self.map.clear(guard);
+    }
+}
+
+impl<K, V, S> HashMultiMap<K, V, S>
+where
+    K: Eq,
+{
+    pub fn get_all<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> ValuesIter<'g, V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        match self.map.get(key, guard) {
+            Some(values) => values.iter(guard),
+            None => ValuesIter::empty(guard),
+        }
+    }
+
+    pub fn count<Q>(&self, key: &Q, guard: &Guard) -> usize
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.get(key, guard).map_or(0, Values::len)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.contains_key(key, guard)
+    }
+}
+
+impl<K, V, S> HashMultiMap<K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    pub fn insert(&self, key: K, value: V, guard: &Guard) {
+        match self.map.entry(key, guard) {
+            Entry::Occupied(entry) => unsafe { entry.get().push(value, guard) },
+            Entry::Vacant(entry) => {
+                entry.insert(Values::new(value));
+            }
+        }
+    }
+
+    pub fn remove<Q>(&self, key: &Q, value: &V, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        V: PartialEq,
+    {
+        let hash = self.map.hash(key);
+        let entry = match self.map.lock_entry(hash, key, false, guard) {
+            Ok(entry) => entry,
+            Err(_) => return false,
+        };
+
+        let values = entry.get();
+        if !unsafe { values.remove_first(|v| v == value, self.map.pending(), guard) } {
+            return false;
+        }
+        if values.is_empty() {
+            entry.remove();
+        }
+        true
+    }
+
+    pub fn remove_all<Q>(&self, key: &Q, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.remove(key, guard).is_some()
+    }
+}
+
+impl<K, V, S> Default for HashMultiMap<K, V, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> fmt::Debug for HashMultiMap<K, V, S>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
@@ -0,0 +1,408 @@
+use std::{borrow::Borrow, hash::Hash, time::Duration};
+
+use crate::util::sync::epoch::Guard;
+use crate::{
+    core::table::TableStats,
+    iter::{HashedIter, Iter, Keys, Values},
+    util::{
+        equivalent::Equivalent,
+        hashing::{KeyHasher, SeededState},
+        lease::{Lease, LeaseError},
+        version::VersionError,
+    },
+};
+
+use super::{
+    entry::{CompareExchangeError, Entry, KeyGuard, OccupiedError},
+    raw_entry::{RawEntryBuilder, RawEntryBuilderMut},
+    ConcurrentHashMap,
+};
+
+pub type HashMapRef<'m, K, V, S = SeededState> = Pinned<'m, K, V, S, Guard>;
+
+pub struct Pinned<'m, K, V, S, G> {
+    map: &'m ConcurrentHashMap<K, V, S>,
+    guard: G,
+}
+
+impl<'m, K, V, S, G> Pinned<'m, K, V, S, G>
+where
+    G: Borrow<Guard>,
+{
+    pub fn new(map: &'m ConcurrentHashMap<K, V, S>, guard: G) -> Self {
+        Pinned { map, guard }
+    }
+
+    pub fn map(&self) -> &'m ConcurrentHashMap<K, V, S> {
+        self.map
+    }
+
+    pub fn guard(&self) -> &Guard {
+        self.guard.borrow()
+    }
+
+    pub fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+
+    pub fn hashed_iter(&self) -> HashedIter<'_, K, V> {
+        self.map.hashed_iter(self.guard())
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.map.iter(self.guard())
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.map.keys(self.guard())
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        self.map.values(self.guard())
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn stats(&self) -> TableStats {
+        self.map.stats(self.guard())
+    }
+
+    pub fn clear(&self) {
+        self.map.clear(self.guard())
+    }
+}
+
+impl<'m, K, V, S, G> Pinned<'m, K, V, S, G>
+where
+    K: Eq,
+    G: Borrow<Guard>,
+{
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.get(key, self.guard())
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.get_key_value(key, self.guard())
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.contains_key(key, self.guard())
+    }
+
+    pub fn raw_entry(&self) -> RawEntryBuilder<'_, K, V, S> {
+        self.map.raw_entry(self.guard())
+    }
+
+    pub fn contains_all<Q>(&self, keys: &[&Q]) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.contains_all(keys, self.guard())
+    }
+
+    pub fn get_all_present<Q>(&self, keys: &[&Q]) -> Vec<(usize, &V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.get_all_present(keys, self.guard())
+    }
+
+    pub fn get_and_touch<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.get_and_touch(key, self.guard())
+    }
+
+    pub fn last_touched<Q>(&self, key: &Q) -> Option<u64>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.last_touched(key, self.guard())
+    }
+
+    pub fn lease<Q>(&self, key: &Q, duration: Duration) -> Result<Lease, LeaseError>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.lease(key, duration, self.guard())
+    }
+
+    pub fn lease_wait<Q>(&self, key: &Q, duration: Duration) -> Result<Lease, LeaseError>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.lease_wait(key, duration, self.guard())
+    }
+
+    pub fn release_lease<Q>(&self, key: &Q, lease: Lease) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.release_lease(key, lease, self.guard())
+    }
+
+    pub fn pin_entry<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.pin_entry(key, self.guard())
+    }
+
+    pub fn unpin_entry<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.unpin_entry(key, self.guard())
+    }
+
+    pub fn is_pinned<Q>(&self, key: &Q) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.is_pinned(key, self.guard())
+    }
+
+    pub fn get_meta<Q>(&self, key: &Q) -> Option<u64>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.get_meta(key, self.guard())
+    }
+
+    pub fn set_meta<Q>(&self, key: &Q, meta: u64) -> Option<u64>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.set_meta(key, meta, self.guard())
+    }
+
+    pub fn fetch_update_meta<Q, F>(&self, key: &Q, f: F) -> Option<Result<u64, u64>>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        F: FnMut(u64) -> Option<u64>,
+    {
+        self.map.fetch_update_meta(key, f, self.guard())
+    }
+
+    pub fn get_versioned<Q>(&self, key: &Q) -> Option<(u64, &V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.get_versioned(key, self.guard())
+    }
+
+    pub fn insert_if_version<Q>(
+        &self,
+        key: &Q,
+        generation: u64,
+        value: V,
+    ) -> Result<u64, VersionError<V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map
+            .insert_if_version(key, generation, value, self.guard())
+    }
+
+    pub fn update_cow<Q, F>(&self, key: &Q, f: F) -> Option<&V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        V: Clone,
+        F: FnMut(&mut V),
+    {
+        self.map.update_cow(key, f, self.guard())
+    }
+}
+
+impl<'m, K, V, S, G> Pinned<'m, K, V, S, G>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+    G: Borrow<Guard>,
+{
+    pub fn insert(&self, key: K, value: V) -> Option<&V> {
+        self.map.insert(key, value, self.guard())
+    }
+
+    pub fn insert_owned(&self, key: K, value: V) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.map.insert_owned(key, value, self.guard())
+    }
+
+    pub fn entry(&self, key: K) -> Entry<'_, K, V, S> {
+        self.map.entry(key, self.guard())
+    }
+
+    pub fn raw_entry_mut(&self) -> RawEntryBuilderMut<'_, K, V, S> {
+        self.map.raw_entry_mut(self.guard())
+    }
+
+    pub fn lock_key(&self, key: K) -> KeyGuard<'_, K, V, S> {
+        self.map.lock_key(key, self.guard())
+    }
+
+    pub fn get_with<F>(&self, key: K, loader: F) -> &V
+    where
+        K: Hash,
+        F: FnOnce() -> V,
+    {
+        self.map.get_with(key, loader, self.guard())
+    }
+
+    pub fn try_get_with<F, E>(&self, key: K, loader: F) -> Result<&V, E>
+    where
+        K: Hash,
+        F: FnOnce() -> Result<V, E>,
+    {
+        self.map.try_get_with(key, loader, self.guard())
+    }
+
+    pub fn try_insert(&self, key: K, value: V) -> Result<&V, OccupiedError<'_, V>> {
+        self.map.try_insert(key, value, self.guard())
+    }
+
+    pub fn get_or_insert_with<F>(&self, key: K, f: F) -> &V
+    where
+        F: FnOnce() -> V,
+    {
+        self.map.get_or_insert_with(key, f, self.guard())
+    }
+
+    pub fn compute<F>(&self, key: K, f: F) -> Option<&V>
+    where
+        F: FnOnce(&K, Option<&V>) -> Option<V>,
+    {
+        self.map.compute(key, f, self.guard())
+    }
+
+    pub fn compute_if_present<Q, F>(&self, key: &Q, f: F) -> Option<&V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        F: FnOnce(&K, &V) -> Option<V>,
+    {
+        self.map.compute_if_present(key, f, self.guard())
+    }
+
+    pub fn update<Q, F>(&self, key: &Q, f: F) -> Option<(&V, &V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        F: FnOnce(&V) -> V,
+    {
+        self.map.update(key, f, self.guard())
+    }
+
+    pub fn update_many<Q, F>(&self, keys: &[&Q], f: F) -> bool
+    where
+        Q: ?Sized + Eq + Equivalent<K>,
+        S: KeyHasher<Q>,
+        V: Clone,
+        F: FnOnce(&mut [V]),
+    {
+        self.map.update_many(keys, f, self.guard())
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.remove(key, self.guard())
+    }
+
+    pub fn remove_if<Q, F>(&self, key: &Q, f: F) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        F: FnOnce(&V) -> bool,
+    {
+        self.map.remove_if(key, f, self.guard())
+    }
+
+    pub fn compare_exchange<Q>(
+        &self,
+        key: &Q,
+        expected: &V,
+        new: V,
+    ) -> Result<&V, CompareExchangeError<'_, V>>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+        V: PartialEq,
+    {
+        self.map.compare_exchange(key, expected, new, self.guard())
+    }
+
+    pub fn retain<F>(&self, f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.map.retain(f, self.guard())
+    }
+
+    pub fn purge_expired(&self) {
+        self.map.purge_expired(self.guard())
+    }
+}
+
+impl<'m, K, V, S, G> Pinned<'m, K, Box<V>, S, G>
+where
+    K: Eq,
+    V: ?Sized,
+    G: Borrow<Guard>,
+{
+    pub fn get_unsized<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.get_unsized(key, self.guard())
+    }
+
+    pub fn insert_boxed<B>(&self, key: K, value: B) -> Option<&V>
+    where
+        K: Clone,
+        S: KeyHasher<K>,
+        B: Into<Box<V>>,
+    {
+        self.map.insert_boxed(key, value, self.guard())
+    }
+}
@@ -0,0 +1,154 @@
+use crate::util::sync::epoch::Guard;
+use crate::util::{
+    equivalent::Equivalent,
+    hashing::{self, KeyHasher},
+};
+
+use super::{
+    entry::{OccupiedEntry, Slot, VacantEntry},
+    ConcurrentHashMap,
+};
+
+struct Matches<F>(F);
+
+impl<K, F> Equivalent<K> for Matches<F>
+where
+    F: Fn(&K) -> bool,
+{
+    fn equivalent(&self, key: &K) -> bool {
+        (self.0)(key)
+    }
+}
+
+pub struct RawEntryBuilder<'g, K, V, S> {
+    map: &'g ConcurrentHashMap<K, V, S>,
+    guard: &'g Guard,
+}
+
+impl<'g, K, V, S> RawEntryBuilder<'g, K, V, S> {
+    pub fn new(map: &'g ConcurrentHashMap<K, V, S>, guard: &'g Guard) -> Self {
+        RawEntryBuilder { map, guard }
+    }
+}
+
+impl<'g, K, V, S> RawEntryBuilder<'g, K, V, S>
+where
+    K: Eq,
+{
+    pub fn from_key<Q>(self, key: &Q) -> Option<(&'g K, &'g V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.map.get_key_value(key, self.guard)
+    }
+
+    pub fn from_key_hashed_nocheck<Q>(self, hash: u64, key: &Q) -> Option<(&'g K, &'g V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        self.map
+            .get_key_value_hashed(hashing::spread(hash), key, self.guard)
+    }
+
+    pub fn from_hash<F>(self, hash: u64, is_match: F) -> Option<(&'g K, &'g V)>
+    where
+        F: Fn(&K) -> bool,
+    {
+        self.from_key_hashed_nocheck(hash, &Matches(is_match))
+    }
+}
+
+pub enum RawEntryMut<'g, K, V, S> {
+    Occupied(OccupiedEntry<'g, K, V, S>),
+    Vacant(RawVacantEntry<'g, K, V, S>),
+}
+
+pub struct RawVacantEntry<'g, K, V, S> {
+    map: &'g ConcurrentHashMap<K, V, S>,
+    hash: u64,
+    slot: Slot<'g, K, V>,
+    guard: &'g Guard,
+}
+
+pub struct RawEntryBuilderMut<'g, K, V, S> {
+    map: &'g ConcurrentHashMap<K, V, S>,
+    guard: &'g Guard,
+}
+
+impl<'g, K, V, S> RawEntryBuilderMut<'g, K, V, S> {
+    pub fn new(map: &'g ConcurrentHashMap<K, V, S>, guard: &'g Guard) -> Self {
+        RawEntryBuilderMut { map, guard }
+    }
+}
+
+impl<'g, K, V, S> RawEntryBuilderMut<'g, K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    pub fn from_key<Q>(self, key: &Q) -> RawEntryMut<'g, K, V, S>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        let hash = self.map.hasher().hash_key(key);
+        self.from_key_hashed_nocheck(hash, key)
+    }
+
+    pub fn from_key_hashed_nocheck<Q>(self, hash: u64, key: &Q) -> RawEntryMut<'g, K, V, S>
+    where
+        Q: ?Sized + Equivalent<K>,
+    {
+        let hash = hashing::spread(hash);
+        match self.map.lock_entry(hash, key, true, self.guard) {
+            Ok(entry) => RawEntryMut::Occupied(entry),
+            Err(slot) => RawEntryMut::Vacant(RawVacantEntry {
+                map: self.map,
+                hash,
+                slot,
+                guard: self.guard,
+            }),
+        }
+    }
+
+    pub fn from_hash<F>(self, hash: u64, is_match: F) -> RawEntryMut<'g, K, V, S>
+    where
+        F: Fn(&K) -> bool,
+    {
+        self.from_key_hashed_nocheck(hash, &Matches(is_match))
+    }
+}
+
+impl<'g, K, V, S> RawEntryMut<'g, K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    pub fn or_insert(self, key: K, value: V) -> &'g V {
+        self.or_insert_with(|| (key, value))
+    }
+
+    pub fn or_insert_with<F>(self, f: F) -> &'g V
+    where
+        F: FnOnce() -> (K, V),
+    {
+        match self {
+            RawEntryMut::Occupied(entry) => entry.get(),
+            RawEntryMut::Vacant(entry) => {
+                let (key, value) = f();
+                entry.insert(key, value)
+            }
+        }
+    }
+}
+
+impl<'g, K, V, S> RawVacantEntry<'g, K, V, S>
+where
+    K: Eq + Clone,
+    S: KeyHasher<K>,
+{
+    pub fn insert(self, key: K, value: V) -> &'g V {
+        VacantEntry::new(self.map, self.hash, key, self.slot, self.guard).insert(value)
+    }
+}
@@ -0,0 +1,150 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::util::hashing::KeyHasher;
+
+use super::{set::HashSet, ConcurrentHashMap};
+
+const MAX_PRESIZED_CAPACITY: usize = 1 << 20;
+
+impl<K, V, S> Serialize for ConcurrentHashMap<K, V, S>
+where
+    K: Serialize,
+    V: Serialize,
+{
+    fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error>
+    where
+        Z: Serializer,
+    {
+        let guard = self.guard();
+        let entries: Vec<_> = self.iter(&guard).collect();
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (key, value) in entries {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, K, V, S> Deserialize<'de> for ConcurrentHashMap<K, V, S>
+where
+    K: Deserialize<'de> + Eq + Clone,
+    V: Deserialize<'de>,
+    S: KeyHasher<K> + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MapVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct MapVisitor<K, V, S> {
+    marker: PhantomData<ConcurrentHashMap<K, V, S>>,
+}
+
+impl<'de, K, V, S> Visitor<'de> for MapVisitor<K, V, S>
+where
+    K: Deserialize<'de> + Eq + Clone,
+    V: Deserialize<'de>,
+    S: KeyHasher<K> + Default,
+{
+    type Value = ConcurrentHashMap<K, V, S>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let map = ConcurrentHashMap::with_capacity_and_hasher(
+            access.size_hint().unwrap_or(0).min(MAX_PRESIZED_CAPACITY),
+            S::default(),
+        );
+        {
+            let guard = map.guard();
+            while let Some((key, value)) = access.next_entry()? {
+                map.insert(key, value, &guard);
+            }
+        }
+        Ok(map)
+    }
+}
+
+impl<T, S> Serialize for HashSet<T, S>
+where
+    T: Serialize,
+{
+    fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error>
+    where
+        Z: Serializer,
+    {
+        let guard = self.guard();
+        let values: Vec<_> = self.iter(&guard).collect();
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T, S> Deserialize<'de> for HashSet<T, S>
+where
+    T: Deserialize<'de> + Eq + Clone,
+    S: KeyHasher<T> + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SetVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct SetVisitor<T, S> {
+    marker: PhantomData<HashSet<T, S>>,
+}
+
+impl<'de, T, S> Visitor<'de> for SetVisitor<T, S>
+where
+    T: Deserialize<'de> + Eq + Clone,
+    S: KeyHasher<T> + Default,
+{
+    type Value = HashSet<T, S>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let set = HashSet::with_capacity_and_hasher(
+            access.size_hint().unwrap_or(0).min(MAX_PRESIZED_CAPACITY),
+            S::default(),
+        );
+        {
+            let guard = set.guard();
+            while let Some(value) = access.next_element()? {
+                set.insert(value, &guard);
+            }
+        }
+        Ok(set)
+    }
+}
@@ -0,0 +1,353 @@
+use std::{
+    fmt,
+    iter::{Chain, FromIterator},
+};
+
+use crate::util::sync::epoch::Guard;
+use crate::{
+    iter::Keys,
+    util::{
+        equivalent::Equivalent,
+        hashing::{KeyHasher, SeededState},
+    },
+};
+
+use super::{builder::Builder, ConcurrentHashMap};
+
+pub struct HashSet<T, S = SeededState> {
+    map: ConcurrentHashMap<T, (), S>,
+}
+
+impl<T> HashSet<T> {
+    pub fn new() -> Self {
+        HashSet {
+            map: ConcurrentHashMap::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        HashSet {
+            map: ConcurrentHashMap::with_capacity(capacity),
+        }
+    }
+}
+
+impl<T, S> HashSet<T, S> {
+    pub fn with_hasher(build_hasher: S) -> Self {
+        HashSet {
+            map: ConcurrentHashMap::with_hasher(build_hasher),
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, build_hasher: S) -> Self {
+        HashSet {
+            map: ConcurrentHashMap::with_capacity_and_hasher(capacity, build_hasher),
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+
+    pub fn guard(&self) -> Guard {
+        self.map.guard()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn iter<'g>(&'g self, guard: &'g Guard) -> Keys<'g, T, ()> {
+        self.map.keys(guard)
+    }
+
+    pub fn clear(&self, guard: &Guard) {
+        self.map.clear(guard);
+    }
+}
+
+impl<T, S> HashSet<T, S>
+where
+    T: Eq,
+{
+    pub fn contains<Q>(&self, value: &Q, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<T>,
+        S: KeyHasher<Q>,
+    {
+        self.map.contains_key(value, guard)
+    }
+
+    pub fn get<'g, Q>(&'g self, value: &Q, guard: &'g Guard) -> Option<&'g T>
+    where
+        Q: ?Sized + Equivalent<T>,
+        S: KeyHasher<Q>,
+    {
+        self.map.get_key_value(value, guard).map(|(value, _)| value)
+    }
+
+    pub fn is_disjoint(&self, other: &HashSet<T, S>, guard: &Guard) -> bool
+    where
+        S: KeyHasher<T>,
+    {
+        self.iter(guard).all(|value| !other.contains(value, guard))
+    }
+
+    pub fn is_subset(&self, other: &HashSet<T, S>, guard: &Guard) -> bool
+    where
+        S: KeyHasher<T>,
+    {
+        self.iter(guard).all(|value| other.contains(value, guard))
+    }
+
+    pub fn is_superset(&self, other: &HashSet<T, S>, guard: &Guard) -> bool
+    where
+        S: KeyHasher<T>,
+    {
+        other.is_subset(self, guard)
+    }
+
+    pub fn difference<'g>(
+        &'g self,
+        other: &'g HashSet<T, S>,
+        guard: &'g Guard,
+    ) -> Difference<'g, T, S> {
+        Difference {
+            iter: self.iter(guard),
+            other,
+            guard,
+        }
+    }
+
+    pub fn intersection<'g>(
+        &'g self,
+        other: &'g HashSet<T, S>,
+        guard: &'g Guard,
+    ) -> Intersection<'g, T, S> {
+        Intersection {
+            iter: self.iter(guard),
+            other,
+            guard,
+        }
+    }
+
+    pub fn union<'g>(&'g self, other: &'g HashSet<T, S>, guard: &'g Guard) -> Union<'g, T, S>
+    where
+        S: KeyHasher<T>,
+    {
+        Union {
+            iter: self.iter(guard).chain(other.difference(self, guard)),
+        }
+    }
+
+    pub fn symmetric_difference<'g>(
+        &'g self,
+        other: &'g HashSet<T, S>,
+        guard: &'g Guard,
+    ) -> SymmetricDifference<'g, T, S>
+    where
+        S: KeyHasher<T>,
+    {
+        SymmetricDifference {
+            iter: self
+                .difference(other, guard)
+                .chain(other.difference(self, guard)),
+        }
+    }
+}
+
+impl<T, S> HashSet<T, S>
+where
+    T: Eq + Clone,
+    S: KeyHasher<T>,
+{
+    pub fn insert(&self, value: T, guard: &Guard) -> bool {
+        self.map.insert(value, (), guard).is_none()
+    }
+
+    pub fn remove<Q>(&self, value: &Q, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<T>,
+        S: KeyHasher<Q>,
+    {
+        self.map.remove(value, guard).is_some()
+    }
+
+    pub fn retain<F>(&self, mut f: F, guard: &Guard)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.map.retain(|value, _| f(value), guard);
+    }
+}
+
+#[derive(Debug)]
+pub struct Difference<'g, T, S> {
+    iter: Keys<'g, T, ()>,
+    other: &'g HashSet<T, S>,
+    guard: &'g Guard,
+}
+
+impl<'g, T, S> Iterator for Difference<'g, T, S>
+where
+    T: Eq,
+    S: KeyHasher<T>,
+{
+    type Item = &'g T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let guard = self.guard;
+        let other = self.other;
+        self.iter
+            .by_ref()
+            .find(|value| !other.contains(*value, guard))
+    }
+}
+
+#[derive(Debug)]
+pub struct Intersection<'g, T, S> {
+    iter: Keys<'g, T, ()>,
+    other: &'g HashSet<T, S>,
+    guard: &'g Guard,
+}
+
+impl<'g, T, S> Iterator for Intersection<'g, T, S>
+where
+    T: Eq,
+    S: KeyHasher<T>,
+{
+    type Item = &'g T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let guard = self.guard;
+        let other = self.other;
+        self.iter
+            .by_ref()
+            .find(|value| other.contains(*value, guard))
+    }
+}
+
+#[derive(Debug)]
+pub struct Union<'g, T, S> {
+    iter: Chain<Keys<'g, T, ()>, Difference<'g, T, S>>,
+}
+
+impl<'g, T, S> Iterator for Union<'g, T, S>
+where
+    T: Eq,
+    S: KeyHasher<T>,
+{
+    type Item = &'g T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+#[derive(Debug)]
+pub struct SymmetricDifference<'g, T, S> {
+    iter: Chain<Difference<'g, T, S>, Difference<'g, T, S>>,
+}
+
+impl<'g, T, S> Iterator for SymmetricDifference<'g, T, S>
+where
+    T: Eq,
+    S: KeyHasher<T>,
+{
+    type Item = &'g T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+impl<T, S> Default for HashSet<T, S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<T, S> Clone for HashSet<T, S>
+where
+    T: Eq + Clone,
+    S: KeyHasher<T> + Clone,
+{
+    fn clone(&self) -> Self {
+        HashSet {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<T, S> PartialEq for HashSet<T, S>
+where
+    T: Eq,
+    S: KeyHasher<T>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<T, S> Eq for HashSet<T, S>
+where
+    T: Eq,
+    S: KeyHasher<T>,
+{
+}
+
+impl<T, S> fmt::Debug for HashSet<T, S>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guard = self.guard();
+        let mut values = self.iter(&guard);
+        let mut set = f.debug_set();
+        set.entries(values.by_ref().take(self.map.debug_limit));
+        if values.next().is_some() {
+            return set.finish_non_exhaustive();
+        }
+        set.finish()
+    }
+}
+
+impl<T, S> FromIterator<T> for HashSet<T, S>
+where
+    T: Eq + Clone,
+    S: KeyHasher<T> + Default,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let mut set = HashSet {
+            map: Builder::new()
+                .capacity(iter.size_hint().0)
+                .build_with_hasher(S::default()),
+        };
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S> Extend<T> for HashSet<T, S>
+where
+    T: Eq + Clone,
+    S: KeyHasher<T>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.map.extend(iter.into_iter().map(|value| (value, ())));
+    }
+}
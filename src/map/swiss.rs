@@ -0,0 +1,370 @@
+use std::fmt;
+
+use crossbeam_utils::CachePadded;
+
+use crate::util::sync::epoch::{self, unprotected, Atomic, Collector, Guard, Owned, Shared};
+use crate::{
+    core::group::{self, Group, DELETED, EMPTY},
+    util::{
+        counter::Counter,
+        equivalent::Equivalent,
+        hashing::{self, KeyHasher, SeededState},
+        lock::Mutex,
+        reclaim,
+        sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+};
+
+struct SwissTable<K, V> {
+    ctrl: Box<[AtomicU64]>,
+    slots: Box<[Atomic<(K, V)>]>,
+    locks: Box<[Mutex<()>]>,
+    used: AtomicUsize,
+}
+
+impl<K, V> SwissTable<K, V> {
+    fn new(capacity: usize) -> Self {
+        SwissTable {
+            ctrl: (0..capacity / 8)
+                .map(|_| AtomicU64::new(u64::MAX))
+                .collect(),
+            slots: (0..capacity).map(|_| Atomic::null()).collect(),
+            locks: (0..capacity / Group::WIDTH)
+                .map(|_| Mutex::new(()))
+                .collect(),
+            used: AtomicUsize::new(0),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn is_full(&self) -> bool {
+        self.used.load(Ordering::Relaxed) >= self.capacity() - self.capacity() / 8
+    }
+
+    fn home(&self, hash: u64) -> usize {
+        hashing::spread(hash) as usize & (self.locks.len() - 1)
+    }
+
+    fn group(&self, group: usize) -> Group {
+        Group::load(&self.ctrl[group * Group::WORDS..][..Group::WORDS])
+    }
+
+    fn probe_seq(&self, hash: u64) -> impl Iterator<Item = usize> {
+        let mask = self.locks.len() - 1;
+        let mut group = self.home(hash);
+        (0..self.locks.len()).map(move |stride| {
+            group = (group + stride) & mask;
+            group
+        })
+    }
+
+    fn find<'g>(
+        &self,
+        hash: u64,
+        mut eq: impl FnMut(&K) -> bool,
+        guard: &'g Guard,
+    ) -> Option<(usize, Shared<'g, (K, V)>)> {
+        let tag = group::tag(hash);
+        for g in self.probe_seq(hash) {
+            let ctrl = self.group(g);
+            for i in ctrl.match_tag(tag) {
+                let index = g * Group::WIDTH + i;
+                let entry = self.slots[index].load(Ordering::Acquire, guard);
+                if let Some((key, _)) = unsafe { entry.as_ref() } {
+                    if eq(key) {
+                        return Some((index, entry));
+                    }
+                }
+            }
+            if ctrl.match_empty().any() {
+                return None;
+            }
+        }
+        None
+    }
+
+    fn claim(&self, hash: u64) -> Option<usize> {
+        let tag = group::tag(hash);
+        for g in self.probe_seq(hash) {
+            for i in self.group(g).match_free() {
+                let index = g * Group::WIDTH + i;
+                if let Some(previous) = group::claim_byte(&self.ctrl, index, tag) {
+                    if previous == EMPTY {
+                        self.used.fetch_add(1, Ordering::Relaxed);
+                    }
+                    return Some(index);
+                }
+            }
+        }
+        None
+    }
+}
+
+fn capacity_for(len: usize) -> usize {
+    (len + len / 7 + 1).next_power_of_two().max(Group::WIDTH)
+}
+
+pub struct SwissMap<K, V, S = SeededState> {
+    table: CachePadded<Atomic<SwissTable<K, V>>>,
+    count: Counter,
+    build_hasher: S,
+    collector: Collector,
+}
+
+impl<K, V> SwissMap<K, V> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, SeededState::new())
+    }
+}
+
+impl<K, V> Default for SwissMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> SwissMap<K, V, S> {
+    pub fn with_hasher(build_hasher: S) -> Self {
+        Self::with_capacity_and_hasher(0, build_hasher)
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, build_hasher: S) -> Self {
+        SwissMap {
+            table: CachePadded::new(Atomic::new(SwissTable::new(capacity_for(capacity)))),
+            count: Counter::new(),
+            build_hasher,
+            collector: epoch::default_collector().clone(),
+        }
+    }
+
+    pub fn with_collector(mut self, collector: Collector) -> Self {
+        self.collector = collector;
+        self
+    }
+
+    pub fn collector(&self) -> &Collector {
+        &self.collector
+    }
+
+    pub fn guard(&self) -> Guard {
+        reclaim::pin(&self.collector)
+    }
+
+    fn check_guard(&self, guard: &Guard) {
+        if let Some(collector) = guard.collector() {
+            assert!(
+                *collector == self.collector,
+                "guard was not created from this map's collector"
+            );
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.build_hasher
+    }
+
+    pub fn len(&self) -> usize {
+        self.count.sum().max(0) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.count.is_positive()
+    }
+
+    pub fn capacity(&self, guard: &Guard) -> usize {
+        self.check_guard(guard);
+        self.table(guard).capacity()
+    }
+
+    fn table<'g>(&self, guard: &'g Guard) -> &'g SwissTable<K, V> {
+        unsafe { self.table.load(Ordering::Acquire, guard).deref() }
+    }
+
+    pub fn iter<'g>(&'g self, guard: &'g Guard) -> SwissIter<'g, K, V> {
+        self.check_guard(guard);
+        SwissIter {
+            slots: self.table(guard).slots.iter(),
+            guard,
+        }
+    }
+
+    pub fn keys<'g>(&'g self, guard: &'g Guard) -> impl Iterator<Item = &'g K> {
+        self.iter(guard).map(|(key, _)| key)
+    }
+
+    pub fn values<'g>(&'g self, guard: &'g Guard) -> impl Iterator<Item = &'g V> {
+        self.iter(guard).map(|(_, value)| value)
+    }
+}
+
+impl<K, V, S> SwissMap<K, V, S>
+where
+    K: Eq,
+{
+    pub fn get_key_value<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<(&'g K, &'g V)>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.check_guard(guard);
+        let hash = self.build_hasher.hash_key(key);
+        let (_, entry) = self
+            .table(guard)
+            .find(hash, |other| key.equivalent(other), guard)?;
+        let (key, value) = unsafe { entry.deref() };
+        Some((key, value))
+    }
+
+    pub fn get<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key, guard).map(|(_, value)| value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q, guard: &Guard) -> bool
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.get_key_value(key, guard).is_some()
+    }
+
+    pub fn remove<'g, Q>(&'g self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: ?Sized + Equivalent<K>,
+        S: KeyHasher<Q>,
+    {
+        self.check_guard(guard);
+        let hash = self.build_hasher.hash_key(key);
+        loop {
+            let shared = self.table.load(Ordering::Acquire, guard);
+            let table = unsafe { shared.deref() };
+            let _lock = table.locks[table.home(hash)].lock();
+            if self.table.load(Ordering::Acquire, guard) != shared {
+                continue;
+            }
+
+            let (index, entry) = table.find(hash, |other| key.equivalent(other), guard)?;
+            table.slots[index].store(Shared::null(), Ordering::Release);
+            group::set_byte(&table.ctrl, index, DELETED);
+            self.count.add(-1);
+            unsafe { guard.defer_destroy(entry) };
+            return Some(unsafe { &entry.deref().1 });
+        }
+    }
+}
+
+impl<K, V, S> SwissMap<K, V, S>
+where
+    K: Eq,
+    S: KeyHasher<K>,
+{
+    pub fn insert<'g>(&'g self, key: K, value: V, guard: &'g Guard) -> Option<&'g V> {
+        self.check_guard(guard);
+        let hash = self.build_hasher.hash_key(&key);
+        let new = Owned::new((key, value));
+        loop {
+            let shared = self.table.load(Ordering::Acquire, guard);
+            let table = unsafe { shared.deref() };
+            let lock = table.locks[table.home(hash)].lock();
+            if self.table.load(Ordering::Acquire, guard) != shared {
+                continue;
+            }
+
+            if let Some((index, old)) = table.find(hash, |other| *other == new.0, guard) {
+                table.slots[index].store(new, Ordering::Release);
+                unsafe { guard.defer_destroy(old) };
+                return Some(unsafe { &old.deref().1 });
+            }
+
+            if !table.is_full() {
+                if let Some(index) = table.claim(hash) {
+                    table.slots[index].store(new, Ordering::Release);
+                    self.count.add(1);
+                    return None;
+                }
+            }
+
+            drop(lock);
+            self.resize(shared, guard);
+        }
+    }
+
+    fn resize(&self, shared: Shared<'_, SwissTable<K, V>>, guard: &Guard) {
+        let table = unsafe { shared.deref() };
+        let _locks: Vec<_> = table.locks.iter().map(|lock| lock.lock()).collect();
+        if self.table.load(Ordering::Acquire, guard) != shared {
+            return;
+        }
+
+        let new = SwissTable::new(capacity_for(self.len() * 2));
+        for slot in table.slots.iter() {
+            let entry = slot.load(Ordering::Relaxed, guard);
+            if let Some((key, _)) = unsafe { entry.as_ref() } {
+                let index = new
+                    .claim(self.build_hasher.hash_key(key))
+                    .expect("resized table has room for every entry");
+                new.slots[index].store(entry, Ordering::Relaxed);
+            }
+        }
+
+        self.table.store(Owned::new(new), Ordering::Release);
+        unsafe { guard.defer_destroy(shared) };
+    }
+}
+
+impl<K, V, S> Drop for SwissMap<K, V, S> {
+    fn drop(&mut self) {
+        unsafe {
+            let guard = unprotected();
+            let table = self.table.load(Ordering::Relaxed, guard);
+            for slot in table.deref().slots.iter() {
+                let entry = slot.load(Ordering::Relaxed, guard);
+                if !entry.is_null() {
+                    drop(entry.into_owned());
+                }
+            }
+            drop(table.into_owned());
+        }
+        reclaim::release(&self.collector);
+    }
+}
+
+impl<K, V, S> fmt::Debug for SwissMap<K, V, S>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guard = self.guard();
+        f.debug_map().entries(self.iter(&guard)).finish()
+    }
+}
+
+pub struct SwissIter<'g, K, V> {
+    slots: std::slice::Iter<'g, Atomic<(K, V)>>,
+    guard: &'g Guard,
+}
+
+impl<'g, K, V> Iterator for SwissIter<'g, K, V> {
+    type Item = (&'g K, &'g V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in self.slots.by_ref() {
+            let entry = slot.load(Ordering::Acquire, self.guard);
+            if let Some((key, value)) = unsafe { entry.as_ref() } {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use crate::util::hashing::{KeyHasher, SeededState};
+use crate::util::sync::epoch::Guard;
+
+use super::{builder::Builder, ConcurrentHashMap};
+
+pub struct ValuePool<V, S = SeededState> {
+    values: ConcurrentHashMap<Arc<V>, (), S>,
+}
+
+impl<V> ValuePool<V> {
+    pub fn new() -> Self {
+        Self::with_hasher(SeededState::new())
+    }
+}
+
+impl<V, S> ValuePool<V, S> {
+    pub fn with_hasher(build_hasher: S) -> Self {
+        ValuePool {
+            values: Builder::new()
+                .read_mostly(true)
+                .build_with_hasher(build_hasher),
+        }
+    }
+
+    pub fn guard(&self) -> Guard {
+        self.values.guard()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<V, S> ValuePool<V, S>
+where
+    V: Eq,
+    S: KeyHasher<V> + KeyHasher<Arc<V>>,
+{
+    pub fn intern(&self, value: V, guard: &Guard) -> Arc<V> {
+        if let Some(node) = self.values.find_node(&value, guard) {
+            return node.key.clone();
+        }
+
+        let value = Arc::new(value);
+        self.values.insert(value.clone(), (), guard);
+        self.values.find_node(&*value, guard).unwrap().key.clone()
+    }
+
+    pub fn insert_dedup<K, T>(
+        &self,
+        map: &ConcurrentHashMap<K, Arc<V>, T>,
+        key: K,
+        value: V,
+        guard: &Guard,
+    ) -> Arc<V>
+    where
+        K: Eq + Clone,
+        T: KeyHasher<K>,
+    {
+        let value = self.intern(value, guard);
+        map.insert(key, value.clone(), guard);
+        value
+    }
+
+    pub fn purge(&self, guard: &Guard) -> usize {
+        let before = self.values.len();
+        self.values
+            .retain(|value, _| Arc::strong_count(value) > 1, guard);
+        before.saturating_sub(self.values.len())
+    }
+}
+
+impl<V> Default for ValuePool<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    convert::TryInto,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+const PREFIX: usize = 8;
+const INLINE: usize = 30;
+
+#[repr(transparent)]
+pub struct Bytes([u8]);
+
+impl Bytes {
+    pub fn new(bytes: &[u8]) -> &Self {
+        unsafe { &*(bytes as *const [u8] as *const Self) }
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Hash for Bytes {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl PartialEq for Bytes {
+    fn eq(&self, other: &Self) -> bool {
+        bytes_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Bytes {}
+
+impl PartialOrd for Bytes {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Bytes {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl fmt::Debug for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Clone)]
+pub struct BytesKey {
+    pub len: usize,
+    pub prefix: u64,
+    pub bytes: Box<[u8]>,
+}
+
+impl BytesKey {
+    pub fn new(bytes: Box<[u8]>) -> Self {
+        let mut prefix = [0; PREFIX];
+        let head = bytes.len().min(PREFIX);
+        prefix[..head].copy_from_slice(&bytes[..head]);
+
+        BytesKey {
+            len: bytes.len(),
+            prefix: u64::from_be_bytes(prefix),
+            bytes,
+        }
+    }
+
+    pub fn as_bytes(&self) -> &Bytes {
+        Bytes::new(&self.bytes)
+    }
+}
+
+impl From<&[u8]> for BytesKey {
+    fn from(bytes: &[u8]) -> Self {
+        BytesKey::new(bytes.into())
+    }
+}
+
+impl From<Vec<u8>> for BytesKey {
+    fn from(bytes: Vec<u8>) -> Self {
+        BytesKey::new(bytes.into_boxed_slice())
+    }
+}
+
+impl Borrow<Bytes> for BytesKey {
+    fn borrow(&self) -> &Bytes {
+        self.as_bytes()
+    }
+}
+
+impl Hash for BytesKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state)
+    }
+}
+
+impl PartialEq for BytesKey {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len || self.prefix != other.prefix {
+            return false;
+        }
+        if self.len <= PREFIX {
+            return true;
+        }
+        bytes_eq(&self.bytes[PREFIX..], &other.bytes[PREFIX..])
+    }
+}
+
+impl Eq for BytesKey {}
+
+impl PartialOrd for BytesKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BytesKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.prefix.cmp(&other.prefix) {
+            Ordering::Equal => self.bytes.cmp(&other.bytes),
+            ordering => ordering,
+        }
+    }
+}
+
+impl fmt::Debug for BytesKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.bytes.fmt(f)
+    }
+}
+
+#[derive(Clone)]
+pub enum InlineBytesKey {
+    Inline { len: u8, bytes: [u8; INLINE] },
+    Heap(Box<[u8]>),
+}
+
+impl InlineBytesKey {
+    pub fn new(bytes: &[u8]) -> Self {
+        if bytes.len() > INLINE {
+            return InlineBytesKey::Heap(bytes.into());
+        }
+        let mut inline = [0; INLINE];
+        inline[..bytes.len()].copy_from_slice(bytes);
+        InlineBytesKey::Inline {
+            len: bytes.len() as u8,
+            bytes: inline,
+        }
+    }
+
+    pub fn is_inline(&self) -> bool {
+        matches!(self, InlineBytesKey::Inline { .. })
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            InlineBytesKey::Inline { len, bytes } => &bytes[..*len as usize],
+            InlineBytesKey::Heap(bytes) => bytes,
+        }
+    }
+
+    pub fn as_bytes(&self) -> &Bytes {
+        Bytes::new(self.as_slice())
+    }
+}
+
+impl From<&[u8]> for InlineBytesKey {
+    fn from(bytes: &[u8]) -> Self {
+        InlineBytesKey::new(bytes)
+    }
+}
+
+impl From<Vec<u8>> for InlineBytesKey {
+    fn from(bytes: Vec<u8>) -> Self {
+        bytes.into_boxed_slice().into()
+    }
+}
+
+impl From<Box<[u8]>> for InlineBytesKey {
+    fn from(bytes: Box<[u8]>) -> Self {
+        if bytes.len() > INLINE {
+            InlineBytesKey::Heap(bytes)
+        } else {
+            InlineBytesKey::new(&bytes)
+        }
+    }
+}
+
+impl Borrow<Bytes> for InlineBytesKey {
+    fn borrow(&self) -> &Bytes {
+        self.as_bytes()
+    }
+}
+
+impl Hash for InlineBytesKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state)
+    }
+}
+
+impl PartialEq for InlineBytesKey {
+    fn eq(&self, other: &Self) -> bool {
+        bytes_eq(self.as_slice(), other.as_slice())
+    }
+}
+
+impl Eq for InlineBytesKey {}
+
+impl PartialOrd for InlineBytesKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for InlineBytesKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl fmt::Debug for InlineBytesKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+pub fn bytes_eq(left: &[u8], right: &[u8]) -> bool {
+    use std::arch::x86_64::{__m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8};
+
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut left_chunks = left.chunks_exact(16);
+    let mut right_chunks = right.chunks_exact(16);
+    for (l, r) in (&mut left_chunks).zip(&mut right_chunks) {
+        let mask = unsafe {
+            let l = _mm_loadu_si128(l.as_ptr() as *const __m128i);
+            let r = _mm_loadu_si128(r.as_ptr() as *const __m128i);
+            _mm_movemask_epi8(_mm_cmpeq_epi8(l, r))
+        };
+        if mask != 0xffff {
+            return false;
+        }
+    }
+    words_eq(left_chunks.remainder(), right_chunks.remainder())
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub fn bytes_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len() && words_eq(left, right)
+}
+
+fn words_eq(left: &[u8], right: &[u8]) -> bool {
+    let mut left_words = left.chunks_exact(8);
+    let mut right_words = right.chunks_exact(8);
+    for (l, r) in (&mut left_words).zip(&mut right_words) {
+        if u64::from_ne_bytes(l.try_into().unwrap()) != u64::from_ne_bytes(r.try_into().unwrap()) {
+            return false;
+        }
+    }
+    left_words.remainder() == right_words.remainder()
+}
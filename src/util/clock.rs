@@ -0,0 +1,26 @@
+#[cfg(not(all(target_arch = "wasm32", not(target_feature = "atomics"))))]
+use std::{sync::OnceLock, time::Instant};
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+use super::sync::atomic::{AtomicU64, Ordering};
+
+pub const TRACKS_TIME: bool = cfg!(not(all(
+    target_arch = "wasm32",
+    not(target_feature = "atomics")
+)));
+
+#[cfg(not(all(target_arch = "wasm32", not(target_feature = "atomics"))))]
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+static TICKS: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(not(all(target_arch = "wasm32", not(target_feature = "atomics"))))]
+pub fn now() -> u64 {
+    EPOCH.get_or_init(Instant::now).elapsed().as_nanos() as u64
+}
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+pub fn now() -> u64 {
+    TICKS.fetch_add(1, Ordering::Relaxed) + 1
+}
@@ -0,0 +1,143 @@
+use std::hint::spin_loop;
+#[cfg(feature = "contention-stats")]
+use std::time::Duration;
+
+#[cfg(feature = "contention-stats")]
+use super::{
+    clock,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use super::{
+    lock::{Mutex, MutexGuard},
+    sync::thread,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum YieldStrategy {
+    Spin,
+    Yield,
+    #[default]
+    Park,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ContentionPolicy {
+    pub spin_budget: u32,
+    pub yield_strategy: YieldStrategy,
+    pub readers_help_resize: bool,
+}
+
+impl ContentionPolicy {
+    pub fn strategy(&self) -> YieldStrategy {
+        if cfg!(all(target_arch = "wasm32", not(target_feature = "atomics"))) {
+            return YieldStrategy::Spin;
+        }
+        self.yield_strategy
+    }
+
+    pub fn lock<'a, T>(&self, mutex: &'a Mutex<T>) -> MutexGuard<'a, T> {
+        for _ in 0..self.spin_budget {
+            if let Some(guard) = mutex.try_lock() {
+                return guard;
+            }
+            spin_loop();
+        }
+
+        match self.strategy() {
+            YieldStrategy::Park => mutex.lock(),
+            YieldStrategy::Yield => loop {
+                if let Some(guard) = mutex.try_lock() {
+                    return guard;
+                }
+                thread::yield_now();
+            },
+            YieldStrategy::Spin => loop {
+                if let Some(guard) = mutex.try_lock() {
+                    return guard;
+                }
+                spin_loop();
+            },
+        }
+    }
+}
+
+#[cfg(feature = "contention-stats")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ContentionStats {
+    pub bin_contentions: u64,
+    pub bin_wait: Duration,
+    pub tree_bin_contentions: u64,
+    pub tree_bin_wait: Duration,
+}
+
+#[derive(Debug, Default)]
+pub struct ContentionTracker {
+    pub policy: ContentionPolicy,
+    #[cfg(feature = "contention-stats")]
+    bin_contentions: AtomicU64,
+    #[cfg(feature = "contention-stats")]
+    bin_wait: AtomicU64,
+    #[cfg(feature = "contention-stats")]
+    tree_bin_contentions: AtomicU64,
+    #[cfg(feature = "contention-stats")]
+    tree_bin_wait: AtomicU64,
+}
+
+impl ContentionTracker {
+    pub fn new(policy: ContentionPolicy) -> Self {
+        ContentionTracker {
+            policy,
+            #[cfg(feature = "contention-stats")]
+            bin_contentions: AtomicU64::new(0),
+            #[cfg(feature = "contention-stats")]
+            bin_wait: AtomicU64::new(0),
+            #[cfg(feature = "contention-stats")]
+            tree_bin_contentions: AtomicU64::new(0),
+            #[cfg(feature = "contention-stats")]
+            tree_bin_wait: AtomicU64::new(0),
+        }
+    }
+
+    pub fn spin_budget(&self) -> u32 {
+        self.policy.spin_budget
+    }
+
+    pub fn strategy(&self) -> YieldStrategy {
+        self.policy.strategy()
+    }
+
+    #[cfg(not(feature = "contention-stats"))]
+    pub fn lock<'a, T>(&self, mutex: &'a Mutex<T>) -> MutexGuard<'a, T> {
+        self.policy.lock(mutex)
+    }
+
+    #[cfg(feature = "contention-stats")]
+    pub fn lock<'a, T>(&self, mutex: &'a Mutex<T>) -> MutexGuard<'a, T> {
+        if let Some(guard) = mutex.try_lock() {
+            return guard;
+        }
+        let start = clock::now();
+        let guard = self.policy.lock(mutex);
+        self.bin_contentions.fetch_add(1, Ordering::Relaxed);
+        self.bin_wait
+            .fetch_add(clock::now().saturating_sub(start), Ordering::Relaxed);
+        guard
+    }
+
+    #[cfg(feature = "contention-stats")]
+    pub fn record_tree_bin(&self, start: u64) {
+        self.tree_bin_contentions.fetch_add(1, Ordering::Relaxed);
+        self.tree_bin_wait
+            .fetch_add(clock::now().saturating_sub(start), Ordering::Relaxed);
+    }
+
+    #[cfg(feature = "contention-stats")]
+    pub fn stats(&self) -> ContentionStats {
+        ContentionStats {
+            bin_contentions: self.bin_contentions.load(Ordering::Relaxed),
+            bin_wait: Duration::from_nanos(self.bin_wait.load(Ordering::Relaxed)),
+            tree_bin_contentions: self.tree_bin_contentions.load(Ordering::Relaxed),
+            tree_bin_wait: Duration::from_nanos(self.tree_bin_wait.load(Ordering::Relaxed)),
+        }
+    }
+}
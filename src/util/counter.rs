@@ -0,0 +1,111 @@
+use std::sync::OnceLock;
+
+use crossbeam_utils::CachePadded;
+
+use super::sync::atomic::{AtomicIsize, AtomicUsize, Ordering};
+
+#[cfg(not(loom))]
+static NEXT_PROBE: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(not(loom))]
+thread_local! {
+    static PROBE: usize = NEXT_PROBE.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(loom)]
+loom::lazy_static! {
+    static ref NEXT_PROBE: AtomicUsize = AtomicUsize::new(0);
+}
+
+#[cfg(loom)]
+loom::thread_local! {
+    static PROBE: usize = NEXT_PROBE.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn probe() -> usize {
+    PROBE.with(|probe| *probe)
+}
+
+#[derive(Debug)]
+pub struct Counter {
+    pub base: CachePadded<AtomicIsize>,
+    pub cells: OnceLock<Box<[CachePadded<AtomicIsize>]>>,
+}
+
+impl Counter {
+    pub fn new() -> Self {
+        Counter {
+            base: CachePadded::new(AtomicIsize::new(0)),
+            cells: OnceLock::new(),
+        }
+    }
+
+    fn cells(&self) -> &[CachePadded<AtomicIsize>] {
+        self.cells.get_or_init(|| {
+            (0..super::sync::stripes())
+                .map(|_| CachePadded::new(AtomicIsize::new(0)))
+                .collect()
+        })
+    }
+
+    pub fn add(&self, delta: isize) {
+        let base = self.base.load(Ordering::Relaxed);
+        if self
+            .base
+            .compare_exchange(base, base + delta, Ordering::SeqCst, Ordering::Relaxed)
+            .is_err()
+        {
+            let cells = self.cells();
+            let cell = &cells[probe() & (cells.len() - 1)];
+            if delta >= 0 {
+                cell.fetch_add(delta, Ordering::SeqCst);
+                return;
+            }
+
+            let mut current = cell.load(Ordering::Relaxed);
+            while current + delta >= 0 {
+                match cell.compare_exchange_weak(
+                    current,
+                    current + delta,
+                    Ordering::SeqCst,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => return,
+                    Err(actual) => current = actual,
+                }
+            }
+            self.base.fetch_add(delta, Ordering::SeqCst);
+        }
+    }
+
+    pub fn is_positive(&self) -> bool {
+        let mut sum = self.base.load(Ordering::SeqCst);
+        if sum > 0 {
+            return true;
+        }
+
+        for cell in self.cells.get().into_iter().flat_map(|cells| cells.iter()) {
+            sum += cell.load(Ordering::SeqCst);
+            if sum > 0 {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn sum(&self) -> isize {
+        self.cells
+            .get()
+            .into_iter()
+            .flat_map(|cells| cells.iter())
+            .fold(self.base.load(Ordering::SeqCst), |sum, cell| {
+                sum + cell.load(Ordering::SeqCst)
+            })
+    }
+}
+
+impl Default for Counter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    #[default]
+    Lru,
+    Lfu,
+}
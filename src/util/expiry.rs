@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use super::clock;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Expiry {
+    pub after_write: Option<u64>,
+    pub after_access: Option<u64>,
+}
+
+impl Expiry {
+    pub fn new(after_write: Option<Duration>, after_access: Option<Duration>) -> Self {
+        if !clock::TRACKS_TIME {
+            return Expiry::default();
+        }
+        Expiry {
+            after_write: after_write.map(|ttl| ttl.as_nanos().min(u64::MAX as u128) as u64),
+            after_access: after_access.map(|tti| tti.as_nanos().min(u64::MAX as u128) as u64),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.after_write.is_some() || self.after_access.is_some()
+    }
+
+    pub fn tracks_access(&self) -> bool {
+        self.after_access.is_some()
+    }
+
+    pub fn has_expired(&self, written: u64, touched: u64, now: u64) -> bool {
+        let write_expired = self
+            .after_write
+            .is_some_and(|ttl| now.saturating_sub(written) >= ttl);
+        let access_expired = self
+            .after_access
+            .is_some_and(|tti| now.saturating_sub(written.max(touched)) >= tti);
+        write_expired || access_expired
+    }
+}
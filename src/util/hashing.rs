@@ -0,0 +1,200 @@
+use std::{
+    collections::hash_map::RandomState,
+    convert::TryInto,
+    hash::{BuildHasher, Hash, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+static NEXT_SEED: AtomicU64 = AtomicU64::new(0);
+
+pub fn spread(hash: u64) -> u64 {
+    let hash = hash ^ (hash >> 32);
+    hash ^ (hash >> 16)
+}
+
+/// # Safety
+///
+/// Keys that compare equal must agree on whether `cached_hash` returns `Some`, and on the
+/// value inside it. A borrowed form `Q` of a key `K` must return what `K` returns, and must
+/// hash like `K` when both fall back to the `BuildHasher`. Otherwise lookups miss entries
+/// and the same key can be inserted twice.
+pub unsafe trait HashCached {
+    fn cached_hash(&self) -> Option<u64>;
+}
+
+pub trait KeyHasher<Q: ?Sized> {
+    fn hash_key(&self, key: &Q) -> u64;
+}
+
+impl<Q, S> KeyHasher<Q> for S
+where
+    Q: ?Sized + Hash,
+    S: BuildHasher,
+{
+    fn hash_key(&self, key: &Q) -> u64 {
+        self.hash_one(key)
+    }
+}
+
+pub fn mix(key: u64) -> u64 {
+    let key = (key ^ (key >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+    let key = (key ^ (key >> 33)).wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    key ^ (key >> 33)
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IntHasher;
+
+macro_rules! int_hasher {
+    ($($int:ty),*) => {
+        $(
+            impl KeyHasher<$int> for IntHasher {
+                fn hash_key(&self, key: &$int) -> u64 {
+                    mix(*key as u64)
+                }
+            }
+        )*
+    };
+}
+
+int_hasher!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[derive(Debug, Default, Clone)]
+pub struct HashCachedState<S = SeededState> {
+    pub fallback: S,
+}
+
+impl<S> HashCachedState<S> {
+    pub fn new(fallback: S) -> Self {
+        HashCachedState { fallback }
+    }
+}
+
+impl<Q, S> KeyHasher<Q> for HashCachedState<S>
+where
+    Q: ?Sized + Hash + HashCached,
+    S: BuildHasher,
+{
+    fn hash_key(&self, key: &Q) -> u64 {
+        match key.cached_hash() {
+            Some(hash) => hash,
+            None => self.fallback.hash_one(key),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeededState {
+    keys: [u64; 2],
+}
+
+impl SeededState {
+    pub fn new() -> Self {
+        let state = RandomState::new();
+        let k0 = state.hash_one(NEXT_SEED.fetch_add(1, Ordering::Relaxed));
+        SeededState {
+            keys: [k0, state.hash_one(k0)],
+        }
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        SeededState {
+            keys: [seed, seed.rotate_left(32) ^ 0x9e37_79b9_7f4a_7c15],
+        }
+    }
+}
+
+impl Default for SeededState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BuildHasher for SeededState {
+    type Hasher = SeededHasher;
+
+    fn build_hasher(&self) -> SeededHasher {
+        SeededHasher::new_with_keys(self.keys[0], self.keys[1])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SeededHasher {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+    tail: u64,
+    ntail: usize,
+    length: usize,
+}
+
+impl SeededHasher {
+    fn new_with_keys(k0: u64, k1: u64) -> Self {
+        SeededHasher {
+            v0: k0 ^ 0x736f_6d65_7073_6575,
+            v1: k1 ^ 0x646f_7261_6e64_6f6d,
+            v2: k0 ^ 0x6c79_6765_6e65_7261,
+            v3: k1 ^ 0x7465_6462_7974_6573,
+            tail: 0,
+            ntail: 0,
+            length: 0,
+        }
+    }
+
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13) ^ self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16) ^ self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21) ^ self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17) ^ self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    fn compress(&mut self, word: u64) {
+        self.v3 ^= word;
+        self.round();
+        self.v0 ^= word;
+    }
+}
+
+impl Hasher for SeededHasher {
+    fn write(&mut self, mut bytes: &[u8]) {
+        self.length += bytes.len();
+        while self.ntail != 0 && !bytes.is_empty() {
+            self.tail |= u64::from(bytes[0]) << (8 * self.ntail);
+            self.ntail = (self.ntail + 1) % 8;
+            bytes = &bytes[1..];
+            if self.ntail == 0 {
+                let word = self.tail;
+                self.compress(word);
+                self.tail = 0;
+            }
+        }
+        if bytes.is_empty() {
+            return;
+        }
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            self.compress(u64::from_le_bytes(word.try_into().unwrap()));
+        }
+        for (i, &byte) in words.remainder().iter().enumerate() {
+            self.tail |= u64::from(byte) << (8 * i);
+        }
+        self.ntail = words.remainder().len();
+    }
+
+    fn finish(&self) -> u64 {
+        let mut state = self.clone();
+        state.compress(((self.length as u64 & 0xff) << 56) | self.tail);
+        state.v2 ^= 0xff;
+        state.round();
+        state.round();
+        state.round();
+        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(all(feature = "parking_lot", not(feature = "std-locks"), not(loom)))]
+pub use parking_lot::{Mutex, MutexGuard};
+
+#[cfg(any(feature = "std-locks", not(feature = "parking_lot"), loom))]
+pub use self::std_locks::{Mutex, MutexGuard};
+
+#[cfg(any(feature = "std-locks", not(feature = "parking_lot"), loom))]
+mod std_locks {
+    use std::sync::{PoisonError, TryLockError};
+
+    #[cfg(not(loom))]
+    use std::sync;
+
+    #[cfg(loom)]
+    use loom::sync;
+
+    pub type MutexGuard<'a, T> = sync::MutexGuard<'a, T>;
+
+    #[derive(Debug, Default)]
+    pub struct Mutex<T>(sync::Mutex<T>);
+
+    impl<T> Mutex<T> {
+        pub fn new(value: T) -> Self {
+            Mutex(sync::Mutex::new(value))
+        }
+
+        pub fn lock(&self) -> MutexGuard<'_, T> {
+            self.0.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+            match self.0.try_lock() {
+                Ok(guard) => Some(guard),
+                Err(TryLockError::Poisoned(error)) => Some(error.into_inner()),
+                Err(TryLockError::WouldBlock) => None,
+            }
+        }
+    }
+}
@@ -1,2 +1,21 @@
+pub mod bytes_key;
+pub mod clock;
+pub mod contention_policy;
+pub mod counter;
 pub mod dir;
+pub mod equivalent;
+pub mod eviction_policy;
+pub mod expiry;
+pub mod hashing;
+pub mod lease;
+pub mod lock;
+pub mod normalized_key;
+pub mod pool;
+pub mod prefetch;
+pub mod reclaim;
+pub mod reservation;
 pub mod state;
+pub mod sync;
+pub mod timing_wheel;
+pub mod treeify_policy;
+pub mod version;
@@ -0,0 +1,184 @@
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+pub trait Normalizer {
+    type Target: ?Sized;
+
+    fn hash<H: Hasher>(value: &Self::Target, state: &mut H);
+
+    fn cmp(left: &Self::Target, right: &Self::Target) -> Ordering;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CaseInsensitive;
+
+impl Normalizer for CaseInsensitive {
+    type Target = str;
+
+    fn hash<H: Hasher>(value: &str, state: &mut H) {
+        for c in value.chars().flat_map(char::to_lowercase) {
+            state.write_u32(c as u32);
+        }
+        state.write_u8(0xff);
+    }
+
+    fn cmp(left: &str, right: &str) -> Ordering {
+        left.chars()
+            .flat_map(char::to_lowercase)
+            .cmp(right.chars().flat_map(char::to_lowercase))
+    }
+}
+
+#[repr(transparent)]
+pub struct Normalized<N: Normalizer> {
+    normalizer: PhantomData<N>,
+    value: N::Target,
+}
+
+impl<N: Normalizer> Normalized<N> {
+    pub fn new(value: &N::Target) -> &Self {
+        unsafe { &*(value as *const N::Target as *const Self) }
+    }
+
+    pub fn get(&self) -> &N::Target {
+        &self.value
+    }
+}
+
+impl<N: Normalizer> Hash for Normalized<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        N::hash(&self.value, state)
+    }
+}
+
+impl<N: Normalizer> PartialEq for Normalized<N> {
+    fn eq(&self, other: &Self) -> bool {
+        N::cmp(&self.value, &other.value) == Ordering::Equal
+    }
+}
+
+impl<N: Normalizer> Eq for Normalized<N> {}
+
+impl<N: Normalizer> PartialOrd for Normalized<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N: Normalizer> Ord for Normalized<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        N::cmp(&self.value, &other.value)
+    }
+}
+
+impl<N> fmt::Debug for Normalized<N>
+where
+    N: Normalizer,
+    N::Target: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+pub struct NormalizedKey<K, N> {
+    pub key: K,
+    normalizer: PhantomData<N>,
+}
+
+impl<K, N> NormalizedKey<K, N> {
+    pub fn new(key: K) -> Self {
+        NormalizedKey {
+            key,
+            normalizer: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> K {
+        self.key
+    }
+}
+
+impl<K, N> NormalizedKey<K, N>
+where
+    K: Borrow<N::Target>,
+    N: Normalizer,
+{
+    pub fn normalized(&self) -> &Normalized<N> {
+        Normalized::new(self.key.borrow())
+    }
+}
+
+impl<K, N> Borrow<Normalized<N>> for NormalizedKey<K, N>
+where
+    K: Borrow<N::Target>,
+    N: Normalizer,
+{
+    fn borrow(&self) -> &Normalized<N> {
+        self.normalized()
+    }
+}
+
+impl<K, N> Hash for NormalizedKey<K, N>
+where
+    K: Borrow<N::Target>,
+    N: Normalizer,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state)
+    }
+}
+
+impl<K, N> PartialEq for NormalizedKey<K, N>
+where
+    K: Borrow<N::Target>,
+    N: Normalizer,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl<K, N> Eq for NormalizedKey<K, N>
+where
+    K: Borrow<N::Target>,
+    N: Normalizer,
+{
+}
+
+impl<K, N> PartialOrd for NormalizedKey<K, N>
+where
+    K: Borrow<N::Target>,
+    N: Normalizer,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K, N> Ord for NormalizedKey<K, N>
+where
+    K: Borrow<N::Target>,
+    N: Normalizer,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.normalized().cmp(other.normalized())
+    }
+}
+
+impl<K: Clone, N> Clone for NormalizedKey<K, N> {
+    fn clone(&self) -> Self {
+        NormalizedKey::new(self.key.clone())
+    }
+}
+
+impl<K: fmt::Debug, N> fmt::Debug for NormalizedKey<K, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.key.fmt(f)
+    }
+}
@@ -0,0 +1,65 @@
+use std::{fmt, mem::MaybeUninit, ptr};
+
+use super::{counter, lock::Mutex, sync};
+use crate::util::sync::epoch::Owned;
+
+pub type Shard<T> = Mutex<Vec<Box<MaybeUninit<T>>>>;
+
+pub struct Pool<T> {
+    shards: Box<[Shard<T>]>,
+    shard_capacity: usize,
+}
+
+impl<T> Pool<T> {
+    pub fn new(capacity: usize) -> Self {
+        let shards = sync::stripes();
+        Pool {
+            shards: (0..shards).map(|_| Mutex::new(Vec::new())).collect(),
+            shard_capacity: capacity.div_ceil(shards),
+        }
+    }
+
+    fn shard(&self) -> &Shard<T> {
+        &self.shards[counter::probe() & (self.shards.len() - 1)]
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.lock().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn alloc(&self, value: T) -> Owned<T> {
+        match self.shard().lock().pop() {
+            Some(mut slot) => {
+                slot.write(value);
+                unsafe { Owned::from_raw(Box::into_raw(slot).cast()) }
+            }
+            None => Owned::new(value),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `raw` must come from `Owned::new` or `alloc`, must be unreachable by every thread and
+    /// must not be used again after this call.
+    pub unsafe fn recycle(&self, raw: *mut T) {
+        ptr::drop_in_place(raw);
+        let slot = Box::from_raw(raw.cast::<MaybeUninit<T>>());
+        let mut shard = self.shard().lock();
+        if shard.len() < self.shard_capacity {
+            shard.push(slot);
+        }
+    }
+}
+
+impl<T> fmt::Debug for Pool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("len", &self.len())
+            .field("shard_capacity", &self.shard_capacity)
+            .finish()
+    }
+}
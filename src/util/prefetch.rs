@@ -0,0 +1,26 @@
+#[cfg(all(feature = "prefetch", target_arch = "x86_64"))]
+#[inline(always)]
+pub fn prefetch<T>(ptr: *const T) {
+    use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+
+    unsafe { _mm_prefetch(ptr as *const i8, _MM_HINT_T0) };
+}
+
+#[cfg(all(feature = "prefetch", target_arch = "aarch64"))]
+#[inline(always)]
+pub fn prefetch<T>(ptr: *const T) {
+    unsafe {
+        std::arch::asm!(
+            "prfm pldl1keep, [{ptr}]",
+            ptr = in(reg) ptr,
+            options(nostack, readonly, preserves_flags)
+        )
+    };
+}
+
+#[cfg(not(all(
+    feature = "prefetch",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+#[inline(always)]
+pub fn prefetch<T>(_: *const T) {}
@@ -0,0 +1,123 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use super::pool::Pool;
+use crate::util::sync::epoch::{self, Collector, Guard, LocalHandle, Shared};
+
+const CACHED_HANDLES: usize = 8;
+
+#[cfg(not(loom))]
+thread_local! {
+    static HANDLES: RefCell<VecDeque<LocalHandle>> = const { RefCell::new(VecDeque::new()) };
+}
+
+#[cfg(loom)]
+loom::thread_local! {
+    static HANDLES: RefCell<VecDeque<LocalHandle>> = RefCell::new(VecDeque::new());
+}
+
+pub fn pin(collector: &Collector) -> Guard {
+    if collector == epoch::default_collector() {
+        return epoch::pin();
+    }
+    HANDLES.with(|handles| {
+        let mut handles = handles.borrow_mut();
+        if let Some(handle) = handles
+            .iter()
+            .find(|handle| handle.collector() == collector)
+        {
+            return handle.pin();
+        }
+        let evicted = if handles.len() == CACHED_HANDLES {
+            handles.pop_front()
+        } else {
+            None
+        };
+        let handle = collector.register();
+        let guard = handle.pin();
+        handles.push_back(handle);
+        drop(handles);
+        drop(evicted);
+        guard
+    })
+}
+
+pub fn release(collector: &Collector) {
+    let _ = HANDLES.try_with(|handles| {
+        let mut handles = handles.borrow_mut();
+        let released = match handles
+            .iter()
+            .position(|handle| handle.collector() == collector)
+        {
+            Some(index) => handles.remove(index),
+            None => None,
+        };
+        drop(handles);
+        drop(released);
+    });
+}
+
+pub fn is_pinned(collector: &Collector) -> bool {
+    if collector == epoch::default_collector() {
+        return epoch::is_pinned();
+    }
+    HANDLES.with(|handles| {
+        handles
+            .borrow()
+            .iter()
+            .any(|handle| handle.collector() == collector && handle.is_pinned())
+    })
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Pending(Arc<AtomicUsize>);
+
+impl Pending {
+    pub fn new() -> Self {
+        Pending::default()
+    }
+
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+pub trait Retire {
+    /// # Safety
+    ///
+    /// `ptr` must be unreachable for threads that pin after this call, and it must not be
+    /// retired twice. This is the same contract as `Guard::defer_destroy`.
+    unsafe fn retire<T>(&self, ptr: Shared<'_, T>, pending: &Pending);
+
+    /// # Safety
+    ///
+    /// Same contract as `retire`, and `ptr` must have been allocated by `Owned::new` or `pool`.
+    unsafe fn recycle<T>(&self, ptr: Shared<'_, T>, pool: &Arc<Pool<T>>, pending: &Pending);
+}
+
+impl Retire for Guard {
+    unsafe fn retire<T>(&self, ptr: Shared<'_, T>, pending: &Pending) {
+        let pending = Arc::clone(&pending.0);
+        pending.fetch_add(1, Ordering::SeqCst);
+        self.defer_unchecked(move || {
+            drop(ptr.into_owned());
+            pending.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+
+    unsafe fn recycle<T>(&self, ptr: Shared<'_, T>, pool: &Arc<Pool<T>>, pending: &Pending) {
+        let pool = Arc::clone(pool);
+        let pending = Arc::clone(&pending.0);
+        pending.fetch_add(1, Ordering::SeqCst);
+        self.defer_unchecked(move || {
+            pool.recycle(ptr.as_raw() as *mut T);
+            pending.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Condvar, Mutex as StdMutex, PoisonError},
+};
+
+use super::lock::Mutex;
+
+#[derive(Debug, Default)]
+pub struct Reservation {
+    done: StdMutex<bool>,
+    ready: Condvar,
+}
+
+impl Reservation {
+    pub fn wait(&self) {
+        let mut done = self.done.lock().unwrap_or_else(PoisonError::into_inner);
+        while !*done {
+            done = self
+                .ready
+                .wait(done)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    pub fn complete(&self) {
+        *self.done.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        self.ready.notify_all();
+    }
+}
+
+#[derive(Debug)]
+pub struct Reservations<K> {
+    pending: Mutex<HashMap<K, Arc<Reservation>>>,
+}
+
+impl<K> Reservations<K> {
+    pub fn new() -> Self {
+        Reservations {
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K> Reservations<K>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn reserve(&self, key: &K) -> Result<ReservationGuard<'_, K>, Arc<Reservation>> {
+        let mut pending = self.pending.lock();
+        if let Some(reservation) = pending.get(key) {
+            return Err(reservation.clone());
+        }
+
+        let reservation = Arc::new(Reservation::default());
+        pending.insert(key.clone(), reservation.clone());
+        Ok(ReservationGuard {
+            reservations: self,
+            key: key.clone(),
+            reservation,
+        })
+    }
+}
+
+impl<K> Default for Reservations<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+pub struct ReservationGuard<'a, K>
+where
+    K: Eq + Hash,
+{
+    reservations: &'a Reservations<K>,
+    key: K,
+    reservation: Arc<Reservation>,
+}
+
+impl<K> Drop for ReservationGuard<'_, K>
+where
+    K: Eq + Hash,
+{
+    fn drop(&mut self) {
+        self.reservations.pending.lock().remove(&self.key);
+        self.reservation.complete();
+    }
+}
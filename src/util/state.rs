@@ -1,8 +1,8 @@
 #[derive(Debug, PartialEq, Eq)]
-#[repr(i64)]
+#[repr(usize)]
 pub enum State {
-    None,
-    Waiter,
-    Reader,
-    Writer,
+    None = 0,
+    Writer = 1,
+    Waiter = 2,
+    Reader = 4,
 }
@@ -0,0 +1,21 @@
+#[cfg(not(loom))]
+pub use std::{sync::atomic, thread};
+
+#[cfg(loom)]
+pub use loom::{sync::atomic, thread};
+
+#[cfg(not(loom))]
+pub use crossbeam_epoch as epoch;
+
+#[cfg(loom)]
+pub mod epoch;
+
+#[cfg(not(loom))]
+pub fn stripes() -> usize {
+    num_cpus::get().next_power_of_two()
+}
+
+#[cfg(loom)]
+pub fn stripes() -> usize {
+    2
+}
@@ -0,0 +1,416 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use loom::sync::atomic::{AtomicUsize, Ordering};
+
+pub trait Pointer<T> {
+    fn into_usize(self) -> usize;
+
+    /// # Safety
+    ///
+    /// `data` must come from `into_usize` on the same pointer type.
+    unsafe fn from_usize(data: usize) -> Self;
+}
+
+pub struct CompareAndSetError<'g, T, P: Pointer<T>> {
+    pub current: Shared<'g, T>,
+    pub new: P,
+}
+
+pub struct Atomic<T> {
+    data: AtomicUsize,
+    _marker: PhantomData<*mut T>,
+}
+
+unsafe impl<T: Send + Sync> Send for Atomic<T> {}
+unsafe impl<T: Send + Sync> Sync for Atomic<T> {}
+
+impl<T> Atomic<T> {
+    pub fn new(value: T) -> Self {
+        Self::from(Owned::new(value))
+    }
+
+    pub fn null() -> Self {
+        Atomic {
+            data: AtomicUsize::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn load<'g>(&self, ord: Ordering, _: &'g Guard) -> Shared<'g, T> {
+        Shared::from_usize(self.data.load(ord))
+    }
+
+    pub fn store<P: Pointer<T>>(&self, new: P, ord: Ordering) {
+        self.data.store(new.into_usize(), ord);
+    }
+
+    pub fn swap<'g, P: Pointer<T>>(&self, new: P, ord: Ordering, _: &'g Guard) -> Shared<'g, T> {
+        Shared::from_usize(self.data.swap(new.into_usize(), ord))
+    }
+
+    pub fn compare_and_set<'g, P: Pointer<T>>(
+        &self,
+        current: Shared<'_, T>,
+        new: P,
+        ord: Ordering,
+        _: &'g Guard,
+    ) -> Result<Shared<'g, T>, CompareAndSetError<'g, T, P>> {
+        let failure = match ord {
+            Ordering::Release | Ordering::Relaxed => Ordering::Relaxed,
+            Ordering::AcqRel | Ordering::Acquire => Ordering::Acquire,
+            _ => Ordering::SeqCst,
+        };
+        let new = new.into_usize();
+        match self.data.compare_exchange(current.data, new, ord, failure) {
+            Ok(_) => Ok(Shared::from_usize(new)),
+            Err(actual) => Err(CompareAndSetError {
+                current: Shared::from_usize(actual),
+                new: unsafe { P::from_usize(new) },
+            }),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// Same contract as `crossbeam_epoch::Atomic::into_owned`.
+    pub unsafe fn into_owned(self) -> Owned<T> {
+        Owned::from_usize(self.data.into_inner())
+    }
+}
+
+impl<T> Clone for Atomic<T> {
+    fn clone(&self) -> Self {
+        Atomic {
+            data: AtomicUsize::new(self.data.load(Ordering::Relaxed)),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for Atomic<T> {
+    fn default() -> Self {
+        Atomic::null()
+    }
+}
+
+impl<T> fmt::Debug for Atomic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Atomic").finish_non_exhaustive()
+    }
+}
+
+impl<T> From<Owned<T>> for Atomic<T> {
+    fn from(owned: Owned<T>) -> Self {
+        Atomic {
+            data: AtomicUsize::new(Pointer::into_usize(owned)),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> From<Shared<'_, T>> for Atomic<T> {
+    fn from(shared: Shared<'_, T>) -> Self {
+        Atomic {
+            data: AtomicUsize::new(shared.data),
+            _marker: PhantomData,
+        }
+    }
+}
+
+pub struct Owned<T> {
+    data: usize,
+    _marker: PhantomData<Box<T>>,
+}
+
+impl<T> Owned<T> {
+    pub fn new(value: T) -> Self {
+        Self::from_usize(Box::into_raw(Box::new(value)) as usize)
+    }
+
+    /// # Safety
+    ///
+    /// `raw` must come from `Box::into_raw`.
+    pub unsafe fn from_raw(raw: *mut T) -> Self {
+        Self::from_usize(raw as usize)
+    }
+
+    fn from_usize(data: usize) -> Self {
+        Owned {
+            data,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn into_shared(self, _: &Guard) -> Shared<'_, T> {
+        Shared::from_usize(Pointer::into_usize(self))
+    }
+
+    pub fn into_box(self) -> Box<T> {
+        unsafe { Box::from_raw(Pointer::into_usize(self) as *mut T) }
+    }
+}
+
+impl<T> Pointer<T> for Owned<T> {
+    fn into_usize(self) -> usize {
+        let data = self.data;
+        mem::forget(self);
+        data
+    }
+
+    unsafe fn from_usize(data: usize) -> Self {
+        Owned::from_usize(data)
+    }
+}
+
+impl<T> Drop for Owned<T> {
+    fn drop(&mut self) {
+        drop(unsafe { Box::from_raw(self.data as *mut T) });
+    }
+}
+
+impl<T> Deref for Owned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*(self.data as *const T) }
+    }
+}
+
+impl<T> DerefMut for Owned<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *(self.data as *mut T) }
+    }
+}
+
+impl<T> fmt::Debug for Owned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Owned").field(&self.data).finish()
+    }
+}
+
+pub struct Shared<'g, T: 'g> {
+    data: usize,
+    _marker: PhantomData<(&'g (), *const T)>,
+}
+
+impl<'g, T> Shared<'g, T> {
+    pub fn null() -> Self {
+        Self::from_usize(0)
+    }
+
+    fn from_usize(data: usize) -> Self {
+        Shared {
+            data,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.data == 0
+    }
+
+    pub fn as_raw(&self) -> *const T {
+        self.data as *const T
+    }
+
+    /// # Safety
+    ///
+    /// Same contract as `crossbeam_epoch::Shared::deref`.
+    pub unsafe fn deref(&self) -> &'g T {
+        &*self.as_raw()
+    }
+
+    /// # Safety
+    ///
+    /// Same contract as `crossbeam_epoch::Shared::as_ref`.
+    pub unsafe fn as_ref(&self) -> Option<&'g T> {
+        self.as_raw().as_ref()
+    }
+
+    /// # Safety
+    ///
+    /// Same contract as `crossbeam_epoch::Shared::into_owned`.
+    pub unsafe fn into_owned(self) -> Owned<T> {
+        Owned::from_usize(self.data)
+    }
+}
+
+impl<T> Pointer<T> for Shared<'_, T> {
+    fn into_usize(self) -> usize {
+        self.data
+    }
+
+    unsafe fn from_usize(data: usize) -> Self {
+        Shared::from_usize(data)
+    }
+}
+
+impl<T> Clone for Shared<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Shared<'_, T> {}
+
+impl<T> PartialEq for Shared<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T> Eq for Shared<'_, T> {}
+
+impl<T> Default for Shared<'_, T> {
+    fn default() -> Self {
+        Shared::null()
+    }
+}
+
+impl<T> From<*const T> for Shared<'_, T> {
+    fn from(raw: *const T) -> Self {
+        Self::from_usize(raw as usize)
+    }
+}
+
+impl<T> fmt::Debug for Shared<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Shared").field(&self.data).finish()
+    }
+}
+
+struct Deferred(Box<dyn FnOnce()>);
+
+unsafe impl Send for Deferred {}
+
+#[derive(Default)]
+struct Global {
+    deferred: Mutex<Vec<Deferred>>,
+}
+
+impl Drop for Global {
+    fn drop(&mut self) {
+        let deferred = mem::take(self.deferred.get_mut().unwrap());
+        for Deferred(f) in deferred {
+            f();
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Collector(Arc<Global>);
+
+impl Collector {
+    pub fn new() -> Self {
+        Collector::default()
+    }
+
+    pub fn register(&self) -> LocalHandle {
+        LocalHandle(self.clone())
+    }
+}
+
+impl PartialEq for Collector {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Collector {}
+
+impl fmt::Debug for Collector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Collector").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+pub struct LocalHandle(Collector);
+
+impl LocalHandle {
+    pub fn pin(&self) -> Guard {
+        Guard {
+            collector: Some(self.0.clone()),
+        }
+    }
+
+    pub fn collector(&self) -> &Collector {
+        &self.0
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug)]
+pub struct Guard {
+    collector: Option<Collector>,
+}
+
+impl Guard {
+    /// # Safety
+    ///
+    /// Same contract as `crossbeam_epoch::Guard::defer_unchecked`.
+    pub unsafe fn defer_unchecked<F, R>(&self, f: F)
+    where
+        F: FnOnce() -> R,
+    {
+        let f: Box<dyn FnOnce() + '_> = Box::new(move || drop(f()));
+        match &self.collector {
+            Some(collector) => {
+                collector
+                    .0
+                    .deferred
+                    .lock()
+                    .unwrap()
+                    .push(Deferred(mem::transmute::<
+                        Box<dyn FnOnce() + '_>,
+                        Box<dyn FnOnce()>,
+                    >(f)))
+            }
+            None => f(),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// Same contract as `crossbeam_epoch::Guard::defer_destroy`.
+    pub unsafe fn defer_destroy<T>(&self, ptr: Shared<'_, T>) {
+        self.defer_unchecked(move || ptr.into_owned());
+    }
+
+    pub fn flush(&self) {}
+
+    pub fn collector(&self) -> Option<&Collector> {
+        self.collector.as_ref()
+    }
+}
+
+static UNPROTECTED: Guard = Guard { collector: None };
+
+/// # Safety
+///
+/// Same contract as `crossbeam_epoch::unprotected`.
+pub unsafe fn unprotected() -> &'static Guard {
+    &UNPROTECTED
+}
+
+pub fn default_collector() -> &'static Collector {
+    static COLLECTOR: OnceLock<Collector> = OnceLock::new();
+    COLLECTOR.get_or_init(Collector::new)
+}
+
+pub fn is_pinned() -> bool {
+    false
+}
+
+pub fn pin() -> Guard {
+    default_collector().register().pin()
+}
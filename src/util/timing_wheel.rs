@@ -0,0 +1,121 @@
+use std::{mem, time::Duration};
+
+const SLOT_BITS: u32 = 6;
+const SLOTS: usize = 1 << SLOT_BITS;
+const LEVELS: usize = 6;
+
+#[derive(Debug)]
+pub struct TimingWheel<T> {
+    resolution: u64,
+    current: u64,
+    levels: Vec<Vec<Vec<(u64, T)>>>,
+    overflow: Vec<(u64, T)>,
+    len: usize,
+}
+
+impl<T> TimingWheel<T> {
+    pub fn new(resolution: Duration, now: u64) -> Self {
+        let resolution = (resolution.as_nanos() as u64).max(1);
+
+        TimingWheel {
+            resolution,
+            current: now / resolution,
+            levels: (0..LEVELS)
+                .map(|_| (0..SLOTS).map(|_| Vec::new()).collect())
+                .collect(),
+            overflow: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn schedule(&mut self, item: T, deadline: u64) {
+        let tick = deadline.div_ceil(self.resolution);
+        self.len += 1;
+        self.place(tick, item);
+    }
+
+    pub fn advance(&mut self, now: u64) -> Vec<T> {
+        let target = now / self.resolution;
+        let mut expired = Vec::new();
+        if self.len == 0 {
+            self.current = self.current.max(target);
+            return expired;
+        }
+
+        loop {
+            let slot = self.current as usize & (SLOTS - 1);
+            let before = expired.len();
+            expired.extend(self.levels[0][slot].drain(..).map(|(_, item)| item));
+            self.len -= expired.len() - before;
+
+            if self.current >= target {
+                break;
+            }
+
+            let next = self.next_event();
+            if next > target {
+                self.current = target;
+                break;
+            }
+            self.current = next;
+            self.cascade();
+        }
+        expired
+    }
+
+    fn next_event(&self) -> u64 {
+        for level in 0..LEVELS {
+            let shift = SLOT_BITS * level as u32;
+            let current_slot = (self.current >> shift) as usize & (SLOTS - 1);
+            let window = self.current >> (shift + SLOT_BITS) << (shift + SLOT_BITS);
+            for slot in current_slot + 1..SLOTS {
+                if !self.levels[level][slot].is_empty() {
+                    return window | (slot as u64) << shift;
+                }
+            }
+        }
+
+        let shift = SLOT_BITS * LEVELS as u32;
+        ((self.current >> shift) + 1) << shift
+    }
+
+    fn cascade(&mut self) {
+        if self.current & ((1 << (SLOT_BITS * LEVELS as u32)) - 1) == 0 {
+            for (tick, item) in mem::take(&mut self.overflow) {
+                self.place(tick, item);
+            }
+        }
+
+        for level in (1..LEVELS).rev() {
+            if self.current & ((1 << (SLOT_BITS * level as u32)) - 1) != 0 {
+                continue;
+            }
+
+            let slot = (self.current >> (SLOT_BITS * level as u32)) as usize & (SLOTS - 1);
+            for (tick, item) in mem::take(&mut self.levels[level][slot]) {
+                self.place(tick, item);
+            }
+        }
+    }
+
+    fn place(&mut self, tick: u64, item: T) {
+        let tick = tick.max(self.current);
+        let significant = 63 - ((tick ^ self.current) | (SLOTS as u64 - 1)).leading_zeros();
+        let level = (significant / SLOT_BITS) as usize;
+        if level >= LEVELS {
+            self.overflow.push((tick, item));
+            return;
+        }
+
+        let slot = (tick >> (SLOT_BITS * level as u32)) as usize & (SLOTS - 1);
+        self.levels[level][slot].push((tick, item));
+    }
+}
@@ -0,0 +1,40 @@
+use super::sync::atomic::{AtomicU64, Ordering};
+
+const COLLISION_WINDOW: u64 = 1024;
+const MIN_COLLISION_SAMPLE: u64 = 8;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TreeifyPolicy {
+    #[default]
+    Fixed,
+    ProbeLength(u64),
+    Adaptive,
+}
+
+#[derive(Debug, Default)]
+pub struct CollisionEstimate {
+    samples: AtomicU64,
+    collisions: AtomicU64,
+}
+
+impl CollisionEstimate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, collided: bool) {
+        if collided {
+            self.collisions.fetch_add(1, Ordering::Relaxed);
+        }
+        if self.samples.fetch_add(1, Ordering::Relaxed) + 1 >= COLLISION_WINDOW {
+            self.samples.store(COLLISION_WINDOW / 2, Ordering::Relaxed);
+            let collisions = self.collisions.load(Ordering::Relaxed);
+            self.collisions.store(collisions / 2, Ordering::Relaxed);
+        }
+    }
+
+    pub fn is_adversarial(&self) -> bool {
+        let samples = self.samples.load(Ordering::Relaxed);
+        samples >= MIN_COLLISION_SAMPLE && self.collisions.load(Ordering::Relaxed) * 4 >= samples
+    }
+}
@@ -0,0 +1,13 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionError<V> {
+    Unsupported(V),
+    Conflict(V),
+}
+
+impl<V> VersionError<V> {
+    pub fn into_value(self) -> V {
+        match self {
+            VersionError::Unsupported(value) | VersionError::Conflict(value) => value,
+        }
+    }
+}
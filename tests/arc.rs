@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn arc_values_outlive_the_guard() {
+    let map: ConcurrentHashMap<u64, Arc<str>> = ConcurrentHashMap::new();
+    assert_eq!(map.insert_arc(1, "one"), None);
+    let one = map.get_arc(&1).unwrap();
+
+    let old = map.insert_arc(1, String::from("uno")).unwrap();
+    assert!(Arc::ptr_eq(&old, &one));
+    assert_eq!(&*one, "one");
+    assert_eq!(map.get_arc(&1).as_deref(), Some("uno"));
+
+    let removed = map.remove_arc(&1).unwrap();
+    assert_eq!(&*removed, "uno");
+    assert_eq!(map.get_arc(&1), None);
+    assert_eq!(map.remove_arc(&1), None);
+}
+
+#[test]
+fn arc_returned_by_get_shares_the_stored_allocation() {
+    let map: ConcurrentHashMap<u64, Arc<Vec<u64>>> = ConcurrentHashMap::new();
+    let value = Arc::new(vec![1, 2, 3]);
+    map.insert_arc(7, value.clone());
+    let got = map.get_arc(&7).unwrap();
+    assert!(Arc::ptr_eq(&got, &value));
+    assert_eq!(Arc::strong_count(&value), 3);
+}
+
+#[test]
+fn get_cloned_returns_an_owned_copy() {
+    let map: ConcurrentHashMap<String, String> = ConcurrentHashMap::new();
+    map.insert("k".to_string(), "v".to_string(), &map.guard());
+
+    let mut cloned = map.get_cloned("k").unwrap();
+    cloned.push('!');
+    assert_eq!(cloned, "v!");
+    assert_eq!(map.get_cloned("k").as_deref(), Some("v"));
+    assert_eq!(map.get_cloned("missing"), None);
+}
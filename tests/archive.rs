@@ -0,0 +1,24 @@
+#![cfg(feature = "rkyv")]
+
+use concurrent_hash_table::ConcurrentHashMap;
+use rkyv::{archived_root, Deserialize, Infallible};
+
+#[test]
+fn archive_round_trip() {
+    let map: ConcurrentHashMap<u32, u64> = ConcurrentHashMap::new();
+    {
+        let guard = map.guard();
+        for key in 0..1_000 {
+            map.insert(key, u64::from(key) * 3, &guard);
+        }
+    }
+
+    let bytes = rkyv::to_bytes::<_, 256>(&map).unwrap();
+    let archived = unsafe { archived_root::<ConcurrentHashMap<u32, u64>>(&bytes) };
+    assert_eq!(archived.len(), 1_000);
+    assert_eq!(archived.get(&7), Some(&21));
+    assert_eq!(archived.get(&1_000), None);
+
+    let restored: ConcurrentHashMap<u32, u64> = archived.deserialize(&mut Infallible).unwrap();
+    assert!(restored == map);
+}
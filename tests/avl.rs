@@ -0,0 +1,105 @@
+use std::sync::atomic::Ordering;
+
+use concurrent_hash_table::core::bin_entry::{
+    avl::Avl, balance::Balance, tree_node::TreeNode, BinEntry,
+};
+use crossbeam_epoch::{self as epoch, Atomic, Guard, Owned, Shared};
+
+type Bin<'g> = Shared<'g, BinEntry<u64, u64>>;
+
+fn node(bin: Bin<'_>) -> &TreeNode<u64, u64> {
+    unsafe { TreeNode::get_tree_node(bin) }
+}
+
+fn insert<'g>(root: Bin<'g>, hash: u64, guard: &'g Guard) -> Bin<'g> {
+    let x = Owned::new(BinEntry::TreeNode(TreeNode::new(
+        hash,
+        hash,
+        Atomic::new(hash),
+        Atomic::null(),
+        Atomic::null(),
+    )))
+    .into_shared(guard);
+    if root.is_null() {
+        node(x).height.store(1, Ordering::Relaxed);
+        return x;
+    }
+
+    let mut p = root;
+    loop {
+        let side = if hash < node(p).node.hash {
+            &node(p).left
+        } else {
+            &node(p).right
+        };
+        let next = side.load(Ordering::Relaxed, guard);
+        if next.is_null() {
+            side.store(x, Ordering::Relaxed);
+            node(x).parent.store(p, Ordering::Relaxed);
+            return Avl::balance_insertion(root, x, guard);
+        }
+        p = next;
+    }
+}
+
+fn find<'g>(root: Bin<'g>, hash: u64, guard: &'g Guard) -> Bin<'g> {
+    let mut p = root;
+    while !p.is_null() && node(p).node.hash != hash {
+        p = if hash < node(p).node.hash {
+            node(p).left.load(Ordering::Relaxed, guard)
+        } else {
+            node(p).right.load(Ordering::Relaxed, guard)
+        };
+    }
+    p
+}
+
+/// Returns the height of the subtree after checking ordering, parent links and AVL balance.
+fn check(x: Bin<'_>, parent: Bin<'_>, keys: &mut Vec<u64>, guard: &Guard) -> u8 {
+    if x.is_null() {
+        return 0;
+    }
+    assert_eq!(node(x).parent.load(Ordering::Relaxed, guard), parent);
+    let left = check(node(x).left.load(Ordering::Relaxed, guard), x, keys, guard);
+    keys.push(node(x).node.hash);
+    let right = check(node(x).right.load(Ordering::Relaxed, guard), x, keys, guard);
+    assert!(left.max(right) - left.min(right) <= 1);
+    assert_eq!(node(x).height.load(Ordering::Relaxed), 1 + left.max(right));
+    1 + left.max(right)
+}
+
+fn keys(root: Bin<'_>, guard: &Guard) -> Vec<u64> {
+    let mut keys = Vec::new();
+    let height = check(root, Shared::null(), &mut keys, guard);
+    assert!(f64::from(height) <= 1.45 * ((keys.len() + 2) as f64).log2());
+    keys
+}
+
+#[test]
+fn ascending_inserts_stay_balanced() {
+    let guard = epoch::pin();
+    let mut root = Shared::null();
+    for hash in 0..200 {
+        root = insert(root, hash, &guard);
+    }
+    assert_eq!(keys(root, &guard), (0..200).collect::<Vec<_>>());
+}
+
+#[test]
+fn removals_stay_balanced() {
+    let guard = epoch::pin();
+    let mut root = Shared::null();
+    for hash in (0..200).map(|k| (k * 37) % 200) {
+        root = insert(root, hash, &guard);
+    }
+
+    for hash in (0..200).filter(|k| k % 3 != 0) {
+        let p = find(root, hash, &guard);
+        root = unsafe { Avl::remove(root, p, &guard) };
+        keys(root, &guard);
+    }
+    assert_eq!(
+        keys(root, &guard),
+        (0..200).filter(|k| k % 3 == 0).collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,56 @@
+use std::thread;
+
+use concurrent_hash_table::{
+    map::backend::Backend,
+    util::{hashing::SeededState, sync::epoch::Collector},
+    ConcurrentHashMap, CuckooMap, SwissMap,
+};
+
+fn exercise<M: Backend<u64, u64, SeededState> + Sync>() {
+    let map = M::with_collector(16, SeededState::new(), Collector::new());
+    let guard = map.guard();
+    for key in 0..1000 {
+        assert_eq!(map.insert(key, key * 2, &guard), None);
+    }
+    assert_eq!(map.insert(7, 70, &guard), Some(&14));
+    assert_eq!(map.len(), 1000);
+    assert_eq!(map.get(&7, &guard), Some(&70));
+    assert_eq!(map.get_key_value(&8, &guard), Some((&8, &16)));
+
+    thread::scope(|s| {
+        for t in 0..4u64 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in (0..1000).filter(|key| key % 4 == t) {
+                    assert!(map.remove(&key, &guard).is_some());
+                }
+            });
+        }
+    });
+
+    assert!(map.is_empty());
+    assert!(!map.contains_key(&7, &guard));
+    assert_eq!(map.iter(&guard).count(), 0);
+}
+
+#[test]
+fn backends_share_one_api() {
+    exercise::<ConcurrentHashMap<u64, u64>>();
+    exercise::<SwissMap<u64, u64>>();
+    exercise::<CuckooMap<u64, u64>>();
+}
+
+#[test]
+#[should_panic(expected = "guard was not created from this map's collector")]
+fn swiss_maps_check_guard_provenance() {
+    let map: SwissMap<u64, u64> = SwissMap::new().with_collector(Collector::new());
+    map.get(&1, &crossbeam_epoch::pin());
+}
+
+#[test]
+#[should_panic(expected = "guard was not created from this map's collector")]
+fn cuckoo_maps_check_guard_provenance() {
+    let map: CuckooMap<u64, u64> = CuckooMap::new().with_collector(Collector::new());
+    map.get(&1, &crossbeam_epoch::pin());
+}
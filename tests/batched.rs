@@ -0,0 +1,30 @@
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn contains_all_checks_every_key() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert!(map.contains_all::<u64>(&[], &guard));
+    assert!(!map.contains_all(&[&1], &guard));
+
+    for key in 0..256 {
+        map.insert(key, key * 10, &guard);
+    }
+    let keys: Vec<u64> = (0..256).rev().collect();
+    let refs: Vec<&u64> = keys.iter().collect();
+    assert!(map.contains_all(&refs, &guard));
+    assert!(!map.contains_all(&[&3, &999, &4], &guard));
+}
+
+#[test]
+fn get_all_present_keeps_caller_order() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..1024 {
+        map.insert(key, key * 10, &guard);
+    }
+
+    let present = map.get_all_present(&[&700, &5000, &3, &700, &1023], &guard);
+    assert_eq!(present, vec![(0, &7000), (2, &30), (3, &7000), (4, &10230)]);
+    assert!(map.get_all_present(&[&2000, &3000], &guard).is_empty());
+}
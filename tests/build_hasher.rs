@@ -0,0 +1,42 @@
+use std::hash::{BuildHasher, BuildHasherDefault, Hasher};
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[derive(Default)]
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3);
+        }
+    }
+}
+
+type FnvBuildHasher = BuildHasherDefault<Fnv>;
+
+#[test]
+fn map_accepts_any_build_hasher() {
+    let map: ConcurrentHashMap<String, u64, FnvBuildHasher> =
+        ConcurrentHashMap::with_hasher(FnvBuildHasher::default());
+    let guard = map.guard();
+    for key in 0..100 {
+        map.insert(key.to_string(), key, &guard);
+    }
+    assert!((0..100).all(|key| map.get(key.to_string().as_str(), &guard) == Some(&key)));
+
+    let mut hasher = map.hasher().build_hasher();
+    hasher.write(b"a");
+    assert_eq!(hasher.finish(), (0x61_u64).wrapping_mul(0x100_0000_01b3));
+}
+
+#[test]
+fn default_builds_with_the_default_hasher() {
+    let map: ConcurrentHashMap<u64, u64, FnvBuildHasher> = ConcurrentHashMap::default();
+    map.insert(1, 2, &map.guard());
+    assert_eq!(map.get(&1, &map.guard()), Some(&2));
+}
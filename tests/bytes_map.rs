@@ -0,0 +1,62 @@
+use concurrent_hash_table::{
+    map::bytes_map::{BytesMap, InlineBytesMap},
+    util::bytes_key::{bytes_eq, Bytes, BytesKey, InlineBytesKey},
+};
+
+#[test]
+fn keys_sharing_a_prefix_stay_distinct() {
+    let map: BytesMap<usize> = BytesMap::new();
+    let guard = map.guard();
+    let keys: Vec<Vec<u8>> = [0, 1, 7, 8, 9, 16, 17, 33, 64]
+        .iter()
+        .map(|&len| (0..len as u8).collect())
+        .collect();
+    for (i, key) in keys.iter().enumerate() {
+        map.insert(BytesKey::from(key.clone()), i, &guard);
+    }
+    assert_eq!(map.len(), keys.len());
+
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(map.get(Bytes::new(key), &guard), Some(&i));
+    }
+    let mut near_miss = keys[7].clone();
+    *near_miss.last_mut().unwrap() ^= 1;
+    assert_eq!(map.get(Bytes::new(&near_miss), &guard), None);
+    assert_eq!(map.get(Bytes::new(&[0, 0]), &guard), None);
+}
+
+#[test]
+fn simd_equality_matches_slice_equality() {
+    let left: Vec<u8> = (0..100).collect();
+    for len in 0..left.len() {
+        assert!(bytes_eq(&left[..len], &left[..len]));
+        for flip in 0..len {
+            let mut right = left[..len].to_vec();
+            right[flip] ^= 0x80;
+            assert!(!bytes_eq(&left[..len], &right));
+        }
+    }
+    assert!(!bytes_eq(&left[..16], &left[..17]));
+}
+
+#[test]
+fn short_keys_are_stored_inline() {
+    let map: InlineBytesMap<usize> = InlineBytesMap::new();
+    let guard = map.guard();
+    let keys: Vec<Vec<u8>> = [0, 1, 20, 30, 31, 64]
+        .iter()
+        .map(|&len| (0..len as u8).collect())
+        .collect();
+    for (i, key) in keys.iter().enumerate() {
+        let key = InlineBytesKey::from(key.clone());
+        assert_eq!(key.is_inline(), key.as_slice().len() <= 30);
+        map.insert(key, i, &guard);
+    }
+    assert_eq!(map.len(), keys.len());
+
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(map.get(Bytes::new(key), &guard), Some(&i));
+    }
+    assert_eq!(map.get(Bytes::new(&[0, 0]), &guard), None);
+    assert!(std::mem::size_of::<InlineBytesKey>() <= 32);
+}
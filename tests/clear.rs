@@ -0,0 +1,56 @@
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn clear_removes_every_entry() {
+    let map: ConcurrentHashMap<u64, String> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..200 {
+        map.insert(key, key.to_string(), &guard);
+    }
+
+    map.clear(&guard);
+    assert!(map.is_empty());
+    assert_eq!(map.len(), 0);
+    assert!((0..200).all(|key| map.get(&key, &guard).is_none()));
+
+    map.insert(7, "seven".to_string(), &guard);
+    assert_eq!(map.get(&7, &guard).map(String::as_str), Some("seven"));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn clear_runs_alongside_writers() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            let guard = map.guard();
+            for key in 0..2000 {
+                map.insert(key, key, &guard);
+            }
+        });
+        let guard = map.guard();
+        for _ in 0..10 {
+            map.clear(&guard);
+        }
+    });
+
+    let guard = map.guard();
+    let remaining = (0..2000)
+        .filter(|key| map.get(key, &guard).is_some())
+        .count();
+    assert_eq!(map.len(), remaining);
+    map.clear(&guard);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn pinned_clear() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    map.with(|pinned| {
+        pinned.insert(1, 1);
+        pinned.insert(2, 2);
+        pinned.clear();
+        assert!(pinned.is_empty());
+    });
+    assert_eq!(map.len(), 0);
+}
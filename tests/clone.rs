@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use concurrent_hash_table::{map::builder::Builder, util::lease::LeasePolicy, ConcurrentHashMap};
+
+#[test]
+fn clone_is_an_independent_deep_copy() {
+    let map: ConcurrentHashMap<u64, String> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..300 {
+        map.insert(key, key.to_string(), &guard);
+    }
+    map.set_meta(&3, 33, &guard);
+
+    let copy = map.clone();
+    let copy_guard = copy.guard();
+    assert_eq!(copy.len(), 300);
+    assert!((0..300).all(|key| copy.get(&key, &copy_guard) == Some(&key.to_string())));
+    assert_eq!(copy.get_meta(&3, &copy_guard), Some(33));
+
+    copy.insert(0, "changed".to_string(), &copy_guard);
+    copy.insert(300, "new".to_string(), &copy_guard);
+    assert_eq!(map.get(&0, &guard).map(String::as_str), Some("0"));
+    assert_eq!(map.get(&300, &guard), None);
+    assert_eq!(map.len(), 300);
+    assert_eq!(copy.len(), 301);
+}
+
+#[test]
+fn clone_keeps_the_lease_policy() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().lease_policy(LeasePolicy::Steal).build();
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+    map.lease(&1, Duration::from_secs(60), &guard).unwrap();
+
+    let copy = map.clone();
+    let copy_guard = copy.guard();
+    copy.insert(1, 2, &copy_guard);
+    assert_eq!(copy.get(&1, &copy_guard), Some(&2));
+}
@@ -0,0 +1,74 @@
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+use crossbeam_epoch::Collector;
+
+#[test]
+fn maps_share_a_collector() {
+    let collector = Collector::new();
+    let a: ConcurrentHashMap<u64, u64> = Builder::new().collector(collector.clone()).build();
+    let b: ConcurrentHashMap<u64, u64> = Builder::new().collector(collector.clone()).build();
+    assert!(a.collector() == &collector);
+    assert!(b.collector() == a.collector());
+
+    let handle = collector.register();
+    let guard = handle.pin();
+    a.insert(1, 10, &guard);
+    b.insert(1, 20, &guard);
+    assert_eq!(a.get(&1, &guard), Some(&10));
+    assert_eq!(b.get(&1, &a.guard()), Some(&20));
+}
+
+#[test]
+fn default_maps_use_the_global_collector() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    assert!(map.collector() == crossbeam_epoch::default_collector());
+}
+
+#[test]
+fn pinned_accepts_owned_and_borrowed_guards() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    map.pinned(map.guard()).insert(1, 1);
+
+    let guard = map.guard();
+    let pinned = map.pinned(&guard);
+    assert_eq!(pinned.get(&1), Some(&1));
+}
+
+#[test]
+#[should_panic(expected = "guard was not created from this map's collector")]
+fn guards_from_another_collector_are_rejected() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let other = Collector::new().register();
+    map.insert(1, 1, &other.pin());
+}
+
+#[test]
+#[should_panic(expected = "guard was not created from this map's collector")]
+fn reads_check_guard_provenance() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().collector(Collector::new()).build();
+    map.get(&1, &crossbeam_epoch::pin());
+}
+
+#[test]
+fn unprotected_guards_are_accepted() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    map.insert(1, 1, &map.guard());
+    let guard = unsafe { crossbeam_epoch::unprotected() };
+    assert_eq!(map.get(&1, guard), Some(&1));
+}
+
+#[test]
+fn guards_from_a_custom_collector_outlive_cached_handles() {
+    let maps: Vec<ConcurrentHashMap<u64, u64>> = (0..12)
+        .map(|_| Builder::new().collector(Collector::new()).build())
+        .collect();
+    let guards: Vec<_> = maps.iter().map(|map| map.guard()).collect();
+    for (key, (map, guard)) in maps.iter().zip(&guards).enumerate() {
+        map.insert(key as u64, key as u64, guard);
+        let again = map.guard();
+        assert_eq!(map.get(&(key as u64), &again), Some(&(key as u64)));
+    }
+    drop(guards);
+    for (key, map) in maps.iter().enumerate() {
+        assert_eq!(map.get(&(key as u64), &map.guard()), Some(&(key as u64)));
+    }
+}
@@ -0,0 +1,64 @@
+#![allow(dead_code)]
+
+use std::{
+    hash::{BuildHasher, Hasher},
+    sync::atomic::Ordering,
+};
+
+use concurrent_hash_table::{
+    core::bin_entry::{tree_bin::TreeBin, tree_node::TreeNode, BinEntry},
+    util::{hashing::KeyHasher, reclaim::Pending},
+};
+use crossbeam_epoch::{Atomic, Guard, Owned, Shared};
+
+#[derive(Clone, Copy)]
+pub struct Collide;
+
+impl<Q: ?Sized> KeyHasher<Q> for Collide {
+    fn hash_key(&self, _: &Q) -> u64 {
+        0
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Constant;
+
+impl BuildHasher for Constant {
+    type Hasher = ConstantHasher;
+
+    fn build_hasher(&self) -> ConstantHasher {
+        ConstantHasher
+    }
+}
+
+pub struct ConstantHasher;
+
+impl Hasher for ConstantHasher {
+    fn finish(&self) -> u64 {
+        7
+    }
+
+    fn write(&mut self, _: &[u8]) {}
+}
+
+pub fn tree_bin(keys: &[u64], guard: &Guard) -> Owned<BinEntry<u64, u64>> {
+    let mut next = Atomic::null();
+    for &key in keys.iter().rev() {
+        let node = TreeNode::new(key % 4, key, Atomic::new(key * 10), next, Atomic::null());
+        next = Atomic::from(Owned::new(BinEntry::TreeNode(node)));
+    }
+    let first = next.load(Ordering::SeqCst, guard);
+    Owned::new(BinEntry::Tree(TreeBin::new(
+        unsafe { first.into_owned() },
+        Pending::new(),
+        guard,
+    )))
+}
+
+pub fn value(found: Shared<'_, BinEntry<u64, u64>>, guard: &Guard) -> Option<u64> {
+    if found.is_null() {
+        return None;
+    }
+    let node = unsafe { TreeNode::get_tree_node(found) };
+    Some(*unsafe { node.node.value.load(Ordering::SeqCst, guard).deref() })
+}
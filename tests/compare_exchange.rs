@@ -0,0 +1,47 @@
+use std::thread;
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn replaces_only_a_matching_value() {
+    let map: ConcurrentHashMap<u64, String> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    let error = map
+        .compare_exchange(&1, &"a".to_string(), "b".to_string(), &guard)
+        .unwrap_err();
+    assert_eq!(error.current, None);
+    assert_eq!(error.new, "b");
+
+    map.insert(1, "a".to_string(), &guard);
+    let error = map
+        .compare_exchange(&1, &"x".to_string(), "b".to_string(), &guard)
+        .unwrap_err();
+    assert_eq!(error.current.map(String::as_str), Some("a"));
+    assert_eq!(error.new, "b");
+
+    let old = map
+        .compare_exchange(&1, &"a".to_string(), "b".to_string(), &guard)
+        .unwrap();
+    assert_eq!(old, "a");
+    assert_eq!(map.get(&1, &guard).map(String::as_str), Some("b"));
+}
+
+#[test]
+fn compare_exchange_loops_lose_no_increments() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    map.insert(1, 0, &map.guard());
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let guard = map.guard();
+                for _ in 0..1000 {
+                    let mut current = *map.get(&1, &guard).unwrap();
+                    while let Err(error) = map.compare_exchange(&1, &current, current + 1, &guard) {
+                        current = *error.current.unwrap();
+                    }
+                }
+            });
+        }
+    });
+    assert_eq!(map.get(&1, &map.guard()), Some(&4000));
+}
@@ -0,0 +1,70 @@
+use std::thread;
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn compute_inserts_updates_and_removes() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(map.compute(1, |_, old| old.map(|v| v + 1), &guard), None);
+    assert!(map.is_empty());
+
+    assert_eq!(
+        map.compute(
+            1,
+            |key, old| Some(old.copied().unwrap_or(*key * 10)),
+            &guard
+        ),
+        Some(&10)
+    );
+    assert_eq!(
+        map.compute(1, |_, old| old.map(|v| v + 1), &guard),
+        Some(&11)
+    );
+    assert_eq!(map.compute(1, |_, _| None, &guard), None);
+    assert_eq!(map.get(&1, &guard), None);
+    assert!(map.is_empty());
+
+    map.insert(1, 1, &guard);
+    assert_eq!(map.get(&1, &guard), Some(&1));
+}
+
+#[test]
+fn compute_if_present_skips_absent_keys() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(
+        map.compute_if_present(&1, |_, _| unreachable!("key is absent"), &guard),
+        None
+    );
+    assert!(map.is_empty());
+
+    map.insert(1, 5, &guard);
+    assert_eq!(
+        map.compute_if_present(&1, |_, v| Some(v * 2), &guard),
+        Some(&10)
+    );
+    assert_eq!(map.compute_if_present(&1, |_, _| None, &guard), None);
+    assert_eq!(map.get(&1, &guard), None);
+    assert_eq!(map.len(), 0);
+}
+
+#[test]
+fn concurrent_computes_are_atomic() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let guard = map.guard();
+                for i in 0..2000 {
+                    map.compute(i % 16, |_, old| Some(old.map_or(1, |v| v + 1)), &guard);
+                }
+            });
+        }
+    });
+    let guard = map.guard();
+    assert_eq!(map.len(), 16);
+    for key in 0..16 {
+        assert_eq!(map.get(&key, &guard), Some(&500));
+    }
+}
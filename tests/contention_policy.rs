@@ -0,0 +1,89 @@
+use std::{thread, time::Duration};
+
+use concurrent_hash_table::{
+    map::builder::Builder,
+    util::{
+        clock,
+        contention_policy::{ContentionPolicy, YieldStrategy},
+        lock::Mutex,
+    },
+    ConcurrentHashMap,
+};
+
+const STRATEGIES: [YieldStrategy; 3] = [
+    YieldStrategy::Spin,
+    YieldStrategy::Yield,
+    YieldStrategy::Park,
+];
+
+#[test]
+fn lock_is_exclusive_under_every_strategy() {
+    for &yield_strategy in STRATEGIES.iter() {
+        let policy = ContentionPolicy {
+            spin_budget: 16,
+            yield_strategy,
+            readers_help_resize: false,
+        };
+        let counter = Mutex::new(0u64);
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..10_000 {
+                        *policy.lock(&counter) += 1;
+                    }
+                });
+            }
+        });
+        assert_eq!(*counter.lock(), 40_000);
+    }
+}
+
+#[test]
+fn concurrent_writers_under_every_strategy() {
+    for &yield_strategy in STRATEGIES.iter() {
+        let map = Builder::<u64, u64>::new()
+            .contention_policy(ContentionPolicy {
+                spin_budget: 4,
+                yield_strategy,
+                readers_help_resize: false,
+            })
+            .build();
+        thread::scope(|s| {
+            for t in 0..4 {
+                let map = &map;
+                s.spawn(move || {
+                    let guard = map.guard();
+                    for key in (t..2048).step_by(4) {
+                        map.insert(key, key, &guard);
+                    }
+                });
+            }
+        });
+
+        let guard = map.guard();
+        assert_eq!(map.len(), 2048);
+        for key in 0..2048 {
+            assert_eq!(map.get(&key, &guard), Some(&key));
+        }
+    }
+}
+
+#[test]
+fn native_targets_keep_the_configured_strategy_and_clock() {
+    for &yield_strategy in STRATEGIES.iter() {
+        let policy = ContentionPolicy {
+            yield_strategy,
+            ..ContentionPolicy::default()
+        };
+        assert_eq!(policy.strategy(), yield_strategy);
+    }
+
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+    assert!(map.lease(&1, Duration::from_secs(1), &guard).is_ok());
+
+    let before = clock::now();
+    thread::sleep(Duration::from_millis(1));
+    assert!(clock::now() > before);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "contention-stats")]
+
+mod common;
+
+use std::thread;
+
+use concurrent_hash_table::{
+    map::builder::Builder, util::contention_policy::ContentionStats, ConcurrentHashMap,
+};
+
+use common::Constant;
+
+#[test]
+fn uncontended_maps_report_nothing() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..1024 {
+        map.insert(key, key, &guard);
+    }
+    assert_eq!(map.contention_stats(), ContentionStats::default());
+}
+
+#[test]
+fn a_hot_bin_records_contended_acquisitions() {
+    let map: ConcurrentHashMap<u64, u64, Constant> = Builder::new()
+        .min_treeify_capacity(0)
+        .build_with_hasher(Constant);
+    thread::scope(|s| {
+        for t in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in (t..2048).step_by(4) {
+                    map.insert(key, key, &guard);
+                    map.get(&(key / 2), &guard);
+                }
+            });
+        }
+    });
+
+    let stats = map.contention_stats();
+    assert!(stats.bin_contentions > 0);
+    assert!(stats.bin_wait.as_nanos() > 0);
+    assert_eq!(map.len(), 2048);
+}
@@ -0,0 +1,95 @@
+use std::{sync::atomic::Ordering, thread};
+
+use concurrent_hash_table::{
+    map::builder::Builder,
+    util::{counter::Counter, hashing::KeyHasher},
+    ConcurrentHashMap,
+};
+
+#[derive(Clone, Copy)]
+struct Identity;
+
+impl KeyHasher<u64> for Identity {
+    fn hash_key(&self, key: &u64) -> u64 {
+        *key
+    }
+}
+
+#[test]
+fn stripes_stay_non_negative() {
+    let counter = Counter::new();
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                for _ in 0..10_000 {
+                    counter.add(1);
+                    counter.add(-1);
+                }
+            });
+        }
+    });
+    assert_eq!(counter.sum(), 0);
+    assert!(!counter.is_positive());
+    assert!(counter
+        .cells
+        .get()
+        .into_iter()
+        .flat_map(|cells| cells.iter())
+        .all(|cell| cell.load(Ordering::SeqCst) >= 0));
+
+    counter.add(3);
+    assert!(counter.is_positive());
+    counter.add(-3);
+    assert!(!counter.is_positive());
+}
+
+#[test]
+fn cells_are_allocated_on_first_contention() {
+    let counter = Counter::new();
+    counter.add(5);
+    counter.add(-2);
+    assert!(counter.cells.get().is_none());
+    assert_eq!(counter.sum(), 3);
+}
+
+#[test]
+fn is_empty_tracks_len() {
+    let map: ConcurrentHashMap<u64, u64, Identity> = Builder::new().build_with_hasher(Identity);
+    assert!(map.is_empty());
+    thread::scope(|s| {
+        for t in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in (t..64).step_by(4) {
+                    map.insert(key, key, &guard);
+                    assert!(!map.is_empty());
+                }
+            });
+        }
+    });
+    assert_eq!(map.len(), 64);
+    assert!(!map.is_empty());
+
+    thread::scope(|s| {
+        for t in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in (t..64).step_by(4) {
+                    map.remove(&key, &guard);
+                }
+            });
+        }
+    });
+    assert_eq!(map.len(), 0);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn base_sits_on_its_own_cache_line() {
+    let counter = Counter::new();
+    let base = &counter.base as *const _ as usize;
+    assert_eq!(base % 64, 0);
+    assert!(std::mem::size_of_val(&counter.base) >= 64);
+}
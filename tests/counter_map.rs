@@ -0,0 +1,85 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use concurrent_hash_table::CounterMap;
+
+const THREADS: i64 = 4;
+const INCREMENTS: i64 = 10_000;
+
+#[test]
+fn insert_does_not_lose_concurrent_increments() {
+    let counters: Arc<CounterMap<u64>> = Arc::new(CounterMap::new());
+    counters.insert(0, 0, &counters.guard());
+
+    let incrementers: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let counters = Arc::clone(&counters);
+            thread::spawn(move || {
+                let guard = counters.guard();
+                for _ in 0..INCREMENTS {
+                    counters.increment(0, 1, &guard);
+                }
+            })
+        })
+        .collect();
+    let resetter = {
+        let counters = Arc::clone(&counters);
+        thread::spawn(move || {
+            let guard = counters.guard();
+            (0..1000)
+                .map(|_| {
+                    thread::yield_now();
+                    counters.insert(0, 0, &guard).unwrap()
+                })
+                .sum::<i64>()
+        })
+    };
+
+    for incrementer in incrementers {
+        incrementer.join().unwrap();
+    }
+    let drained = resetter.join().unwrap();
+    let remaining = counters.get(&0, &counters.guard()).unwrap();
+    assert_eq!(drained + remaining, THREADS * INCREMENTS);
+}
+
+#[test]
+fn remove_does_not_lose_concurrent_increments() {
+    let counters: Arc<CounterMap<u64>> = Arc::new(CounterMap::new());
+    let running = Arc::new(AtomicUsize::new(THREADS as usize));
+
+    let incrementers: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let (counters, running) = (Arc::clone(&counters), Arc::clone(&running));
+            thread::spawn(move || {
+                let guard = counters.guard();
+                for _ in 0..INCREMENTS * 20 {
+                    counters.increment(0, 1, &guard);
+                }
+                running.fetch_sub(1, Ordering::SeqCst);
+            })
+        })
+        .collect();
+    let remover = {
+        let (counters, running) = (Arc::clone(&counters), Arc::clone(&running));
+        thread::spawn(move || {
+            let mut removed = 0;
+            while running.load(Ordering::SeqCst) > 0 {
+                removed += counters.remove(&0, &counters.guard()).unwrap_or(0);
+            }
+            removed
+        })
+    };
+
+    for incrementer in incrementers {
+        incrementer.join().unwrap();
+    }
+    let removed = remover.join().unwrap();
+    let remaining = counters.get(&0, &counters.guard()).unwrap_or(0);
+    assert_eq!(removed + remaining, THREADS * INCREMENTS * 20);
+}
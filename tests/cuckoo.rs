@@ -0,0 +1,96 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+};
+
+use concurrent_hash_table::{util::hashing::SeededState, CuckooMap};
+
+#[test]
+fn insert_get_remove() {
+    let map = CuckooMap::new();
+    let guard = map.guard();
+    for key in 0..1000u64 {
+        assert_eq!(map.insert(key, key * 2, &guard), None);
+    }
+    assert_eq!(map.len(), 1000);
+    assert!(map.capacity(&guard) >= 1000);
+    assert_eq!(map.insert(7, 70, &guard), Some(&14));
+    assert_eq!(map.get(&7, &guard), Some(&70));
+
+    for key in (0..1000u64).step_by(2) {
+        assert!(map.remove(&key, &guard).is_some());
+    }
+    assert_eq!(map.len(), 500);
+    assert_eq!(map.get(&2, &guard), None);
+    assert_eq!(map.get(&3, &guard), Some(&6));
+    assert_eq!(map.iter(&guard).count(), 500);
+}
+
+#[test]
+fn fills_buckets_before_growing() {
+    let map = CuckooMap::with_capacity_and_hasher(1024, SeededState::with_seed(0));
+    let guard = map.guard();
+    let capacity = map.capacity(&guard);
+    for key in 0..capacity as u64 * 9 / 10 {
+        map.insert(key, key, &guard);
+    }
+    assert_eq!(map.capacity(&guard), capacity);
+}
+
+#[test]
+fn readers_never_miss_displaced_entries() {
+    let map = CuckooMap::with_capacity(64);
+    {
+        let guard = map.guard();
+        for key in 0..64u64 {
+            map.insert(key, key, &guard);
+        }
+    }
+
+    let done = AtomicBool::new(false);
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let guard = map.guard();
+                while !done.load(Ordering::Relaxed) {
+                    for key in 0..64u64 {
+                        assert_eq!(map.get(&key, &guard), Some(&key));
+                    }
+                }
+            });
+        }
+        s.spawn(|| {
+            let guard = map.guard();
+            for key in 64..50_000u64 {
+                map.insert(key, key, &guard);
+            }
+            done.store(true, Ordering::Relaxed);
+        });
+    });
+    assert_eq!(map.len(), 50_000);
+}
+
+#[test]
+fn concurrent_inserts_and_removes() {
+    let map = CuckooMap::new();
+    thread::scope(|s| {
+        for t in 0..8u64 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in t * 10_000..(t + 1) * 10_000 {
+                    map.insert(key, key, &guard);
+                }
+                for key in (t * 10_000..(t + 1) * 10_000).filter(|key| key % 3 == 0) {
+                    assert_eq!(map.remove(&key, &guard), Some(&key));
+                }
+            });
+        }
+    });
+
+    let guard = map.guard();
+    assert_eq!(map.len(), (0..80_000).filter(|key| key % 3 != 0).count());
+    for key in 0..80_000u64 {
+        assert_eq!(map.contains_key(&key, &guard), key % 3 != 0);
+    }
+}
@@ -0,0 +1,27 @@
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+
+#[test]
+fn debug_prints_every_entry_below_the_limit() {
+    let map: ConcurrentHashMap<u64, &str> = ConcurrentHashMap::new();
+    assert_eq!(format!("{:?}", map), "{}");
+
+    map.insert(1, "one", &map.guard());
+    assert_eq!(format!("{:?}", map), r#"{1: "one"}"#);
+}
+
+#[test]
+fn debug_truncates_above_the_limit() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().debug_limit(3).build();
+    let guard = map.guard();
+    for key in 0..3 {
+        map.insert(key, key, &guard);
+    }
+    let printed = format!("{:?}", map);
+    assert_eq!(printed.matches(':').count(), 3);
+    assert!(printed.ends_with('}') && !printed.contains(".."));
+
+    map.insert(3, 3, &guard);
+    let printed = format!("{:?}", map);
+    assert_eq!(printed.matches(':').count(), 3);
+    assert!(printed.ends_with(", ..}"), "{}", printed);
+}
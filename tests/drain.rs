@@ -0,0 +1,193 @@
+use std::{
+    sync::{
+        mpsc::{self, SendError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use concurrent_hash_table::{
+    map::{builder::Builder, DrainError},
+    util::sync::epoch::Collector,
+    ConcurrentHashMap,
+};
+
+fn filled(n: u64) -> ConcurrentHashMap<u64, u64> {
+    let map = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..n {
+        map.insert(key, key, &guard);
+    }
+    map
+}
+
+#[test]
+fn drain_moves_every_entry() {
+    let map = filled(100);
+    let (sender, receiver) = mpsc::channel();
+    assert_eq!(map.drain_to(&sender), Ok(100));
+    drop(sender);
+
+    let mut drained: Vec<_> = receiver.into_iter().collect();
+    drained.sort_unstable();
+    assert_eq!(drained, (0..100).map(|key| (key, key)).collect::<Vec<_>>());
+    assert!(map.is_empty());
+}
+
+#[test]
+fn drain_moves_values_that_are_not_clone() {
+    let map: ConcurrentHashMap<u64, Box<str>> = ConcurrentHashMap::new();
+    {
+        let guard = map.guard();
+        for key in 0..10 {
+            map.insert(key, key.to_string().into_boxed_str(), &guard);
+        }
+    }
+    let (sender, receiver) = mpsc::channel::<(u64, Box<str>)>();
+    assert_eq!(map.drain_to(&sender), Ok(10));
+    drop(sender);
+    for (key, value) in receiver {
+        assert_eq!(*value, key.to_string());
+    }
+}
+
+#[test]
+fn failed_send_returns_one_entry_and_restores_the_rest() {
+    let map = filled(100);
+    let (sender, receiver) = mpsc::channel();
+    drop(receiver);
+
+    let (key, value) = match map.drain_to(&sender) {
+        Err(DrainError::Disconnected(SendError(entry))) => entry,
+        other => panic!("unexpected drain result: {:?}", other),
+    };
+    assert_eq!(key, value);
+    assert_eq!(map.len(), 99);
+    let guard = map.guard();
+    for other in 0..100 {
+        assert_eq!(map.get(&other, &guard).is_some(), other != key);
+    }
+}
+
+#[test]
+fn failed_send_does_not_overwrite_concurrent_writers() {
+    let map = filled(1000);
+    let (sender, receiver) = mpsc::channel();
+    drop(receiver);
+
+    let failed = thread::scope(|s| {
+        s.spawn(|| {
+            let guard = map.guard();
+            for key in 0..1000 {
+                map.insert(key, key + 10_000, &guard);
+            }
+        });
+        match map.drain_to(&sender) {
+            Err(DrainError::Disconnected(SendError((key, _)))) => key,
+            other => panic!("unexpected drain result: {:?}", other),
+        }
+    });
+
+    let guard = map.guard();
+    for key in 0..1000 {
+        match map.get(&key, &guard) {
+            Some(&value) => assert_eq!(value, key + 10_000, "key {} was overwritten", key),
+            None => assert_eq!(key, failed),
+        }
+    }
+}
+
+#[test]
+fn drain_hands_off_entries_when_another_thread_stays_pinned() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().collector(Collector::new()).build();
+    {
+        let guard = map.guard();
+        for key in 0..100 {
+            map.insert(key, key, &guard);
+        }
+    }
+    let (sender, receiver) = mpsc::channel();
+    let (pinned, release) = mpsc::channel::<()>();
+    let (ready, wait) = mpsc::channel();
+
+    thread::scope(|s| {
+        let map = &map;
+        s.spawn(move || {
+            let _guard = map.guard();
+            ready.send(()).unwrap();
+            let _ = release.recv();
+        });
+        wait.recv().unwrap();
+        match map.drain_to(&sender) {
+            Err(DrainError::Stalled { sent, pending }) => assert_eq!(sent + pending, 100),
+            other => panic!("unexpected drain result: {:?}", other),
+        }
+        drop(pinned);
+    });
+    drop(sender);
+    assert!(map.is_empty());
+
+    let mut drained = Vec::new();
+    while drained.len() < 100 {
+        map.try_advance();
+        if let Ok(entry) = receiver.recv_timeout(Duration::from_millis(10)) {
+            drained.push(entry);
+        }
+    }
+    drained.sort_unstable();
+    assert_eq!(drained, (0..100).map(|key| (key, key)).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic(expected = "drain_to cannot wait for reclamation while this thread is pinned")]
+fn drain_refuses_to_run_while_pinned() {
+    let map = filled(10);
+    let (sender, _receiver) = mpsc::channel();
+    let _guard = map.guard();
+    let _ = map.drain_to(&sender);
+}
+
+#[test]
+fn consumers_receive_entries_while_the_drain_runs() {
+    let map = Arc::new(filled(10_000));
+    let (sender, receiver) = mpsc::channel();
+    let drainer = {
+        let map = Arc::clone(&map);
+        thread::spawn(move || map.drain_to(&sender))
+    };
+    let received = receiver.into_iter().count();
+    assert_eq!(drainer.join().unwrap(), Ok(10_000));
+    assert_eq!(received, 10_000);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn drain_runs_alongside_writers() {
+    let map = filled(1000);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|s| {
+        s.spawn(|| {
+            let guard = map.guard();
+            for key in 1000..2000 {
+                map.insert(key, key, &guard);
+            }
+        });
+        map.drain_to(&sender).unwrap();
+    });
+    drop(sender);
+
+    let guard = map.guard();
+    let drained: Vec<_> = receiver.into_iter().collect();
+    for key in 0..2000 {
+        let in_map = map.get(&key, &guard).is_some();
+        let sent = drained.iter().any(|&(k, _)| k == key);
+        assert!(
+            in_map != sent,
+            "key {} in map: {}, sent: {}",
+            key,
+            in_map,
+            sent
+        );
+    }
+}
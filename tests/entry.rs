@@ -0,0 +1,121 @@
+mod common;
+
+use std::{thread, time::Duration};
+
+use concurrent_hash_table::{
+    map::{builder::Builder, entry::Entry},
+    ConcurrentHashMap,
+};
+
+use common::Collide;
+
+#[test]
+fn vacant_and_occupied_entries() {
+    let map: ConcurrentHashMap<u64, String> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    match map.entry(1, &guard) {
+        Entry::Vacant(entry) => {
+            assert_eq!(*entry.key(), 1);
+            assert_eq!(entry.insert("one".to_string()), "one");
+        }
+        Entry::Occupied(_) => panic!("empty map has an occupied entry"),
+    }
+
+    match map.entry(1, &guard) {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.get(), "one");
+            assert_eq!(entry.insert("uno".to_string()), "one");
+            assert_eq!(entry.get(), "uno");
+        }
+        Entry::Vacant(_) => panic!("inserted key is vacant"),
+    }
+    assert_eq!(map.get(&1, &guard).map(String::as_str), Some("uno"));
+
+    match map.entry(1, &guard) {
+        Entry::Occupied(entry) => assert_eq!(entry.remove_entry(), (&1, &"uno".to_string())),
+        Entry::Vacant(_) => panic!("inserted key is vacant"),
+    }
+    assert_eq!(map.get(&1, &guard), None);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn or_insert_variants() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(*map.entry(1, &guard).or_insert(10), 10);
+    assert_eq!(*map.entry(1, &guard).or_insert(20), 10);
+    assert_eq!(
+        *map.entry(2, &guard).or_insert_with_key(|key| key * 100),
+        200
+    );
+    assert_eq!(*map.entry(3, &guard).or_default(), 0);
+    assert_eq!(
+        *map.entry(1, &guard)
+            .and_modify(|value| *value += 1)
+            .or_insert(0),
+        11
+    );
+    assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn entries_serialise_concurrent_writers() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let guard = map.guard();
+                for i in 0..4000 {
+                    map.entry(i % 8, &guard)
+                        .and_modify(|value| *value += 1)
+                        .or_insert(1);
+                }
+            });
+        }
+    });
+    let guard = map.guard();
+    assert_eq!(map.len(), 8);
+    for key in 0..8 {
+        assert_eq!(map.get(&key, &guard), Some(&2000));
+    }
+}
+
+#[test]
+fn entries_in_tree_bins() {
+    let map: ConcurrentHashMap<u64, u64, Collide> = Builder::new().build_with_hasher(Collide);
+    let guard = map.guard();
+    for key in 0..64 {
+        map.entry(key, &guard).or_insert(key);
+    }
+    for key in 0..64 {
+        assert_eq!(
+            *map.entry(key, &guard)
+                .and_modify(|value| *value *= 2)
+                .or_insert(0),
+            key * 2
+        );
+    }
+    assert_eq!(map.len(), 64);
+}
+
+#[test]
+fn entry_waits_for_a_lease() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+    let lease = map.lease(&1, Duration::from_secs(60), &guard).unwrap();
+
+    thread::scope(|s| {
+        let writer = s.spawn(|| {
+            let guard = map.guard();
+            map.entry(1, &guard).and_modify(|value| *value = 2);
+        });
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(map.get(&1, &guard), Some(&1));
+        assert_eq!(map.insert_leased(&1, 3, lease, &guard), Ok(&1));
+        assert!(map.release_lease(&1, lease, &guard));
+        writer.join().unwrap();
+    });
+    assert_eq!(map.get(&1, &guard), Some(&2));
+}
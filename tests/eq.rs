@@ -0,0 +1,36 @@
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+use crossbeam_epoch::Collector;
+
+fn map(entries: impl IntoIterator<Item = (u64, u64)>) -> ConcurrentHashMap<u64, u64> {
+    entries.into_iter().collect()
+}
+
+#[test]
+fn maps_with_the_same_entries_are_equal() {
+    let a = map((0..100).map(|key| (key, key)));
+    let b = map((0..100).rev().map(|key| (key, key)));
+    assert!(a == b);
+    assert!(map(None) == map(None));
+}
+
+#[test]
+fn maps_differing_in_a_value_or_key_are_not_equal() {
+    let a = map((0..100).map(|key| (key, key)));
+    assert!(a != map((0..100).map(|key| (key, key + u64::from(key == 50)))));
+    assert!(a != map((1..101).map(|key| (key, key))));
+    assert!(a != map((0..99).map(|key| (key, key))));
+}
+
+#[test]
+fn maps_with_different_collectors_compare_equal() {
+    let left: ConcurrentHashMap<u64, u64> = Builder::new().collector(Collector::new()).build();
+    let right: ConcurrentHashMap<u64, u64> = Builder::new().collector(Collector::new()).build();
+    for key in 0..32 {
+        left.insert(key, key * 2, &left.guard());
+        right.insert(key, key * 2, &right.guard());
+    }
+    assert!(left == right);
+
+    right.insert(5, 0, &right.guard());
+    assert!(left != right);
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use std::hash::{Hash, Hasher};
+
+use concurrent_hash_table::{
+    map::builder::Builder, util::equivalent::Equivalent, ConcurrentHashMap,
+};
+
+use common::Collide;
+
+struct KeyRef<'a>(&'a str, u32);
+
+impl Hash for KeyRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.0, self.1).hash(state);
+    }
+}
+
+impl Equivalent<(String, u32)> for KeyRef<'_> {
+    fn equivalent(&self, key: &(String, u32)) -> bool {
+        self.0 == key.0 && self.1 == key.1
+    }
+}
+
+#[test]
+fn composite_keys_are_found_through_a_borrowed_view() {
+    let map: ConcurrentHashMap<(String, u32), u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(("a".to_string(), 1), 10, &guard);
+    map.insert(("a".to_string(), 2), 20, &guard);
+
+    assert_eq!(map.get(&KeyRef("a", 1), &guard), Some(&10));
+    assert_eq!(map.get(&KeyRef("a", 2), &guard), Some(&20));
+    assert!(!map.contains_key(&KeyRef("b", 1), &guard));
+    assert_eq!(map.remove(&KeyRef("a", 1), &guard), Some(&10));
+    assert_eq!(map.get(&KeyRef("a", 1), &guard), None);
+}
+
+#[test]
+fn tree_bins_find_keys_through_equivalent() {
+    let map: ConcurrentHashMap<(String, u32), u64, Collide> =
+        Builder::new().build_with_hasher(Collide);
+    let guard = map.guard();
+    for n in 0..32 {
+        map.insert((n.to_string(), n), u64::from(n), &guard);
+    }
+    for n in 0..32 {
+        let name = n.to_string();
+        assert_eq!(map.get(&KeyRef(&name, n), &guard), Some(&u64::from(n)));
+    }
+    assert_eq!(map.get(&KeyRef("x", 0), &guard), None);
+}
+
+#[test]
+fn string_keys_are_found_by_str() {
+    let map: ConcurrentHashMap<String, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert("key".to_string(), 1, &guard);
+    assert_eq!(map.get("key", &guard), Some(&1));
+    assert!(map.contains_key("key", &guard));
+}
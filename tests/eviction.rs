@@ -0,0 +1,77 @@
+use concurrent_hash_table::{
+    map::builder::Builder, util::eviction_policy::EvictionPolicy, ConcurrentHashMap,
+};
+
+#[test]
+fn inserts_evict_down_to_max_capacity() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().max_capacity(100).build();
+    let guard = map.guard();
+    for key in 0..1000 {
+        map.insert(key, key, &guard);
+        assert!(map.len() <= 100);
+    }
+    assert_eq!(map.len(), 100);
+    assert_eq!(map.iter(&guard).count(), 100);
+}
+
+#[test]
+fn lru_prefers_entries_that_were_not_read() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().max_capacity(100).build();
+    let guard = map.guard();
+    for key in 0..100 {
+        map.insert(key, key, &guard);
+    }
+    for key in 0..50 {
+        assert_eq!(map.get(&key, &guard), Some(&key));
+    }
+    for key in 100..150 {
+        map.insert(key, key, &guard);
+    }
+
+    let read = (0..50).filter(|key| map.contains_key(key, &guard)).count();
+    let unread = (50..100)
+        .filter(|key| map.contains_key(key, &guard))
+        .count();
+    assert!(read > unread, "read {} unread {}", read, unread);
+}
+
+#[test]
+fn lfu_prefers_entries_that_were_read_often() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new()
+        .max_capacity(100)
+        .eviction_policy(EvictionPolicy::Lfu)
+        .build();
+    let guard = map.guard();
+    for key in 0..100 {
+        map.insert(key, key, &guard);
+    }
+    for _ in 0..4 {
+        for key in 50..100 {
+            map.get(&key, &guard);
+        }
+    }
+    for key in 100..150 {
+        map.insert(key, key, &guard);
+    }
+
+    let hot = (50..100)
+        .filter(|key| map.contains_key(key, &guard))
+        .count();
+    let cold = (0..50).filter(|key| map.contains_key(key, &guard)).count();
+    assert!(hot > cold, "hot {} cold {}", hot, cold);
+}
+
+#[test]
+fn pinned_entries_are_never_evicted() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().max_capacity(10).build();
+    let guard = map.guard();
+    for key in 0..5 {
+        map.insert(key, key, &guard);
+        assert!(map.pin_entry(&key, &guard));
+    }
+    for key in 5..500 {
+        map.insert(key, key, &guard);
+    }
+    assert_eq!(map.len(), 10);
+    assert!((0..5).all(|key| map.contains_key(&key, &guard)));
+}
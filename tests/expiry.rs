@@ -0,0 +1,58 @@
+use std::{thread, time::Duration};
+
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+
+#[test]
+fn expired_entries_read_as_absent_until_rewritten() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new()
+        .expire_after_write(Duration::from_millis(20))
+        .build();
+    let guard = map.guard();
+    map.insert(1, 10, &guard);
+    assert_eq!(map.get(&1, &guard), Some(&10));
+
+    thread::sleep(Duration::from_millis(40));
+    assert_eq!(map.get(&1, &guard), None);
+    assert!(!map.contains_key(&1, &guard));
+    assert_eq!(map.iter(&guard).count(), 0);
+    assert_eq!(map.update(&1, |value| value + 1, &guard), None);
+
+    assert_eq!(map.insert(1, 11, &guard), None);
+    assert_eq!(map.get(&1, &guard), Some(&11));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn reads_extend_expire_after_access() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new()
+        .expire_after_access(Duration::from_millis(100))
+        .build();
+    let guard = map.guard();
+    map.insert(1, 10, &guard);
+    map.insert(2, 20, &guard);
+    for _ in 0..6 {
+        thread::sleep(Duration::from_millis(30));
+        assert_eq!(map.get(&1, &guard), Some(&10));
+    }
+
+    assert_eq!(map.get(&2, &guard), None);
+    assert_eq!(map.get(&1, &guard), Some(&10));
+}
+
+#[test]
+fn purge_expired_removes_entries() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new()
+        .expire_after_write(Duration::from_millis(20))
+        .build();
+    let guard = map.guard();
+    for key in 0..256 {
+        map.insert(key, key, &guard);
+    }
+    thread::sleep(Duration::from_millis(40));
+    map.insert(1000, 1000, &guard);
+    assert!(map.len() <= 257);
+
+    map.purge_expired(&guard);
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get(&1000, &guard), Some(&1000));
+}
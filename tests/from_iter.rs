@@ -0,0 +1,20 @@
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn collect_builds_a_map() {
+    let map: ConcurrentHashMap<u64, u64> = (0..500).map(|key| (key, key * 2)).collect();
+    let guard = map.guard();
+    assert_eq!(map.len(), 500);
+    assert!((0..500).all(|key| map.get(&key, &guard) == Some(&(key * 2))));
+}
+
+#[test]
+fn extend_inserts_and_overwrites() {
+    let mut map: ConcurrentHashMap<u64, u64> = (0..10).map(|key| (key, 0)).collect();
+    map.extend((5..20).map(|key| (key, 1)));
+
+    let guard = map.guard();
+    assert_eq!(map.len(), 20);
+    assert!((0..5).all(|key| map.get(&key, &guard) == Some(&0)));
+    assert!((5..20).all(|key| map.get(&key, &guard) == Some(&1)));
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+
+use common::Collide;
+
+#[test]
+fn freeze_keeps_every_entry() {
+    let map: ConcurrentHashMap<String, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..500 {
+        map.insert(key.to_string(), key, &guard);
+    }
+    map.remove("7", &guard);
+    drop(guard);
+
+    let frozen = map.freeze();
+    assert_eq!(frozen.len(), 499);
+    assert_eq!(frozen.get("42"), Some(&42));
+    assert_eq!(
+        frozen.get_key_value("499"),
+        Some((&"499".to_string(), &499))
+    );
+    assert!(!frozen.contains_key("7"));
+    assert!(!frozen.contains_key("500"));
+
+    let mut values: Vec<u64> = frozen.iter().map(|(_, &value)| value).collect();
+    values.sort_unstable();
+    assert_eq!(values, (0..500).filter(|&v| v != 7).collect::<Vec<_>>());
+    assert_eq!(frozen.keys().count(), 499);
+}
+
+#[test]
+fn freeze_flattens_tree_bins() {
+    let map: ConcurrentHashMap<u64, u64, Collide> = Builder::new().build_with_hasher(Collide);
+    let guard = map.guard();
+    for key in 0..64 {
+        map.insert(key, key * 2, &guard);
+    }
+    drop(guard);
+
+    let frozen = map.freeze();
+    assert_eq!(frozen.len(), 64);
+    assert!((0..64).all(|key| frozen.get(&key) == Some(&(key * 2))));
+    assert_eq!(frozen.get(&64), None);
+}
+
+#[test]
+fn freezing_an_empty_map() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let frozen = map.freeze();
+    assert!(frozen.is_empty());
+    assert_eq!(frozen.get(&1), None);
+}
@@ -0,0 +1,48 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn inserts_only_when_absent() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(*map.get_or_insert_with(1, || 10, &guard), 10);
+    assert_eq!(
+        *map.get_or_insert_with(1, || unreachable!("key is present"), &guard),
+        10
+    );
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn initializer_runs_once_under_contention() {
+    for key in 0..20 {
+        let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+        let calls = AtomicUsize::new(0);
+        thread::scope(|s| {
+            for t in 0..8 {
+                let map = &map;
+                let calls = &calls;
+                s.spawn(move || {
+                    let guard = map.guard();
+                    let value = map.get_or_insert_with(
+                        key,
+                        || {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            thread::sleep(Duration::from_millis(1));
+                            t
+                        },
+                        &guard,
+                    );
+                    assert!(*value < 8);
+                });
+            }
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(map.len(), 1);
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn get_with_loads_on_a_miss_only() {
+    let map: ConcurrentHashMap<u64, String> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(map.get_with(1, || "one".to_string(), &guard), "one");
+    assert_eq!(map.get(&1, &guard).map(String::as_str), Some("one"));
+    assert_eq!(
+        map.get_with(1, || unreachable!("loaded a present key"), &guard),
+        "one"
+    );
+}
+
+#[test]
+fn errors_are_returned_and_not_cached() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(
+        map.try_get_with(1, || Err("unavailable"), &guard),
+        Err("unavailable")
+    );
+    assert_eq!(map.get(&1, &guard), None);
+    assert_eq!(map.try_get_with(1, || Ok::<_, &str>(10), &guard), Ok(&10));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn concurrent_misses_run_the_loader_once() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let loads = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                let guard = map.guard();
+                let value = map.get_with(
+                    7,
+                    || {
+                        loads.fetch_add(1, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(20));
+                        70
+                    },
+                    &guard,
+                );
+                assert_eq!(*value, 70);
+            });
+        }
+    });
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn a_failed_load_lets_a_waiter_retry() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let attempts = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let guard = map.guard();
+                let _ = map.try_get_with(
+                    1,
+                    || {
+                        thread::sleep(Duration::from_millis(10));
+                        if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                            Err(())
+                        } else {
+                            Ok(1)
+                        }
+                    },
+                    &guard,
+                );
+            });
+        }
+    });
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    assert_eq!(map.get(&1, &map.guard()), Some(&1));
+}
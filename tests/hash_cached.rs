@@ -0,0 +1,139 @@
+use std::{
+    borrow::Borrow,
+    hash::{Hash, Hasher},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use concurrent_hash_table::{
+    map::{builder::Builder, ConcurrentHashMap},
+    util::hashing::{HashCached, HashCachedState, KeyHasher, SeededState},
+};
+
+static REHASHES: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Symbol {
+    id: u64,
+    cached: bool,
+}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        REHASHES.fetch_add(1, Ordering::SeqCst);
+        self.id.hash(state);
+    }
+}
+
+unsafe impl HashCached for Symbol {
+    fn cached_hash(&self) -> Option<u64> {
+        if self.cached {
+            Some(self.id.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn cached_hashes_are_reused_and_others_fall_back() {
+    let map: ConcurrentHashMap<Symbol, u64, HashCachedState> =
+        Builder::new().build_with_hasher(HashCachedState::new(SeededState::new()));
+    let guard = map.guard();
+
+    for id in 0..64 {
+        map.insert(Symbol { id, cached: true }, id, &guard);
+    }
+    for id in 0..64 {
+        assert_eq!(map.get(&Symbol { id, cached: true }, &guard), Some(&id));
+    }
+    assert_eq!(REHASHES.load(Ordering::SeqCst), 0);
+
+    map.insert(
+        Symbol {
+            id: 100,
+            cached: false,
+        },
+        100,
+        &guard,
+    );
+    assert_eq!(
+        map.get(
+            &Symbol {
+                id: 100,
+                cached: false
+            },
+            &guard
+        ),
+        Some(&100)
+    );
+    assert!(REHASHES.load(Ordering::SeqCst) >= 2);
+    assert_eq!(map.len(), 65);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct SymbolId(u64);
+
+unsafe impl HashCached for SymbolId {
+    fn cached_hash(&self) -> Option<u64> {
+        Some(self.0.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Named {
+    id: SymbolId,
+    name: String,
+}
+
+impl PartialEq for Named {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Named {}
+
+impl Hash for Named {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+unsafe impl HashCached for Named {
+    fn cached_hash(&self) -> Option<u64> {
+        self.id.cached_hash()
+    }
+}
+
+impl Borrow<SymbolId> for Named {
+    fn borrow(&self) -> &SymbolId {
+        &self.id
+    }
+}
+
+#[test]
+fn borrowed_keys_hash_like_their_owners() {
+    let map: ConcurrentHashMap<Named, u64, HashCachedState> =
+        Builder::new().build_with_hasher(HashCachedState::new(SeededState::new()));
+    let guard = map.guard();
+
+    for id in 0..64 {
+        let key = Named {
+            id: SymbolId(id),
+            name: format!("symbol-{}", id),
+        };
+        assert_eq!(
+            map.hasher().hash_key(&key),
+            map.hasher().hash_key(&SymbolId(id))
+        );
+        map.insert(key, id, &guard);
+    }
+    for id in 0..64 {
+        assert_eq!(map.get(&SymbolId(id), &guard), Some(&id));
+        let (key, _) = map.get_key_value(&SymbolId(id), &guard).unwrap();
+        assert_eq!(key.name, format!("symbol-{}", id));
+    }
+    assert_eq!(map.remove(&SymbolId(7), &guard), Some(&7));
+    assert!(!map.contains_key(&SymbolId(7), &guard));
+    assert_eq!(map.len(), 63);
+}
@@ -0,0 +1,61 @@
+use concurrent_hash_table::{
+    map::builder::Builder,
+    util::hashing::{self, KeyHasher},
+    ConcurrentHashMap,
+};
+
+#[derive(Clone, Copy)]
+struct Spread(u64);
+
+impl KeyHasher<u64> for Spread {
+    fn hash_key(&self, key: &u64) -> u64 {
+        key.wrapping_mul(self.0)
+    }
+}
+
+#[test]
+fn yields_every_entry_with_its_stored_hash() {
+    let hasher = Spread(0x9e37_79b9_7f4a_7c15);
+    let map: ConcurrentHashMap<u64, u64, Spread> = Builder::new().build_with_hasher(hasher);
+    let guard = map.guard();
+    for key in 0..500 {
+        map.insert(key, key * 3, &guard);
+    }
+
+    let mut seen: Vec<_> = map
+        .hashed_iter(&guard)
+        .map(|(hash, &key, &value)| {
+            assert_eq!(hash, hashing::spread(hasher.hash_key(&key)));
+            assert_eq!(value, key * 3);
+            key
+        })
+        .collect();
+    seen.sort_unstable();
+    assert_eq!(seen, (0..500).collect::<Vec<_>>());
+}
+
+#[test]
+fn walks_tree_bins() {
+    let hasher = Spread(0);
+    let map: ConcurrentHashMap<u64, u64, Spread> = Builder::new().build_with_hasher(hasher);
+    let guard = map.guard();
+    for key in 0..64 {
+        map.insert(key, key, &guard);
+    }
+
+    let mut seen: Vec<_> = map
+        .hashed_iter(&guard)
+        .map(|(hash, &key, _)| {
+            assert_eq!(hash, 0);
+            key
+        })
+        .collect();
+    seen.sort_unstable();
+    assert_eq!(seen, (0..64).collect::<Vec<_>>());
+}
+
+#[test]
+fn empty_map_yields_nothing() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    assert_eq!(map.hashed_iter(&map.guard()).count(), 0);
+}
@@ -0,0 +1,79 @@
+use std::thread;
+
+use concurrent_hash_table::{
+    map::builder::Builder, util::contention_policy::ContentionPolicy, ConcurrentHashMap,
+};
+
+fn incremental(stride: usize, readers_help_resize: bool) -> ConcurrentHashMap<u64, u64> {
+    Builder::new()
+        .capacity(16)
+        .incremental_resize(stride)
+        .contention_policy(ContentionPolicy {
+            readers_help_resize,
+            ..ContentionPolicy::default()
+        })
+        .build()
+}
+
+#[test]
+fn incremental_resizes_keep_every_entry() {
+    let map = incremental(1, false);
+    let guard = map.guard();
+    for key in 0..20_000 {
+        map.insert(key, key, &guard);
+        assert_eq!(map.get(&(key / 2), &guard), Some(&(key / 2)));
+    }
+    assert_eq!(map.len(), 20_000);
+    assert!((0..20_000).all(|key| map.get(&key, &guard) == Some(&key)));
+    assert_eq!(map.iter(&guard).count(), 20_000);
+}
+
+#[test]
+fn concurrent_writers_share_incremental_resizes() {
+    let map = incremental(2, true);
+    thread::scope(|s| {
+        for t in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in (t * 10_000)..((t + 1) * 10_000) {
+                    map.insert(key, key, &guard);
+                }
+            });
+        }
+        s.spawn(|| {
+            let guard = map.guard();
+            for key in 0..40_000 {
+                if let Some(value) = map.get(&key, &guard) {
+                    assert_eq!(*value, key);
+                }
+            }
+        });
+    });
+
+    let guard = map.guard();
+    assert_eq!(map.len(), 40_000);
+    assert!((0..40_000).all(|key| map.get(&key, &guard) == Some(&key)));
+}
+
+#[test]
+fn load_finishes_an_incremental_resize() {
+    let mut map = incremental(1, false);
+    {
+        let guard = map.guard();
+        for key in 0..13 {
+            map.insert(key, key, &guard);
+        }
+    }
+
+    map.load(|loader| {
+        for key in 13..1_000 {
+            loader.insert(key, key);
+        }
+    });
+
+    let guard = map.guard();
+    assert_eq!(map.len(), 1_000);
+    assert!((0..1_000).all(|key| map.get(&key, &guard) == Some(&key)));
+    assert_eq!(map.iter(&guard).count(), 1_000);
+}
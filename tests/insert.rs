@@ -0,0 +1,44 @@
+mod common;
+
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+
+use common::Collide;
+
+#[test]
+fn insert_returns_the_displaced_value() {
+    let map: ConcurrentHashMap<u64, String> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(map.insert(1, "a".to_string(), &guard), None);
+    assert_eq!(
+        map.insert(1, "b".to_string(), &guard).map(String::as_str),
+        Some("a")
+    );
+    assert_eq!(
+        map.insert_owned(1, "c".to_string(), &guard),
+        Some("b".to_string())
+    );
+    assert_eq!(map.insert_owned(2, "d".to_string(), &guard), None);
+    assert_eq!(map.get(&1, &guard).map(String::as_str), Some("c"));
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn insert_into_a_tree_bin_returns_the_displaced_value() {
+    let map: ConcurrentHashMap<u64, u64, Collide> = Builder::new().build_with_hasher(Collide);
+    let guard = map.guard();
+    for key in 0..64 {
+        assert_eq!(map.insert(key, key, &guard), None);
+    }
+    for key in 0..64 {
+        assert_eq!(map.insert(key, key + 1, &guard), Some(&key));
+    }
+    assert_eq!(map.len(), 64);
+}
+
+#[test]
+fn insert_boxed_returns_the_displaced_value() {
+    let map: ConcurrentHashMap<u64, Box<str>> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(map.insert_boxed(1, "a", &guard), None);
+    assert_eq!(map.insert_boxed(1, "b", &guard), Some("a"));
+}
@@ -0,0 +1,58 @@
+use std::thread;
+
+use concurrent_hash_table::{
+    util::hashing::{self, IntHasher, KeyHasher},
+    IntMap,
+};
+
+#[test]
+fn hashes_are_mixed_keys() {
+    assert_eq!(IntHasher.hash_key(&42_u64), hashing::mix(42));
+    assert_eq!(IntHasher.hash_key(&42_u32), hashing::mix(42));
+    assert_ne!(hashing::mix(1), hashing::mix(2));
+    assert_ne!(hashing::mix(1) & 0xff, hashing::mix(257) & 0xff);
+}
+
+#[test]
+fn u64_keys() {
+    let map: IntMap<u64> = IntMap::default();
+    let guard = map.guard();
+    for key in 0..10_000 {
+        map.insert(key << 32, key, &guard);
+    }
+    assert_eq!(map.len(), 10_000);
+    for key in 0..10_000 {
+        assert_eq!(map.get(&(key << 32), &guard), Some(&key));
+    }
+    assert_eq!(map.remove(&0, &guard), Some(&0));
+    assert_eq!(map.get(&0, &guard), None);
+}
+
+#[test]
+fn u32_and_signed_keys() {
+    let small: IntMap<&str, u32> = IntMap::with_capacity_and_hasher(16, IntHasher);
+    let signed: IntMap<&str, i64> = IntMap::default();
+    let guard = small.guard();
+    small.insert(7, "seven", &guard);
+    signed.insert(-7, "minus seven", &guard);
+    assert_eq!(small.get(&7, &guard), Some(&"seven"));
+    assert_eq!(signed.get(&-7, &guard), Some(&"minus seven"));
+    assert_eq!(signed.get(&7, &guard), None);
+}
+
+#[test]
+fn concurrent_inserts() {
+    let map: IntMap<u64> = IntMap::default();
+    thread::scope(|s| {
+        for t in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in (t..40_000).step_by(4) {
+                    map.insert(key, key, &guard);
+                }
+            });
+        }
+    });
+    assert_eq!(map.len(), 40_000);
+}
@@ -0,0 +1,46 @@
+use std::{sync::Arc, thread};
+
+use concurrent_hash_table::map::{interner::Interner, ConcurrentHashMap};
+
+#[test]
+fn equal_strings_share_one_allocation() {
+    let interner = Interner::new();
+    let first = interner.intern("status");
+    let second = interner.intern(&String::from("status"));
+    assert!(Arc::ptr_eq(&first, &second));
+    assert!(!Arc::ptr_eq(&first, &interner.intern("other")));
+    assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn racing_threads_get_the_same_string() {
+    let interner = Arc::new(Interner::new());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let interner = Arc::clone(&interner);
+            thread::spawn(move || {
+                (0..100)
+                    .map(|i| interner.intern(&format!("key-{}", i)))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+    assert_eq!(interner.len(), 100);
+    for result in &results[1..] {
+        for (left, right) in result.iter().zip(&results[0]) {
+            assert!(Arc::ptr_eq(left, right));
+        }
+    }
+}
+
+#[test]
+fn intern_in_map_keys_the_map_by_the_shared_string() {
+    let interner = Interner::new();
+    let map: ConcurrentHashMap<Arc<str>, u32> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    let key = interner.intern_in_map(&map, "host", 1, &guard);
+    assert!(Arc::ptr_eq(&key, &interner.intern("host")));
+    assert_eq!(map.get("host", &guard), Some(&1));
+}
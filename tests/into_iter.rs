@@ -0,0 +1,52 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+struct Tracked(Arc<AtomicUsize>);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn into_iter_yields_owned_entries() {
+    let map: ConcurrentHashMap<u64, String> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..200 {
+        map.insert(key, key.to_string(), &guard);
+    }
+    drop(guard);
+
+    let entries = map.into_iter();
+    assert_eq!(entries.len(), 200);
+    let mut entries: Vec<(u64, String)> = entries.collect();
+    entries.sort_unstable();
+    assert_eq!(
+        entries,
+        (0..200)
+            .map(|key| (key, key.to_string()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn into_iter_hands_every_value_to_the_caller() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let map: ConcurrentHashMap<u64, Tracked> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..100 {
+        map.insert(key, Tracked(Arc::clone(&drops)), &guard);
+    }
+    drop(guard);
+
+    let entries: Vec<_> = map.into_iter().collect();
+    assert_eq!(entries.len(), 100);
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    drop(entries);
+    assert_eq!(drops.load(Ordering::SeqCst), 100);
+}
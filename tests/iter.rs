@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn iter_keys_and_values_agree() {
+    let map: ConcurrentHashMap<u64, String> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..300 {
+        map.insert(key, key.to_string(), &guard);
+    }
+
+    let entries: HashMap<u64, String> = map
+        .iter(&guard)
+        .map(|(key, value)| (*key, value.clone()))
+        .collect();
+    assert_eq!(entries.len(), 300);
+    assert!(entries.iter().all(|(key, value)| *value == key.to_string()));
+
+    let mut keys: Vec<u64> = map.keys(&guard).copied().collect();
+    keys.sort_unstable();
+    assert_eq!(keys, (0..300).collect::<Vec<_>>());
+
+    let mut values: Vec<u64> = map
+        .values(&guard)
+        .map(|value| value.parse().unwrap())
+        .collect();
+    values.sort_unstable();
+    assert_eq!(values, keys);
+}
+
+#[test]
+fn iter_on_an_empty_map_yields_nothing() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(map.iter(&guard).count(), 0);
+    map.with(|pinned| {
+        pinned.insert(1, 2);
+        assert_eq!(pinned.iter().collect::<Vec<_>>(), vec![(&1, &2)]);
+        assert_eq!(pinned.keys().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(pinned.values().collect::<Vec<_>>(), vec![&2]);
+    });
+}
@@ -0,0 +1,60 @@
+use std::sync::mpsc;
+
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+
+#[test]
+fn reads_on_an_untouched_map_see_nothing() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().capacity(1 << 16).build();
+    let guard = map.guard();
+    assert!(map.is_empty());
+    assert_eq!(map.len(), 0);
+    assert_eq!(map.get(&1, &guard), None);
+    assert_eq!(map.get_key_value(&1, &guard), None);
+    assert_eq!(map.remove(&1, &guard), None);
+    assert!(!map.remove_if(&1, |_| true, &guard));
+    assert_eq!(map.iter(&guard).count(), 0);
+    map.retain(|_, _| false, &guard);
+    map.clear(&guard);
+    drop(guard);
+
+    let (sender, receiver) = mpsc::channel();
+    assert_eq!(map.drain_to(&sender), Ok(0));
+    assert!(receiver.try_recv().is_err());
+
+    let clone = map.clone();
+    assert!(clone.is_empty());
+    assert_eq!(clone.iter(&clone.guard()).count(), 0);
+}
+
+#[test]
+fn first_insert_allocates_the_table() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(map.insert(1, 10, &guard), None);
+    assert_eq!(map.get(&1, &guard), Some(&10));
+    for key in 2..1_000 {
+        map.insert(key, key * 10, &guard);
+    }
+    assert_eq!(map.len(), 999);
+    assert!((1..1_000).all(|key| map.get(&key, &guard) == Some(&(key * 10))));
+}
+
+#[test]
+fn reserve_and_load_initialize_an_untouched_map() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.reserve(100, &guard);
+    assert!(map.is_empty());
+    map.insert(1, 1, &guard);
+    assert_eq!(map.get(&1, &guard), Some(&1));
+
+    let mut map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    map.load(|loader| {
+        for key in 0..100 {
+            loader.insert(key, key);
+        }
+    });
+    let guard = map.guard();
+    assert_eq!(map.len(), 100);
+    assert!((0..100).all(|key| map.get(&key, &guard) == Some(&key)));
+}
@@ -0,0 +1,131 @@
+use std::{sync::Arc, thread, time::Duration};
+
+use concurrent_hash_table::{
+    map::builder::Builder,
+    util::lease::{LeaseError, LeasePolicy},
+    ConcurrentHashMap,
+};
+
+#[test]
+fn lease_is_exclusive_until_released() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(
+        map.lease(&1, Duration::from_secs(60), &guard),
+        Err(LeaseError::Absent)
+    );
+
+    map.insert(1, 1, &guard);
+    let lease = map.lease(&1, Duration::from_secs(60), &guard).unwrap();
+    assert_eq!(
+        map.lease(&1, Duration::from_secs(60), &guard),
+        Err(LeaseError::Held(lease))
+    );
+    assert!(map.release_lease(&1, lease, &guard));
+    assert!(!map.release_lease(&1, lease, &guard));
+    assert!(map.lease(&1, Duration::from_secs(60), &guard).is_ok());
+}
+
+#[test]
+fn lease_wait_claims_after_expiry() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+    let first = map.lease(&1, Duration::from_millis(20), &guard).unwrap();
+    let second = map.lease_wait(&1, Duration::from_secs(60), &guard).unwrap();
+    assert!(second.deadline > first.deadline);
+}
+
+#[test]
+fn put_waits_for_lease() {
+    let map: Arc<ConcurrentHashMap<u64, u64>> = Arc::new(ConcurrentHashMap::new());
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+    let lease = map.lease(&1, Duration::from_secs(60), &guard).unwrap();
+
+    let writer = {
+        let map = Arc::clone(&map);
+        thread::spawn(move || {
+            let guard = map.guard();
+            map.insert(1, 2, &guard).copied()
+        })
+    };
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(map.get(&1, &guard), Some(&1));
+
+    assert!(map.release_lease(&1, lease, &guard));
+    assert_eq!(writer.join().unwrap(), Some(1));
+    assert_eq!(map.get(&1, &guard), Some(&2));
+}
+
+#[test]
+fn remove_waits_for_lease() {
+    let map: Arc<ConcurrentHashMap<u64, u64>> = Arc::new(ConcurrentHashMap::new());
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+    let lease = map.lease(&1, Duration::from_secs(60), &guard).unwrap();
+
+    let remover = {
+        let map = Arc::clone(&map);
+        thread::spawn(move || {
+            let guard = map.guard();
+            map.remove(&1, &guard).copied()
+        })
+    };
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(map.get(&1, &guard), Some(&1));
+
+    assert_eq!(map.insert_leased(&1, 3, lease, &guard), Ok(&1));
+    assert!(map.release_lease(&1, lease, &guard));
+    assert_eq!(remover.join().unwrap(), Some(3));
+    assert_eq!(map.get(&1, &guard), None);
+}
+
+#[test]
+fn put_waits_for_lease_to_expire() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+    let lease = map.lease(&1, Duration::from_millis(20), &guard).unwrap();
+
+    assert_eq!(map.insert(1, 2, &guard), Some(&1));
+    assert_eq!(map.get(&1, &guard), Some(&2));
+    assert_eq!(
+        map.insert_leased(&1, 3, lease, &guard),
+        Err(LeaseError::Expired)
+    );
+}
+
+#[test]
+fn put_steals_lease() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().lease_policy(LeasePolicy::Steal).build();
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+    let lease = map.lease(&1, Duration::from_secs(60), &guard).unwrap();
+
+    assert_eq!(map.insert(1, 2, &guard), Some(&1));
+    assert_eq!(map.get(&1, &guard), Some(&2));
+    assert!(!map.release_lease(&1, lease, &guard));
+    assert!(map.lease(&1, Duration::from_secs(60), &guard).is_ok());
+}
+
+#[test]
+fn clear_waits_for_lease() {
+    let map: Arc<ConcurrentHashMap<u64, u64>> = Arc::new(ConcurrentHashMap::new());
+    let guard = map.guard();
+    for key in 0..64 {
+        map.insert(key, key, &guard);
+    }
+    let lease = map.lease(&7, Duration::from_secs(60), &guard).unwrap();
+
+    let clearer = {
+        let map = Arc::clone(&map);
+        thread::spawn(move || map.clear(&map.guard()))
+    };
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(map.get(&7, &guard), Some(&7));
+
+    assert!(map.release_lease(&7, lease, &guard));
+    clearer.join().unwrap();
+    assert!(map.is_empty());
+}
@@ -0,0 +1,80 @@
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+
+use concurrent_hash_table::{
+    map::{builder::Builder, listener::Event},
+    ConcurrentHashMap,
+};
+
+fn recording() -> (ConcurrentHashMap<u64, u64>, Arc<Mutex<Vec<String>>>) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&log);
+    let map = Builder::new()
+        .listener(move |event: &Event<'_, u64, u64>| {
+            let line = match *event {
+                Event::Insert { key, value } => format!("insert {} {}", key, value),
+                Event::Replace { key, old, new } => format!("replace {} {} {}", key, old, new),
+                Event::Remove { key, value } => format!("remove {} {}", key, value),
+            };
+            sink.lock().unwrap().push(line);
+        })
+        .build();
+    (map, log)
+}
+
+#[test]
+fn writes_emit_events_in_order() {
+    let (map, log) = recording();
+    let guard = map.guard();
+    map.insert(1, 10, &guard);
+    map.insert(1, 11, &guard);
+    map.update(&1, |value| value + 1, &guard);
+    map.update_cow(&1, |value| *value += 1, &guard);
+    map.remove(&1, &guard);
+    map.remove(&1, &guard);
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "insert 1 10",
+            "replace 1 10 11",
+            "replace 1 11 12",
+            "replace 1 12 13",
+            "remove 1 13",
+        ]
+    );
+}
+
+#[test]
+fn listeners_run_after_the_bin_is_unlocked() {
+    let this: Arc<OnceLock<Weak<ConcurrentHashMap<u64, u64>>>> = Arc::new(OnceLock::new());
+    let handle = Arc::clone(&this);
+    let map = Arc::new(
+        Builder::new()
+            .listener(move |event: &Event<'_, u64, u64>| {
+                if let Event::Insert { key, value } = *event {
+                    let map = handle.get().unwrap().upgrade().unwrap();
+                    map.insert(*key, value + 1, &map.guard());
+                }
+            })
+            .build(),
+    );
+    this.set(Arc::downgrade(&map)).unwrap();
+
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+    assert_eq!(map.get(&1, &guard), Some(&2));
+}
+
+#[test]
+fn entry_writes_emit_events_when_the_entry_is_dropped() {
+    let (map, log) = recording();
+    let guard = map.guard();
+    map.entry(1, &guard).or_insert(1);
+    {
+        let mut entry = map.lock_key(1, &guard);
+        entry.insert(2);
+        entry.insert(3);
+        assert_eq!(log.lock().unwrap().len(), 1);
+    }
+    assert_eq!(*log.lock().unwrap(), ["insert 1 1", "replace 1 1 3"]);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+
+use common::Collide;
+
+#[test]
+fn load_populates_the_map() {
+    let mut map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let loaded = map.load(|loader| {
+        for key in 0..10_000 {
+            loader.insert(key, key);
+        }
+        for key in 0..100 {
+            loader.insert(key, key + 1);
+        }
+        loader.loaded()
+    });
+    assert_eq!(loaded, 10_000);
+    assert_eq!(map.len(), 10_000);
+
+    let guard = map.guard();
+    for key in 0..10_000 {
+        let expected = if key < 100 { key + 1 } else { key };
+        assert_eq!(map.get(&key, &guard), Some(&expected));
+    }
+    map.insert(10_000, 0, &guard);
+    assert_eq!(map.len(), 10_001);
+}
+
+#[test]
+fn sealing_treeifies_long_bins() {
+    let mut map: ConcurrentHashMap<u64, u64, Collide> = Builder::new().build_with_hasher(Collide);
+    map.load(|loader| {
+        for key in 0..300 {
+            loader.insert(key, key * 2);
+        }
+        loader.insert(5, 0);
+    });
+    assert_eq!(map.len(), 300);
+
+    let guard = map.guard();
+    for key in 0..300 {
+        let expected = if key == 5 { 0 } else { key * 2 };
+        assert_eq!(map.get(&key, &guard), Some(&expected));
+    }
+    map.insert(300, 600, &guard);
+    map.insert(7, 7, &guard);
+    assert_eq!(map.get(&300, &guard), Some(&600));
+    assert_eq!(map.get(&7, &guard), Some(&7));
+    assert_eq!(map.len(), 301);
+}
+
+#[test]
+fn versioned_loads_assign_generations() {
+    let mut map: ConcurrentHashMap<u64, u64> = Builder::new().versioned(true).build();
+    map.load(|loader| {
+        loader.insert(1, 1);
+        loader.insert(2, 2);
+    });
+    let guard = map.guard();
+    let (first, _) = map.get_versioned(&1, &guard).unwrap();
+    let (second, _) = map.get_versioned(&2, &guard).unwrap();
+    assert_ne!(first, second);
+    assert!(map.insert_if_version(&1, first, 10, &guard).is_ok());
+}
+
+#[test]
+fn load_grows_a_small_table() {
+    let mut map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::with_capacity(1);
+    map.load(|loader| {
+        for key in 0..10_000 {
+            loader.insert(key, key * 2);
+        }
+    });
+
+    let guard = map.guard();
+    assert_eq!(map.len(), 10_000);
+    for key in 0..10_000 {
+        assert_eq!(map.get(&key, &guard), Some(&(key * 2)));
+    }
+}
+
+#[test]
+fn load_rebuilds_tree_bins() {
+    let mut map: ConcurrentHashMap<u64, u64, Collide> = Builder::new().build_with_hasher(Collide);
+    {
+        let guard = map.guard();
+        for key in 0..64 {
+            map.insert(key, key, &guard);
+        }
+    }
+
+    map.load(|loader| {
+        for key in 64..256 {
+            loader.insert(key, key);
+        }
+    });
+
+    let guard = map.guard();
+    assert_eq!(map.len(), 256);
+    for key in 0..256 {
+        assert_eq!(map.get(&key, &guard), Some(&key));
+    }
+    assert_eq!(map.iter(&guard).count(), 256);
+}
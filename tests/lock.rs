@@ -0,0 +1,47 @@
+use std::thread;
+
+use concurrent_hash_table::{util::lock::Mutex, ConcurrentHashMap};
+
+#[test]
+fn try_lock_fails_while_held() {
+    let mutex = Mutex::new(1);
+    let guard = mutex.lock();
+    assert!(mutex.try_lock().is_none());
+    drop(guard);
+    *mutex.try_lock().unwrap() += 1;
+    assert_eq!(*mutex.lock(), 2);
+}
+
+#[cfg(feature = "std-locks")]
+#[test]
+fn poisoned_lock_is_recovered() {
+    let mutex = Mutex::new(0);
+    let _ = thread::scope(|s| {
+        s.spawn(|| {
+            let _guard = mutex.lock();
+            panic!("poison the lock");
+        })
+        .join()
+    });
+    *mutex.lock() += 1;
+    assert_eq!(*mutex.try_lock().unwrap(), 1);
+}
+
+#[test]
+fn bin_locks_serialise_writers() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    thread::scope(|s| {
+        for t in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in 0..1000 {
+                    map.insert(key, t, &guard);
+                }
+            });
+        }
+    });
+    let guard = map.guard();
+    assert_eq!(map.len(), 1000);
+    assert!((0..1000).all(|key| map.get(&key, &guard).is_some()));
+}
@@ -0,0 +1,68 @@
+use std::{sync::Arc, thread};
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn lock_key_exposes_the_entry() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    {
+        let mut key = map.lock_key(1, &guard);
+        assert_eq!(key.get(), None);
+        assert_eq!(key.insert(5), None);
+        assert_eq!(key.get(), Some(&5));
+    }
+    assert_eq!(map.get(&1, &guard), Some(&5));
+
+    let pinned = map.pin();
+    assert_eq!(pinned.lock_key(1).remove(), Some(&5));
+    assert_eq!(pinned.get(&1), None);
+}
+
+#[test]
+fn lock_key_reservation_is_invisible_and_released_without_a_write() {
+    let map: ConcurrentHashMap<String, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    {
+        let mut key = map.lock_key("a".to_string(), &guard);
+        assert_eq!(map.get("a", &guard), None);
+        assert_eq!(map.len(), 0);
+        key.insert(1);
+        assert_eq!(map.get("a", &guard), None);
+    }
+    assert_eq!(map.get("a", &guard), Some(&1));
+
+    drop(map.lock_key("b".to_string(), &guard));
+    assert_eq!(map.get("b", &guard), None);
+    assert_eq!(map.len(), 1);
+    map.insert("b".to_string(), 2, &guard);
+    assert_eq!(map.get("b", &guard), Some(&2));
+
+    let mut key = map.lock_key("c".to_string(), &guard);
+    key.insert(3);
+    assert_eq!(key.remove(), Some(&3));
+    assert_eq!(map.get("c", &guard), None);
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn lock_key_excludes_other_writers_to_the_key() {
+    let map: Arc<ConcurrentHashMap<u64, u64>> = Arc::new(ConcurrentHashMap::new());
+    let writers: Vec<_> = (0..4)
+        .map(|_| {
+            let map = Arc::clone(&map);
+            thread::spawn(move || {
+                let guard = map.guard();
+                for _ in 0..2_000 {
+                    let mut key = map.lock_key(7, &guard);
+                    let value = key.get().copied().unwrap_or(0);
+                    key.insert(value + 1);
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    assert_eq!(map.get(&7, &map.guard()), Some(&8_000));
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+
+use common::Collide;
+
+#[derive(Debug, Clone)]
+struct Tagged {
+    id: u64,
+    tag: &'static str,
+}
+
+impl PartialEq for Tagged {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Tagged {}
+
+impl PartialOrd for Tagged {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Tagged {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl Hash for Tagged {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Borrow<u64> for Tagged {
+    fn borrow(&self) -> &u64 {
+        &self.id
+    }
+}
+
+#[test]
+fn get_key_value_returns_the_stored_key() {
+    let map: ConcurrentHashMap<Tagged, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(
+        Tagged {
+            id: 1,
+            tag: "stored",
+        },
+        10,
+        &guard,
+    );
+
+    let (key, value) = map.get_key_value(&1, &guard).unwrap();
+    assert_eq!(key.tag, "stored");
+    assert_eq!(*value, 10);
+    assert!(map.contains_key(&1, &guard));
+    assert!(!map.contains_key(&2, &guard));
+    assert_eq!(map.get_key_value(&2, &guard), None);
+}
+
+#[test]
+fn lookups_in_tree_bins() {
+    let map: ConcurrentHashMap<Tagged, u64, Collide> = Builder::new().build_with_hasher(Collide);
+    let guard = map.guard();
+    for id in 0..64 {
+        map.insert(Tagged { id, tag: "tree" }, id, &guard);
+    }
+    for id in 0..64 {
+        let (key, &value) = map.get_key_value(&id, &guard).unwrap();
+        assert_eq!((key.id, key.tag, value), (id, "tree", id));
+        assert!(map.contains_key(&id, &guard));
+    }
+    assert!(!map.contains_key(&64, &guard));
+}
@@ -0,0 +1,60 @@
+#![cfg(loom)]
+
+use concurrent_hash_table::{
+    map::builder::Builder,
+    util::{hashing::SeededState, sync::epoch::Collector},
+    ConcurrentHashMap,
+};
+use loom::{sync::Arc, thread};
+
+#[test]
+fn concurrent_inserts_are_both_visible() {
+    loom::model(|| {
+        let map: Arc<ConcurrentHashMap<u64, u64, SeededState>> = Arc::new(
+            Builder::new()
+                .collector(Collector::new())
+                .build_with_hasher(SeededState::with_seed(0)),
+        );
+        let handles: Vec<_> = (0..2)
+            .map(|key| {
+                let map = Arc::clone(&map);
+                thread::spawn(move || {
+                    let guard = map.guard();
+                    map.insert(key, key * 10, &guard);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let guard = map.guard();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&0, &guard), Some(&0));
+        assert_eq!(map.get(&1, &guard), Some(&10));
+    });
+}
+
+#[test]
+fn remove_races_with_insert() {
+    loom::model(|| {
+        let map: Arc<ConcurrentHashMap<u64, u64, SeededState>> = Arc::new(
+            Builder::new()
+                .collector(Collector::new())
+                .build_with_hasher(SeededState::with_seed(0)),
+        );
+        map.insert(1, 1, &map.guard());
+
+        let remover = {
+            let map = Arc::clone(&map);
+            thread::spawn(move || map.remove(&1, &map.guard()).copied())
+        };
+        map.insert(2, 2, &map.guard());
+
+        assert_eq!(remover.join().unwrap(), Some(1));
+        let guard = map.guard();
+        assert_eq!(map.get(&1, &guard), None);
+        assert_eq!(map.get(&2, &guard), Some(&2));
+        assert_eq!(map.len(), 1);
+    });
+}
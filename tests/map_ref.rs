@@ -0,0 +1,43 @@
+use std::thread;
+
+use concurrent_hash_table::{ConcurrentHashMap, HashMapRef};
+
+#[test]
+fn pin_runs_operations_without_guard_arguments() {
+    let map: ConcurrentHashMap<String, u64> = ConcurrentHashMap::new();
+    let pinned: HashMapRef<'_, String, u64> = map.pin();
+    assert!(pinned.is_empty());
+    pinned.insert("a".to_string(), 1);
+    pinned.insert("b".to_string(), 2);
+    assert_eq!(pinned.get("a"), Some(&1));
+    assert_eq!(pinned.remove("a"), Some(&1));
+    assert_eq!(pinned.len(), 1);
+    assert_eq!(pinned.keys().collect::<Vec<_>>(), vec!["b"]);
+}
+
+#[test]
+fn pinned_refs_are_independent_per_thread() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    thread::scope(|s| {
+        for t in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                let pinned = map.pin();
+                for key in t * 100..(t + 1) * 100 {
+                    pinned.insert(key, key);
+                }
+            });
+        }
+    });
+    let pinned = map.pin();
+    assert_eq!(pinned.len(), 400);
+    assert!((0..400).all(|key| pinned.get(&key) == Some(&key)));
+}
+
+#[test]
+fn boxed_values_are_reachable_through_a_pinned_ref() {
+    let map: ConcurrentHashMap<u64, Box<str>> = ConcurrentHashMap::new();
+    let pinned = map.pin();
+    pinned.insert_boxed(1, "one");
+    assert_eq!(pinned.get_unsized(&1), Some("one"));
+}
@@ -0,0 +1,58 @@
+use std::thread;
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn meta_defaults_to_zero_and_can_be_set() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(map.get_meta(&1, &guard), None);
+    assert_eq!(map.set_meta(&1, 5, &guard), None);
+
+    map.insert(1, 10, &guard);
+    assert_eq!(map.get_meta(&1, &guard), Some(0));
+    assert_eq!(map.set_meta(&1, 5, &guard), Some(0));
+    assert_eq!(map.set_meta(&1, 6, &guard), Some(5));
+    assert_eq!(map.get_meta(&1, &guard), Some(6));
+
+    assert_eq!(
+        map.fetch_update_meta(&1, |meta| Some(meta | 1), &guard),
+        Some(Ok(6))
+    );
+    assert_eq!(map.fetch_update_meta(&1, |_| None, &guard), Some(Err(7)));
+    assert_eq!(map.fetch_update_meta(&2, |_| Some(1), &guard), None);
+    assert_eq!(map.get_meta(&1, &guard), Some(7));
+}
+
+#[test]
+fn concurrent_fetch_updates_are_not_lost() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    map.insert(1, 10, &map.guard());
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let guard = map.guard();
+                for _ in 0..1000 {
+                    map.fetch_update_meta(&1, |meta| Some(meta + 1), &guard);
+                }
+            });
+        }
+    });
+    assert_eq!(map.get_meta(&1, &map.guard()), Some(4000));
+}
+
+#[test]
+fn meta_survives_treeification() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..8 {
+        map.insert(key, key, &guard);
+        map.set_meta(&key, key + 100, &guard);
+    }
+    for key in 8..1024 {
+        map.insert(key, key, &guard);
+    }
+    for key in 0..8 {
+        assert_eq!(map.get_meta(&key, &guard), Some(key + 100));
+    }
+}
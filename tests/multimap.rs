@@ -0,0 +1,67 @@
+use std::thread;
+
+use concurrent_hash_table::HashMultiMap;
+
+#[test]
+fn values_accumulate_per_key() {
+    let map = HashMultiMap::new();
+    let guard = map.guard();
+    map.insert("a", 1, &guard);
+    map.insert("a", 2, &guard);
+    map.insert("a", 2, &guard);
+    map.insert("b", 3, &guard);
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.count("a", &guard), 3);
+    let mut values: Vec<_> = map.get_all("a", &guard).copied().collect();
+    values.sort_unstable();
+    assert_eq!(values, vec![1, 2, 2]);
+    assert_eq!(map.get_all("c", &guard).count(), 0);
+    assert!(map.contains_key("b", &guard));
+}
+
+#[test]
+fn removing_the_last_value_removes_the_key() {
+    let map = HashMultiMap::new();
+    let guard = map.guard();
+    map.insert(1, "x", &guard);
+    map.insert(1, "y", &guard);
+
+    assert!(map.remove(&1, &"x", &guard));
+    assert!(!map.remove(&1, &"x", &guard));
+    assert_eq!(map.count(&1, &guard), 1);
+    assert!(map.remove(&1, &"y", &guard));
+    assert!(!map.contains_key(&1, &guard));
+    assert!(map.is_empty());
+
+    map.insert(2, "z", &guard);
+    assert!(map.remove_all(&2, &guard));
+    assert!(!map.remove_all(&2, &guard));
+}
+
+#[test]
+fn concurrent_inserts_keep_every_value() {
+    let map = HashMultiMap::new();
+    thread::scope(|s| {
+        for t in 0..4u64 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for n in 0..1_000u64 {
+                    map.insert(n % 8, t * 1_000 + n, &guard);
+                }
+            });
+        }
+    });
+    let guard = map.guard();
+    assert_eq!(map.len(), 8);
+    assert_eq!(
+        (0..8).map(|key| map.count(&key, &guard)).sum::<usize>(),
+        4_000
+    );
+    let mut all: Vec<u64> = (0..8)
+        .flat_map(|key| map.get_all(&key, &guard).copied())
+        .collect();
+    all.sort_unstable();
+    assert_eq!(all, (0..4_000).collect::<Vec<_>>());
+}
@@ -0,0 +1,36 @@
+use concurrent_hash_table::{
+    map::ConcurrentHashMap,
+    util::normalized_key::{CaseInsensitive, Normalized, NormalizedKey},
+};
+
+type Header = NormalizedKey<String, CaseInsensitive>;
+
+#[test]
+fn lookups_ignore_case_without_allocating_a_key() {
+    let map: ConcurrentHashMap<Header, &str> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(
+        Header::new("Content-Type".to_string()),
+        "text/plain",
+        &guard,
+    );
+    map.insert(Header::new("Accept".to_string()), "*/*", &guard);
+
+    let key = Normalized::<CaseInsensitive>::new("content-TYPE");
+    assert_eq!(map.get(key, &guard), Some(&"text/plain"));
+    assert_eq!(map.get(Normalized::new("ACCEPT"), &guard), Some(&"*/*"));
+    assert_eq!(map.get(Normalized::new("Accept-Encoding"), &guard), None);
+}
+
+#[test]
+fn keys_differing_only_in_case_are_the_same_entry() {
+    let map: ConcurrentHashMap<Header, u32> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(Header::new("Host".to_string()), 1, &guard);
+    map.insert(Header::new("HOST".to_string()), 2, &guard);
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get(Normalized::new("host"), &guard), Some(&2));
+
+    assert!(Header::new("Straße".to_string()) == Header::new("STRAßE".to_string()));
+    assert!(Header::new("a".to_string()) < Header::new("B".to_string()));
+}
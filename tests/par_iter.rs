@@ -0,0 +1,104 @@
+#![cfg(feature = "rayon")]
+
+use std::thread;
+
+use concurrent_hash_table::ConcurrentHashMap;
+use rayon::iter::ParallelIterator;
+
+#[test]
+fn par_iter_visits_every_entry_once() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..10_000 {
+        map.insert(key, key * 2, &guard);
+    }
+
+    assert_eq!(map.par_iter(&guard).count(), 10_000);
+    assert_eq!(map.par_keys(&guard).sum::<u64>(), (0..10_000).sum::<u64>());
+    assert_eq!(
+        map.par_values(&guard).sum::<u64>(),
+        (0..10_000).map(|key| key * 2).sum::<u64>()
+    );
+    assert!(map.par_iter(&guard).all(|(key, value)| *value == key * 2));
+}
+
+#[test]
+fn par_iter_on_an_empty_map() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(map.par_iter(&guard).count(), 0);
+}
+
+#[test]
+fn iterators_do_not_skip_entries_during_a_resize() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..1_000 {
+        map.insert(key, key, &guard);
+    }
+    thread::scope(|s| {
+        let map = &map;
+        s.spawn(move || {
+            let guard = map.guard();
+            for key in 1_000..50_000 {
+                map.insert(key, key, &guard);
+            }
+        });
+        for _ in 0..20 {
+            let guard = map.guard();
+            let stable = map.par_keys(&guard).filter(|key| **key < 1_000).count();
+            assert_eq!(stable, 1_000);
+            assert_eq!(
+                map.iter(&guard).filter(|(key, _)| **key < 1_000).count(),
+                1_000
+            );
+        }
+    });
+}
+
+#[test]
+fn collect_and_extend_in_parallel() {
+    use rayon::iter::{IntoParallelIterator, ParallelExtend};
+
+    let map: ConcurrentHashMap<u64, u64> = (0..10_000u64)
+        .into_par_iter()
+        .map(|key| (key, key + 1))
+        .collect();
+    let guard = map.guard();
+    assert_eq!(map.len(), 10_000);
+    assert!((0..10_000).all(|key| map.get(&key, &guard) == Some(&(key + 1))));
+
+    let mut shared = &map;
+    shared.par_extend((5_000..15_000u64).into_par_iter().map(|key| (key, 0)));
+    assert_eq!(map.len(), 15_000);
+    assert_eq!(map.get(&4_999, &guard), Some(&5_000));
+    assert_eq!(map.get(&14_999, &guard), Some(&0));
+}
+
+#[test]
+fn par_retain_and_par_for_each() {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..10_000 {
+        map.insert(key, key, &guard);
+    }
+
+    map.par_retain(|key, _| key % 3 == 0, &guard);
+    assert_eq!(map.len(), 3_334);
+    assert!((0..10_000).all(|key| map.get(&key, &guard).is_some() == (key % 3 == 0)));
+
+    let sum = AtomicU64::new(0);
+    map.par_for_each(
+        |_, value| {
+            sum.fetch_add(*value, Ordering::Relaxed);
+        },
+        &guard,
+    );
+    assert_eq!(
+        sum.into_inner(),
+        (0..10_000).filter(|key| key % 3 == 0).sum::<u64>()
+    );
+    assert_eq!(map.len(), 3_334);
+}
@@ -0,0 +1,42 @@
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn pin_and_unpin_entries() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(1, 10, &guard);
+    assert!(!map.is_pinned(&1, &guard));
+
+    assert!(map.pin_entry(&1, &guard));
+    assert!(map.is_pinned(&1, &guard));
+    assert!(map.pin_entry(&1, &guard));
+    assert!(map.is_pinned(&1, &guard));
+
+    assert!(map.unpin_entry(&1, &guard));
+    assert!(!map.is_pinned(&1, &guard));
+    assert!(map.unpin_entry(&1, &guard));
+
+    assert!(!map.pin_entry(&2, &guard));
+    assert!(!map.unpin_entry(&2, &guard));
+    assert!(!map.is_pinned(&2, &guard));
+}
+
+#[test]
+fn pins_survive_treeification() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..8 {
+        map.insert(key, key, &guard);
+    }
+    for key in (0..8).step_by(2) {
+        assert!(map.pin_entry(&key, &guard));
+    }
+
+    for key in 8..1024 {
+        map.insert(key, key, &guard);
+    }
+    for key in 0..8 {
+        assert_eq!(map.is_pinned(&key, &guard), key % 2 == 0);
+    }
+    assert!(!map.is_pinned(&9, &guard));
+}
@@ -0,0 +1,46 @@
+use concurrent_hash_table::{map::pinned::Pinned, ConcurrentHashMap};
+
+#[test]
+fn with_exposes_the_map_without_guard_arguments() {
+    let map: ConcurrentHashMap<u64, String> = ConcurrentHashMap::new();
+    let len = map.with(|pinned| {
+        assert!(pinned.is_empty());
+        for key in 0..100 {
+            pinned.insert(key, key.to_string());
+        }
+        assert_eq!(pinned.get(&42).map(String::as_str), Some("42"));
+        assert_eq!(pinned.remove(&42).map(String::as_str), Some("42"));
+        assert_eq!(pinned.get(&42), None);
+        pinned.len()
+    });
+    assert_eq!(len, 99);
+    assert_eq!(map.len(), 99);
+}
+
+#[test]
+fn with_flushed_returns_the_closure_result() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    map.with(|pinned| {
+        pinned.insert(1, 10);
+    });
+    let value = map.with_flushed(|pinned| {
+        pinned.update_cow(&1, |value| *value += 1);
+        pinned.get(&1).copied()
+    });
+    assert_eq!(value, Some(11));
+}
+
+#[test]
+fn pinned_can_own_its_guard() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let pinned = Pinned::new(&map, map.guard());
+    pinned.insert(7, 70);
+    assert!(pinned.pin_entry(&7));
+    assert!(pinned.is_pinned(&7));
+    assert_eq!(pinned.get(&7), Some(&70));
+    assert!(std::ptr::eq(pinned.map(), &map));
+    drop(pinned);
+
+    let guard = map.guard();
+    assert_eq!(map.get(&7, &guard), Some(&70));
+}
@@ -0,0 +1,52 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use concurrent_hash_table::{map::builder::Builder, util::pool::Pool, ConcurrentHashMap};
+
+struct Tracked(Arc<AtomicUsize>);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn recycled_slots_are_reused_and_their_values_dropped() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let pool = Pool::new(64);
+
+    let first = pool.alloc(Tracked(drops.clone())).into_box();
+    let raw = Box::into_raw(first);
+    unsafe { pool.recycle(raw) };
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+    assert_eq!(pool.len(), 1);
+
+    let second = pool.alloc(Tracked(drops.clone()));
+    assert!(pool.is_empty());
+    drop(second);
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn map_with_node_pool_survives_churn() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().node_pool(256).build();
+
+    for round in 0..8 {
+        let guard = map.guard();
+        for key in 0..512 {
+            map.insert(key, key + round, &guard);
+        }
+        for key in (0..512).step_by(2) {
+            assert_eq!(map.remove(&key, &guard), Some(&(key + round)));
+        }
+        for key in (1..512).step_by(2) {
+            assert_eq!(map.get(&key, &guard), Some(&(key + round)));
+        }
+        drop(guard);
+        map.flush();
+    }
+    assert_eq!(map.len(), 256);
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "prefetch")]
+
+use concurrent_hash_table::{util::prefetch::prefetch, ConcurrentHashMap};
+
+#[test]
+fn prefetching_null_and_dangling_pointers_is_harmless() {
+    prefetch(std::ptr::null::<u64>());
+    prefetch(std::ptr::NonNull::<u64>::dangling().as_ptr());
+}
+
+#[test]
+fn lookups_walk_long_chains() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::with_capacity(4096);
+    let guard = map.guard();
+    for key in 0..4096 {
+        map.insert(key, key * 3, &guard);
+    }
+    for key in 0..4096 {
+        assert_eq!(map.get(&key, &guard), Some(&(key * 3)));
+    }
+    assert_eq!(map.get(&4096, &guard), None);
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use concurrent_hash_table::{
+    map::raw_entry::RawEntryMut, util::hashing::KeyHasher, ConcurrentHashMap,
+};
+
+#[test]
+fn lookup_by_precomputed_hash() {
+    let map: ConcurrentHashMap<Arc<str>, usize> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert("alpha".into(), 1, &guard);
+    map.insert("beta".into(), 2, &guard);
+
+    let hash = map.hasher().hash_key("alpha");
+    let (key, value) = map
+        .raw_entry(&guard)
+        .from_hash(hash, |key| &**key == "alpha")
+        .unwrap();
+    assert_eq!((&**key, *value), ("alpha", 1));
+    assert_eq!(
+        map.raw_entry(&guard)
+            .from_key_hashed_nocheck(hash, "alpha")
+            .map(|(_, value)| *value),
+        Some(1)
+    );
+    assert!(map
+        .raw_entry(&guard)
+        .from_hash(hash, |key| &**key == "beta")
+        .is_none());
+    assert_eq!(
+        map.raw_entry(&guard)
+            .from_key("beta")
+            .map(|(_, value)| *value),
+        Some(2)
+    );
+}
+
+#[test]
+fn insert_with_provided_hash() {
+    let map: ConcurrentHashMap<Arc<str>, usize> = ConcurrentHashMap::new();
+    let pinned = map.pin();
+    for (i, string) in ["a", "b", "a", "c", "b"].iter().enumerate() {
+        let hash = map.hasher().hash_key(*string);
+        match pinned
+            .raw_entry_mut()
+            .from_hash(hash, |key| &**key == *string)
+        {
+            RawEntryMut::Occupied(entry) => assert!(*entry.get() < i),
+            RawEntryMut::Vacant(entry) => {
+                assert_eq!(*entry.insert(Arc::from(*string), i), i);
+            }
+        }
+    }
+    assert_eq!(pinned.len(), 3);
+    assert_eq!(pinned.get("a"), Some(&0));
+    assert_eq!(pinned.get("c"), Some(&3));
+
+    let hash = map.hasher().hash_key("d");
+    let value = pinned
+        .raw_entry_mut()
+        .from_key_hashed_nocheck(hash, "d")
+        .or_insert("d".into(), 4);
+    assert_eq!(*value, 4);
+    assert_eq!(pinned.raw_entry().from_key("d").map(|(_, v)| *v), Some(4));
+}
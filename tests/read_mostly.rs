@@ -0,0 +1,45 @@
+mod common;
+
+use std::sync::atomic::Ordering;
+
+use concurrent_hash_table::core::bin_entry::tree_bin::TreeBin;
+use crossbeam_epoch as epoch;
+
+use common::{tree_bin, value};
+
+#[test]
+fn read_mostly_lookup_matches_locked_lookup() {
+    let guard = epoch::pin();
+    let keys: Vec<u64> = (0..32).chain(Some(100)).collect();
+    let bin = tree_bin(&keys, &guard).into_shared(&guard);
+
+    for &key in &keys {
+        let hash = key % 4;
+        let read_mostly = TreeBin::find_read_mostly(bin, hash, &key, &guard);
+        assert_eq!(
+            value(read_mostly, &guard),
+            value(TreeBin::find(bin, hash, &key, &guard), &guard)
+        );
+        assert!(value(read_mostly, &guard).is_some());
+    }
+    assert!(TreeBin::find_read_mostly(bin, 1, &64, &guard).is_null());
+}
+
+#[test]
+fn read_mostly_lookup_scans_the_list_while_a_writer_is_active() {
+    let guard = epoch::pin();
+    let bin = tree_bin(&(0..32).collect::<Vec<_>>(), &guard).into_shared(&guard);
+    let tree = unsafe { bin.deref() }.as_tree_bin().unwrap();
+
+    tree.version.fetch_add(1, Ordering::SeqCst);
+    assert_eq!(
+        value(TreeBin::find_read_mostly(bin, 3, &7, &guard), &guard),
+        Some(70)
+    );
+    assert!(TreeBin::find_read_mostly(bin, 3, &8, &guard).is_null());
+    tree.version.fetch_add(1, Ordering::SeqCst);
+    assert_eq!(
+        value(TreeBin::find_read_mostly(bin, 3, &7, &guard), &guard),
+        Some(70)
+    );
+}
@@ -0,0 +1,22 @@
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn pending_reclamation_is_counted_per_map() {
+    let busy: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let idle: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+
+    let guard = busy.guard();
+    for key in 0..64 {
+        busy.insert(key, key, &guard);
+    }
+    for key in 0..64 {
+        busy.insert(key, key + 1, &guard);
+    }
+    assert!(busy.pending_reclamation() >= 64);
+    assert_eq!(idle.pending_reclamation(), 0);
+    assert_eq!(idle.flush(), 0);
+
+    drop(guard);
+    busy.flush();
+    assert_eq!(idle.pending_reclamation(), 0);
+}
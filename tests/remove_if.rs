@@ -0,0 +1,45 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn removes_only_when_the_predicate_holds() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert!(!map.remove_if(&1, |_| true, &guard));
+
+    map.insert(1, 10, &guard);
+    assert!(!map.remove_if(&1, |&value| value == 11, &guard));
+    assert_eq!(map.get(&1, &guard), Some(&10));
+    assert!(map.remove_if(&1, |&value| value == 10, &guard));
+    assert_eq!(map.get(&1, &guard), None);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn only_one_racing_remove_if_succeeds() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..16 {
+        map.insert(key, key, &guard);
+    }
+
+    let removed = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let guard = map.guard();
+                for key in 0..16 {
+                    if map.remove_if(&key, |&value| value == key, &guard) {
+                        removed.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+            });
+        }
+    });
+    assert_eq!(removed.load(Ordering::SeqCst), 16);
+    assert!(map.is_empty());
+}
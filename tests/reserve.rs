@@ -0,0 +1,50 @@
+use std::thread;
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn reserve_then_fill() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.reserve(10_000, &guard);
+    for key in 0..10_000 {
+        map.insert(key, key * 2, &guard);
+    }
+    assert_eq!(map.len(), 10_000);
+    assert!((0..10_000).all(|key| map.get(&key, &guard) == Some(&(key * 2))));
+}
+
+#[test]
+fn reserve_races_with_writers() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    thread::scope(|s| {
+        for t in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in (t * 5_000)..((t + 1) * 5_000) {
+                    if key % 1_000 == 0 {
+                        map.reserve(20_000, &guard);
+                    }
+                    map.insert(key, key, &guard);
+                }
+            });
+        }
+    });
+    let guard = map.guard();
+    assert_eq!(map.len(), 20_000);
+    assert!((0..20_000).all(|key| map.get(&key, &guard) == Some(&key)));
+}
+
+#[test]
+fn reserve_on_a_populated_map_keeps_entries() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..100 {
+        map.insert(key, key, &guard);
+    }
+    map.reserve(0, &guard);
+    map.reserve(4_000, &guard);
+    assert_eq!(map.len(), 100);
+    assert!((0..100).all(|key| map.get(&key, &guard) == Some(&key)));
+}
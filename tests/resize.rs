@@ -0,0 +1,72 @@
+use std::thread;
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn inserts_grow_the_table_and_keep_every_entry() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..50_000 {
+        map.insert(key, key, &guard);
+    }
+    assert_eq!(map.len(), 50_000);
+    assert!((0..50_000).all(|key| map.get(&key, &guard) == Some(&key)));
+    assert_eq!(map.iter(&guard).count(), 50_000);
+}
+
+#[test]
+fn concurrent_writers_and_readers_follow_forwarding_bins() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    thread::scope(|s| {
+        for t in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in (t * 20_000)..((t + 1) * 20_000) {
+                    map.insert(key, key + 1, &guard);
+                    assert_eq!(map.get(&key, &guard), Some(&(key + 1)));
+                }
+            });
+        }
+        s.spawn(|| {
+            let guard = map.guard();
+            for _ in 0..20 {
+                for (key, value) in map.iter(&guard) {
+                    assert_eq!(*value, key + 1);
+                }
+            }
+        });
+    });
+
+    let guard = map.guard();
+    assert_eq!(map.len(), 80_000);
+    assert!((0..80_000).all(|key| map.get(&key, &guard) == Some(&(key + 1))));
+}
+
+#[test]
+fn removes_and_clears_run_during_resizes() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    thread::scope(|s| {
+        s.spawn(|| {
+            let guard = map.guard();
+            for key in 0..40_000 {
+                map.insert(key, key, &guard);
+            }
+        });
+        s.spawn(|| {
+            let guard = map.guard();
+            for key in 0..40_000 {
+                map.remove(&key, &guard);
+            }
+        });
+    });
+
+    let guard = map.guard();
+    let present = (0..40_000)
+        .filter(|key| map.get(key, &guard).is_some())
+        .count();
+    assert_eq!(map.len(), present);
+    map.clear(&guard);
+    assert!(map.is_empty());
+    assert_eq!(map.iter(&guard).count(), 0);
+}
@@ -0,0 +1,65 @@
+use std::{sync::Arc, thread, time::Duration};
+
+use concurrent_hash_table::{map::builder::Builder, util::hashing::KeyHasher, ConcurrentHashMap};
+
+#[derive(Clone, Default)]
+struct Identity;
+
+impl KeyHasher<u64> for Identity {
+    fn hash_key(&self, key: &u64) -> u64 {
+        *key
+    }
+}
+
+#[test]
+fn retain_keeps_only_matching_entries() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..100 {
+        map.insert(key, key * 10, &guard);
+    }
+
+    map.retain(|key, value| key % 3 == 0 && *value == key * 10, &guard);
+    assert_eq!(map.len(), 34);
+    for key in 0..100 {
+        assert_eq!(map.get(&key, &guard).is_some(), key % 3 == 0);
+    }
+}
+
+#[test]
+fn retain_filters_tree_bins() {
+    let map: ConcurrentHashMap<u64, u64, Identity> = Builder::new().build_with_hasher(Identity);
+    let guard = map.guard();
+    for key in 0..64 {
+        map.insert(key * 16, key, &guard);
+    }
+
+    map.retain(|_, value| value % 2 == 1, &guard);
+    assert_eq!(map.len(), 32);
+    for key in 0..64 {
+        assert_eq!(map.get(&(key * 16), &guard).is_some(), key % 2 == 1);
+    }
+    map.insert(0, 0, &guard);
+    assert_eq!(map.get(&0, &guard), Some(&0));
+}
+
+#[test]
+fn retain_waits_for_lease() {
+    let map: Arc<ConcurrentHashMap<u64, u64>> = Arc::new(ConcurrentHashMap::new());
+    let guard = map.guard();
+    for key in 0..16 {
+        map.insert(key, key, &guard);
+    }
+    let lease = map.lease(&7, Duration::from_secs(60), &guard).unwrap();
+
+    let retainer = {
+        let map = Arc::clone(&map);
+        thread::spawn(move || map.retain(|_, _| false, &map.guard()))
+    };
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(map.get(&7, &guard), Some(&7));
+
+    assert!(map.release_lease(&7, lease, &guard));
+    retainer.join().unwrap();
+    assert!(map.is_empty());
+}
@@ -0,0 +1,37 @@
+use std::hash::BuildHasher;
+
+use concurrent_hash_table::{map::builder::Builder, util::hashing::SeededState, ConcurrentHashMap};
+
+#[test]
+fn with_seed_hashes_reproducibly() {
+    let a: ConcurrentHashMap<u64, u64, SeededState> = ConcurrentHashMap::with_seed(7);
+    let b: ConcurrentHashMap<u64, u64, SeededState> = ConcurrentHashMap::with_seed(7);
+    assert_eq!(a.hasher(), b.hasher());
+    assert_eq!(a.hasher().hash_one(42_u64), b.hasher().hash_one(42_u64));
+    assert_ne!(
+        a.hasher().hash_one(42_u64),
+        SeededState::with_seed(8).hash_one(42_u64)
+    );
+
+    a.insert(1, 1, &a.guard());
+    assert_eq!(a.get(&1, &a.guard()), Some(&1));
+}
+
+#[test]
+fn fresh_states_are_seeded_per_instance() {
+    let first = SeededState::new();
+    let second = SeededState::new();
+    assert_ne!(first, second);
+    assert_ne!(first.hash_one(42_u64), second.hash_one(42_u64));
+}
+
+#[test]
+fn default_maps_are_seeded_per_instance() {
+    let first: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let second: ConcurrentHashMap<u64, u64> = Builder::new().build();
+    assert_ne!(first.hasher(), second.hasher());
+    assert_ne!(
+        first.hasher().hash_one(42_u64),
+        second.hasher().hash_one(42_u64)
+    );
+}
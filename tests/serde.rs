@@ -0,0 +1,53 @@
+#![cfg(feature = "serde")]
+
+use concurrent_hash_table::{ConcurrentHashMap, HashSet};
+use serde_test::{assert_de_tokens, assert_ser_tokens, Token};
+
+#[test]
+fn map_serializes_as_a_map() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    assert_ser_tokens(&map, &[Token::Map { len: Some(0) }, Token::MapEnd]);
+
+    map.insert(1, 10, &map.guard());
+    assert_ser_tokens(
+        &map,
+        &[
+            Token::Map { len: Some(1) },
+            Token::U64(1),
+            Token::U64(10),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn map_deserializes_from_a_map() {
+    let mut tokens = vec![Token::Map { len: Some(100) }];
+    for key in 0..100u64 {
+        tokens.push(Token::U64(key));
+        tokens.push(Token::U64(key * 2));
+    }
+    tokens.push(Token::MapEnd);
+
+    let map: ConcurrentHashMap<u64, u64> = (0..100).map(|key| (key, key * 2)).collect();
+    assert_de_tokens(&map, &tokens);
+}
+
+#[test]
+fn set_round_trips_through_a_sequence() {
+    let set: HashSet<u64> = HashSet::new();
+    set.insert(7, &set.guard());
+    assert_ser_tokens(
+        &set,
+        &[Token::Seq { len: Some(1) }, Token::U64(7), Token::SeqEnd],
+    );
+    assert_de_tokens(
+        &set,
+        &[
+            Token::Seq { len: None },
+            Token::U64(7),
+            Token::U64(7),
+            Token::SeqEnd,
+        ],
+    );
+}
@@ -0,0 +1,89 @@
+use std::{collections::BTreeSet, thread};
+
+use concurrent_hash_table::HashSet;
+
+fn sorted<'a>(values: impl Iterator<Item = &'a u64>) -> Vec<u64> {
+    values
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn insert_contains_remove() {
+    let set = HashSet::new();
+    let guard = set.guard();
+    assert!(set.insert(1, &guard));
+    assert!(!set.insert(1, &guard));
+    assert!(set.contains(&1, &guard));
+    assert_eq!(set.get(&1, &guard), Some(&1));
+    assert_eq!(set.len(), 1);
+    assert!(set.remove(&1, &guard));
+    assert!(!set.remove(&1, &guard));
+    assert!(set.is_empty());
+}
+
+#[test]
+fn set_combinators() {
+    let a: HashSet<u64> = (0..6).collect();
+    let b: HashSet<u64> = (4..10).collect();
+    let guard = a.guard();
+
+    assert_eq!(sorted(a.difference(&b, &guard)), vec![0, 1, 2, 3]);
+    assert_eq!(sorted(a.intersection(&b, &guard)), vec![4, 5]);
+    assert_eq!(sorted(a.union(&b, &guard)), (0..10).collect::<Vec<_>>());
+    assert_eq!(a.union(&b, &guard).count(), 10);
+    assert_eq!(
+        sorted(a.symmetric_difference(&b, &guard)),
+        vec![0, 1, 2, 3, 6, 7, 8, 9]
+    );
+
+    let small: HashSet<u64> = (1..3).collect();
+    let far: HashSet<u64> = (20..30).collect();
+    assert!(small.is_subset(&a, &guard));
+    assert!(a.is_superset(&small, &guard));
+    assert!(!a.is_subset(&b, &guard));
+    assert!(a.is_disjoint(&far, &guard));
+    assert!(!a.is_disjoint(&b, &guard));
+}
+
+#[test]
+fn retain_clone_and_eq() {
+    let set: HashSet<u64> = (0..100).collect();
+    let guard = set.guard();
+    set.retain(|value| value % 10 == 0, &guard);
+    assert_eq!(
+        sorted(set.iter(&guard)),
+        (0..100).step_by(10).collect::<Vec<_>>()
+    );
+
+    let clone = set.clone();
+    assert!(clone == set);
+    clone.insert(1, &clone.guard());
+    assert!(clone != set);
+}
+
+#[test]
+fn concurrent_inserts_are_counted_once() {
+    let set = HashSet::new();
+    let inserted: usize = thread::scope(|s| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let set = &set;
+                s.spawn(move || {
+                    let guard = set.guard();
+                    (0..1_000)
+                        .filter(|&value| set.insert(value, &guard))
+                        .count()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .sum()
+    });
+    assert_eq!(inserted, 1_000);
+    assert_eq!(set.len(), 1_000);
+}
@@ -0,0 +1,72 @@
+use std::thread;
+
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+
+#[test]
+fn shrink_to_fit_keeps_the_remaining_entries() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..10_000 {
+        map.insert(key, key, &guard);
+    }
+    for key in 100..10_000 {
+        assert_eq!(map.remove(&key, &guard), Some(&key));
+    }
+    map.shrink_to_fit(&guard);
+
+    assert_eq!(map.len(), 100);
+    assert_eq!(map.iter(&guard).count(), 100);
+    assert!((0..100).all(|key| map.get(&key, &guard) == Some(&key)));
+    assert_eq!(map.get(&100, &guard), None);
+
+    for key in 100..1_000 {
+        map.insert(key, key, &guard);
+    }
+    assert_eq!(map.iter(&guard).count(), 1_000);
+}
+
+#[test]
+fn auto_shrink_survives_concurrent_churn() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().auto_shrink(0.1).build();
+    thread::scope(|s| {
+        for t in 0..4 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                let keys = (t * 10_000)..((t + 1) * 10_000);
+                for key in keys.clone() {
+                    map.insert(key, key, &guard);
+                }
+                for key in keys.clone().filter(|key| key % 50 != 0) {
+                    assert_eq!(map.remove(&key, &guard), Some(&key));
+                }
+                for key in keys.filter(|key| key % 50 == 0) {
+                    assert_eq!(map.get(&key, &guard), Some(&key));
+                }
+            });
+        }
+    });
+
+    let guard = map.guard();
+    assert_eq!(map.len(), 800);
+    assert_eq!(map.iter(&guard).count(), 800);
+    assert!((0..40_000)
+        .filter(|key| key % 50 == 0)
+        .all(|key| map.get(&key, &guard) == Some(&key)));
+}
+
+#[test]
+fn retain_and_clear_after_a_shrink() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new().auto_shrink(0.25).build();
+    let guard = map.guard();
+    for key in 0..4_096 {
+        map.insert(key, key, &guard);
+    }
+    map.retain(|key, _| key % 64 == 0, &guard);
+    assert_eq!(map.len(), 64);
+    assert!((0..4_096).all(|key| map.get(&key, &guard).is_some() == (key % 64 == 0)));
+
+    map.clear(&guard);
+    assert!(map.is_empty());
+    assert_eq!(map.iter(&guard).count(), 0);
+}
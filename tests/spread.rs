@@ -0,0 +1,38 @@
+use std::collections::HashSet;
+
+use concurrent_hash_table::{
+    map::builder::Builder,
+    util::hashing::{self, KeyHasher},
+    ConcurrentHashMap,
+};
+
+#[derive(Clone, Copy)]
+struct HighBits;
+
+impl KeyHasher<u64> for HighBits {
+    fn hash_key(&self, key: &u64) -> u64 {
+        key << 48
+    }
+}
+
+#[test]
+fn spread_mixes_high_bits_into_the_bin_index() {
+    let bins: HashSet<u64> = (0..16)
+        .map(|key| hashing::spread(HighBits.hash_key(&key)) & 15)
+        .collect();
+    assert_eq!(bins.len(), 16);
+    assert_eq!(hashing::spread(0), 0);
+}
+
+#[test]
+fn map_stores_spread_hashes() {
+    let map: ConcurrentHashMap<u64, u64, HighBits> = Builder::new().build_with_hasher(HighBits);
+    let guard = map.guard();
+    for key in 0..64 {
+        map.insert(key, key, &guard);
+    }
+    assert!((0..64).all(|key| map.get(&key, &guard) == Some(&key)));
+    for (hash, key, _) in map.hashed_iter(&guard) {
+        assert_eq!(hash, hashing::spread(key << 48));
+    }
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+
+use common::Constant;
+
+#[test]
+fn empty_map_reports_its_initial_capacity() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::with_capacity(100);
+    let guard = map.guard();
+    let stats = map.stats(&guard);
+    assert_eq!(stats.capacity, 256);
+    assert_eq!(stats.len, 0);
+    assert_eq!(stats.tree_bins, 0);
+    assert_eq!(stats.moved_bins, 0);
+}
+
+#[test]
+fn histogram_covers_every_bin_and_entry() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..1000 {
+        map.insert(key, key, &guard);
+    }
+    let stats = map.stats(&guard);
+    assert_eq!(stats.len, 1000);
+    assert_eq!(stats.chain_lengths.iter().sum::<usize>(), stats.capacity);
+    let entries: usize = stats
+        .chain_lengths
+        .iter()
+        .enumerate()
+        .map(|(length, bins)| length * bins)
+        .sum();
+    assert_eq!(entries, 1000);
+}
+
+#[test]
+fn colliding_keys_show_up_as_a_tree_bin() {
+    let map: ConcurrentHashMap<u64, u64, Constant> = Builder::new()
+        .min_treeify_capacity(0)
+        .build_with_hasher(Constant);
+    let guard = map.guard();
+    for key in 0..64 {
+        map.insert(key, key, &guard);
+    }
+    let stats = map.pin().stats();
+    assert_eq!(stats.tree_bins, 1);
+    assert_eq!(stats.chain_lengths.len(), 65);
+    assert_eq!(stats.chain_lengths[64], 1);
+    assert_eq!(stats.chain_lengths[0], stats.capacity - 1);
+}
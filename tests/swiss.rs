@@ -0,0 +1,125 @@
+use std::{convert::TryInto, sync::atomic::AtomicU64, thread};
+
+use concurrent_hash_table::{
+    core::group::{self, Group, DELETED, EMPTY},
+    util::hashing::KeyHasher,
+    SwissMap,
+};
+
+#[test]
+fn group_matches_control_bytes() {
+    let mut bytes = [EMPTY; 16];
+    bytes[1] = 0x11;
+    bytes[9] = 0x11;
+    bytes[4] = DELETED;
+    bytes[15] = 0x7f;
+    let words = [
+        AtomicU64::new(u64::from_le_bytes(bytes[..8].try_into().unwrap())),
+        AtomicU64::new(u64::from_le_bytes(bytes[8..].try_into().unwrap())),
+    ];
+    let group = Group::load(&words);
+
+    assert_eq!(group.match_tag(0x11).collect::<Vec<_>>(), [1, 9]);
+    assert_eq!(group.match_tag(0x7f).collect::<Vec<_>>(), [15]);
+    assert!(!group.match_tag(0x12).any());
+    assert_eq!(group.match_empty().count(), 12);
+    assert_eq!(group.match_free().count(), 13);
+
+    assert_eq!(group::claim_byte(&words, 4, 0x22), Some(DELETED));
+    assert_eq!(group::claim_byte(&words, 4, 0x33), None);
+    assert_eq!(group::set_byte(&words, 4, DELETED), 0x22);
+}
+
+#[test]
+fn insert_get_remove() {
+    let map = SwissMap::new();
+    let guard = map.guard();
+    for key in 0..1000u64 {
+        assert_eq!(map.insert(key, key * 2, &guard), None);
+    }
+    assert_eq!(map.len(), 1000);
+    assert!(map.capacity(&guard) >= 1000);
+    assert_eq!(map.insert(7, 70, &guard), Some(&14));
+    assert_eq!(map.get(&7, &guard), Some(&70));
+
+    for key in (0..1000u64).step_by(2) {
+        assert!(map.remove(&key, &guard).is_some());
+    }
+    assert_eq!(map.len(), 500);
+    assert_eq!(map.get(&2, &guard), None);
+    assert_eq!(map.get(&3, &guard), Some(&6));
+    assert_eq!(map.iter(&guard).count(), 500);
+}
+
+#[test]
+fn churn_reuses_tombstones() {
+    let map = SwissMap::with_capacity(64);
+    let guard = map.guard();
+    for key in 0..100_000u64 {
+        map.insert(key, key, &guard);
+        assert_eq!(map.remove(&key, &guard), Some(&key));
+    }
+    assert!(map.is_empty());
+    assert!(map.capacity(&guard) <= 128);
+}
+
+#[test]
+fn concurrent_inserts_and_removes() {
+    let map = SwissMap::new();
+    thread::scope(|s| {
+        for t in 0..8u64 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in t * 10_000..(t + 1) * 10_000 {
+                    map.insert(key, key, &guard);
+                }
+                for key in (t * 10_000..(t + 1) * 10_000).filter(|key| key % 3 == 0) {
+                    assert_eq!(map.remove(&key, &guard), Some(&key));
+                }
+            });
+        }
+    });
+
+    let guard = map.guard();
+    assert_eq!(map.len(), (0..80_000).filter(|key| key % 3 != 0).count());
+    for key in 0..80_000u64 {
+        assert_eq!(map.contains_key(&key, &guard), key % 3 != 0);
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Identity;
+
+impl KeyHasher<u64> for Identity {
+    fn hash_key(&self, key: &u64) -> u64 {
+        *key
+    }
+}
+
+#[test]
+fn removes_do_not_clobber_inserts_from_other_home_groups() {
+    let map = SwissMap::with_capacity_and_hasher(16, Identity);
+    thread::scope(|s| {
+        for t in 0..4u64 {
+            let map = &map;
+            s.spawn(move || {
+                let guard = map.guard();
+                for round in 0..50_000u64 {
+                    let key = (round * 4 + t) % 60_000;
+                    map.insert(key, key, &guard);
+                    if round < 49_990 {
+                        assert_eq!(map.remove(&key, &guard), Some(&key));
+                    }
+                }
+            });
+        }
+    });
+
+    let guard = map.guard();
+    assert_eq!(map.len(), 40);
+    assert_eq!(map.iter(&guard).count(), 40);
+    for (key, value) in map.iter(&guard) {
+        assert_eq!(map.get(key, &guard), Some(value));
+    }
+}
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use concurrent_hash_table::util::timing_wheel::TimingWheel;
+
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.0 >> 33
+    }
+}
+
+#[test]
+fn items_expire_once_their_deadline_passes() {
+    let mut wheel = TimingWheel::new(Duration::from_nanos(10), 0);
+    wheel.schedule("b", 25);
+    wheel.schedule("a", 10);
+    wheel.schedule("c", 1000);
+    assert_eq!(wheel.len(), 3);
+
+    assert!(wheel.advance(9).is_empty());
+    assert_eq!(wheel.advance(10), vec!["a"]);
+    assert!(wheel.advance(29).is_empty());
+    assert_eq!(wheel.advance(30), vec!["b"]);
+    assert_eq!(wheel.len(), 1);
+    assert_eq!(wheel.advance(5000), vec!["c"]);
+    assert!(wheel.is_empty());
+}
+
+#[test]
+fn past_deadlines_expire_on_the_next_advance() {
+    let mut wheel = TimingWheel::new(Duration::from_nanos(1), 100);
+    wheel.schedule(1, 50);
+    wheel.schedule(2, 100);
+    assert_eq!(wheel.advance(100), vec![1, 2]);
+}
+
+#[test]
+fn random_deadlines_across_every_level_and_the_overflow() {
+    let mut rng = Lcg(7);
+    let mut wheel = TimingWheel::new(Duration::from_nanos(1), 0);
+    let mut deadlines = Vec::new();
+    for id in 0..5000usize {
+        let deadline = match id % 4 {
+            0 => rng.next() % 64,
+            1 => rng.next() % 100_000,
+            2 => rng.next() % (1 << 30),
+            _ => (rng.next() << 10) % (1 << 40),
+        };
+        deadlines.push(deadline);
+        wheel.schedule(id, deadline);
+    }
+
+    let mut now = 0;
+    let mut expired = vec![false; deadlines.len()];
+    while !wheel.is_empty() {
+        now += rng.next() % (1 << (rng.next() % 40));
+        for id in wheel.advance(now) {
+            assert!(deadlines[id] <= now, "{} expired early", id);
+            assert!(!expired[id]);
+            expired[id] = true;
+        }
+        for (id, &deadline) in deadlines.iter().enumerate() {
+            if deadline <= now {
+                assert!(
+                    expired[id],
+                    "{} (deadline {}) missed at {}",
+                    id, deadline, now
+                );
+            }
+        }
+    }
+    assert!(expired.iter().all(|&expired| expired));
+}
@@ -0,0 +1,104 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use concurrent_hash_table::{map::builder::Builder, util::clock, ConcurrentHashMap};
+
+#[test]
+fn get_and_touch_records_an_access_stamp() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(1, 10, &guard);
+    assert_eq!(map.last_touched(&1, &guard), Some(0));
+    assert_eq!(map.get(&1, &guard), Some(&10));
+    assert_eq!(map.last_touched(&1, &guard), Some(0));
+
+    assert_eq!(map.get_and_touch(&1, &guard), Some(&10));
+    let first = map.last_touched(&1, &guard).unwrap();
+    assert!(first > 0);
+    assert_eq!(map.get_and_touch(&1, &guard), Some(&10));
+    assert!(map.last_touched(&1, &guard).unwrap() >= first);
+
+    assert_eq!(map.get_and_touch(&2, &guard), None);
+    assert_eq!(map.last_touched(&2, &guard), None);
+}
+
+#[test]
+fn access_stamps_survive_treeification() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    for key in 0..8 {
+        map.insert(key, key, &guard);
+        map.get_and_touch(&key, &guard);
+    }
+    let stamps: Vec<_> = (0..8).map(|key| map.last_touched(&key, &guard)).collect();
+
+    for key in 8..1024 {
+        map.insert(key, key, &guard);
+    }
+    let after: Vec<_> = (0..8).map(|key| map.last_touched(&key, &guard)).collect();
+    assert_eq!(stamps, after);
+}
+
+#[test]
+fn access_and_expiry_stamps_survive_concurrent_resizes() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new()
+        .expire_after_access(Duration::from_secs(60))
+        .build();
+    {
+        let guard = map.guard();
+        for key in 0..8 {
+            map.insert(key, key, &guard);
+            map.set_meta(&key, key + 1, &guard);
+        }
+    }
+
+    let readers = AtomicUsize::new(4);
+    let stamps: Vec<Vec<u64>> = thread::scope(|s| {
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let (map, readers) = (&map, &readers);
+                s.spawn(move || {
+                    let guard = map.guard();
+                    let mut stamps = vec![0; 8];
+                    for _ in 0..5_000 {
+                        for key in 0..8u64 {
+                            stamps[key as usize] = clock::now();
+                            let value = if (key + t) % 2 == 0 {
+                                map.get(&key, &guard)
+                            } else {
+                                map.get_and_touch(&key, &guard)
+                            };
+                            assert_eq!(value, Some(&key));
+                        }
+                    }
+                    readers.fetch_sub(1, Ordering::SeqCst);
+                    stamps
+                })
+            })
+            .collect();
+        s.spawn(|| {
+            while readers.load(Ordering::SeqCst) > 0 {
+                let guard = map.guard();
+                map.reserve(256, &guard);
+                map.shrink_to_fit(&guard);
+            }
+        });
+        handles
+            .into_iter()
+            .map(|reader| reader.join().unwrap())
+            .collect()
+    });
+
+    let guard = map.guard();
+    for key in 0..8u64 {
+        let touched = map.last_touched(&key, &guard).unwrap();
+        for reader in &stamps {
+            assert!(touched >= reader[key as usize]);
+        }
+        assert_eq!(map.get_meta(&key, &guard), Some(key + 1));
+        assert_eq!(map.get(&key, &guard), Some(&key));
+    }
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+use tracing::{
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+use common::Constant;
+
+#[derive(Default)]
+struct Recorder {
+    next: AtomicU64,
+    names: Mutex<Vec<&'static str>>,
+}
+
+impl Recorder {
+    fn saw(&self, name: &'static str) -> bool {
+        self.names.lock().unwrap().contains(&name)
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.names.lock().unwrap().push(span.metadata().name());
+        Id::from_u64(self.next.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        self.names.lock().unwrap().push(event.metadata().name());
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn resizes_open_a_transfer_span() {
+    let recorder = Arc::new(Recorder::default());
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let map = ConcurrentHashMap::new();
+        let guard = map.guard();
+        for key in 0..1024 {
+            map.insert(key, key, &guard);
+        }
+    });
+    assert!(recorder.saw("transfer"));
+}
+
+#[test]
+fn colliding_keys_trace_long_scans_and_treeification() {
+    let recorder = Arc::new(Recorder::default());
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let map: ConcurrentHashMap<u64, u64, Constant> = Builder::new()
+            .treeify_threshold(32)
+            .untreeify_threshold(6)
+            .min_treeify_capacity(0)
+            .build_with_hasher(Constant);
+        let guard = map.guard();
+        for key in 0..64 {
+            map.insert(key, key, &guard);
+        }
+        for key in 0..32 {
+            map.get(&key, &guard);
+        }
+    });
+    assert!(recorder.saw("treeify_bin"));
+    assert!(recorder
+        .names
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|name| name.starts_with("event")));
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+
+use common::Constant;
+
+#[test]
+fn removing_from_a_tree_bin_keeps_it_valid() {
+    let map: ConcurrentHashMap<u64, u64, Constant> = Builder::new()
+        .min_treeify_capacity(0)
+        .build_with_hasher(Constant);
+    let guard = map.guard();
+    for key in 0..256 {
+        map.insert(key, key, &guard);
+    }
+
+    let mut removed = Vec::new();
+    for key in (0..256).step_by(2).chain((0..128).map(|k| 255 - 2 * k)) {
+        assert_eq!(map.remove(&key, &guard), Some(&key));
+        removed.push(key);
+        map.validate(&guard);
+        assert_eq!(map.get(&key, &guard), None);
+        assert!((0..256)
+            .filter(|key| !removed.contains(key))
+            .all(|key| map.get(&key, &guard) == Some(&key)));
+    }
+    assert!(map.is_empty());
+}
+
+#[test]
+fn retaining_in_a_tree_bin_keeps_it_valid() {
+    let map: ConcurrentHashMap<u64, u64, Constant> = Builder::new()
+        .min_treeify_capacity(0)
+        .build_with_hasher(Constant);
+    let guard = map.guard();
+    for key in 0..256 {
+        map.insert(key, key, &guard);
+    }
+
+    for divisor in [2, 3, 5, 7].iter() {
+        map.retain(|key, _| key % divisor != 0, &guard);
+        map.validate(&guard);
+    }
+    for key in 0..256 {
+        let kept = [2, 3, 5, 7].iter().all(|divisor| key % divisor != 0);
+        assert_eq!(map.get(&key, &guard).is_some(), kept);
+    }
+}
+
+#[test]
+fn shrinking_a_tree_bin_untreeifies_and_regrows_it() {
+    let map: ConcurrentHashMap<u64, u64, Constant> = Builder::new()
+        .min_treeify_capacity(0)
+        .build_with_hasher(Constant);
+    let guard = map.guard();
+    for round in 0..3 {
+        for key in 0..32 {
+            map.insert(key, key + round, &guard);
+        }
+        for key in 2..32 {
+            assert_eq!(map.remove(&key, &guard), Some(&(key + round)));
+        }
+        assert_eq!(map.len(), 2);
+        map.validate(&guard);
+        assert_eq!(map.get(&0, &guard), Some(&round));
+        assert_eq!(map.get(&1, &guard), Some(&(1 + round)));
+        assert_eq!(map.get(&2, &guard), None);
+    }
+}
+
+#[test]
+fn long_chains_built_through_insert_and_entry_stay_reachable() {
+    let map: ConcurrentHashMap<u64, u64, Constant> = Builder::new()
+        .min_treeify_capacity(0)
+        .build_with_hasher(Constant);
+    let guard = map.guard();
+    for key in 0..64 {
+        if key % 2 == 0 {
+            map.insert(key, key * 10, &guard);
+        } else {
+            assert_eq!(*map.entry(key, &guard).or_insert(key * 10), key * 10);
+        }
+    }
+    assert_eq!(map.len(), 64);
+    map.validate(&guard);
+    assert!((0..64).all(|key| map.get(&key, &guard) == Some(&(key * 10))));
+    assert_eq!(*map.entry(7, &guard).or_insert(0), 70);
+}
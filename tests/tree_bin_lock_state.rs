@@ -0,0 +1,53 @@
+mod common;
+
+use std::{sync::atomic::Ordering, thread};
+
+use concurrent_hash_table::{
+    core::bin_entry::{tree_bin::TreeBin, BinEntry},
+    util::state::State,
+};
+use crossbeam_epoch::{self as epoch, Shared};
+
+use common::{tree_bin, value};
+
+#[test]
+fn saturated_reader_count_falls_back_to_the_list() {
+    let guard = epoch::pin();
+    let bin = tree_bin(&(0..32).collect::<Vec<_>>(), &guard).into_shared(&guard);
+    let tree = unsafe { bin.deref() }.as_tree_bin().unwrap();
+
+    let saturated = usize::from(u16::MAX) * State::Reader as usize;
+    tree.lock_state.store(saturated, Ordering::SeqCst);
+    assert_eq!(value(TreeBin::find(bin, 3, &7, &guard), &guard), Some(70));
+    assert!(TreeBin::find(bin, 3, &8, &guard).is_null());
+    assert_eq!(tree.lock_state.load(Ordering::SeqCst), saturated);
+
+    tree.lock_state
+        .store(State::None as usize, Ordering::SeqCst);
+    assert_eq!(value(TreeBin::find(bin, 1, &5, &guard), &guard), Some(50));
+    assert_eq!(tree.lock_state.load(Ordering::SeqCst), State::None as usize);
+}
+
+#[test]
+fn concurrent_readers_leave_the_lock_state_clear() {
+    let guard = epoch::pin();
+    let bin = tree_bin(&(0..64).collect::<Vec<_>>(), &guard).into_shared(&guard);
+    let tree = unsafe { bin.deref() }.as_tree_bin().unwrap();
+    let raw = bin.as_raw() as usize;
+
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(move || {
+                let guard = epoch::pin();
+                let bin = Shared::from(raw as *const BinEntry<u64, u64>);
+                for key in 0..64 {
+                    assert_eq!(
+                        value(TreeBin::find(bin, key % 4, &key, &guard), &guard),
+                        Some(key * 10)
+                    );
+                }
+            });
+        }
+    });
+    assert_eq!(tree.lock_state.load(Ordering::SeqCst), State::None as usize);
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use concurrent_hash_table::{
+    map::{builder::Builder, ConcurrentHashMap},
+    util::treeify_policy::TreeifyPolicy,
+};
+
+use common::Constant;
+
+fn fill(map: &ConcurrentHashMap<u64, u64>) {
+    let guard = map.guard();
+    for key in 0..2048 {
+        map.insert(key, key * 2, &guard);
+    }
+    assert_eq!(map.len(), 2048);
+    for key in 0..2048 {
+        assert_eq!(map.get(&key, &guard), Some(&(key * 2)));
+    }
+    assert_eq!(map.get(&2048, &guard), None);
+}
+
+#[test]
+fn fixed_policy_keeps_every_entry_reachable() {
+    fill(&Builder::new().treeify_policy(TreeifyPolicy::Fixed).build());
+}
+
+#[test]
+fn probe_length_policy_keeps_every_entry_reachable() {
+    fill(
+        &Builder::new()
+            .treeify_policy(TreeifyPolicy::ProbeLength(1))
+            .build(),
+    );
+}
+
+#[test]
+fn read_mostly_lookups_see_tree_bins() {
+    fill(
+        &Builder::new()
+            .read_mostly(true)
+            .treeify_policy(TreeifyPolicy::ProbeLength(1))
+            .build(),
+    );
+}
+
+#[test]
+fn remove_from_a_linked_bin() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(1, 10, &guard);
+    map.insert(2, 20, &guard);
+    assert_eq!(map.remove(&1, &guard), Some(&10));
+    assert_eq!(map.remove(&1, &guard), None);
+    assert_eq!(map.get(&2, &guard), Some(&20));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn adaptive_policy_keeps_every_entry_reachable() {
+    fill(
+        &Builder::new()
+            .treeify_policy(TreeifyPolicy::Adaptive)
+            .build(),
+    );
+}
+
+#[test]
+fn adaptive_policy_treeifies_equal_hashes_early() {
+    let capacity = |policy| {
+        let map: ConcurrentHashMap<u64, u64, Constant> = Builder::new()
+            .treeify_policy(policy)
+            .min_treeify_capacity(1024)
+            .build_with_hasher(Constant);
+        let guard = map.guard();
+        for key in 0..128 {
+            map.insert(key, key, &guard);
+        }
+        for key in 0..128 {
+            assert_eq!(map.get(&key, &guard), Some(&key));
+        }
+        let stats = map.stats(&guard);
+        assert_eq!(stats.tree_bins, 1);
+        stats.capacity
+    };
+    assert!(capacity(TreeifyPolicy::Adaptive) < capacity(TreeifyPolicy::Fixed));
+}
+
+#[test]
+fn adaptive_policy_leaves_well_spread_keys_alone() {
+    let map: ConcurrentHashMap<u64, u64> = Builder::new()
+        .treeify_policy(TreeifyPolicy::Adaptive)
+        .build();
+    let guard = map.guard();
+    for key in 0..4096 {
+        map.insert(key, key, &guard);
+    }
+    assert_eq!(map.stats(&guard).tree_bins, 0);
+}
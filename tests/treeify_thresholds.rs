@@ -0,0 +1,66 @@
+mod common;
+
+use concurrent_hash_table::{
+    map::builder::{BuildError, Builder},
+    ConcurrentHashMap,
+};
+
+use common::Collide;
+
+fn fill(map: &ConcurrentHashMap<u64, u64, Collide>) {
+    let guard = map.guard();
+    for key in 0..64 {
+        map.insert(key, key * 3, &guard);
+    }
+    assert_eq!(map.len(), 64);
+    assert!((0..64).all(|key| map.get(&key, &guard) == Some(&(key * 3))));
+    assert_eq!(map.get(&64, &guard), None);
+}
+
+#[test]
+fn low_thresholds_treeify_small_tables() {
+    fill(
+        &Builder::new()
+            .treeify_threshold(3)
+            .untreeify_threshold(1)
+            .min_treeify_capacity(0)
+            .build_with_hasher(Collide),
+    );
+}
+
+#[test]
+fn small_tables_resize_instead_of_treeifying() {
+    fill(
+        &Builder::new()
+            .treeify_threshold(2)
+            .untreeify_threshold(0)
+            .min_treeify_capacity(1 << 12)
+            .build_with_hasher(Collide),
+    );
+}
+
+#[test]
+fn inconsistent_thresholds_are_rejected() {
+    let error = Builder::<u64, u64>::new()
+        .treeify_threshold(4)
+        .untreeify_threshold(4)
+        .try_build()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        BuildError::InvalidTreeifyThresholds {
+            treeify: 4,
+            untreeify: 4,
+        }
+    );
+    assert!(Builder::<u64, u64>::new()
+        .treeify_threshold(1)
+        .untreeify_threshold(0)
+        .try_build()
+        .is_err());
+    assert!(Builder::<u64, u64>::new()
+        .treeify_threshold(4)
+        .untreeify_threshold(2)
+        .try_build()
+        .is_ok());
+}
@@ -0,0 +1,48 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn try_insert_hands_back_the_rejected_value() {
+    let map: ConcurrentHashMap<u64, String> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(map.try_insert(1, "one".to_string(), &guard).unwrap(), "one");
+
+    let error = map.try_insert(1, "uno".to_string(), &guard).unwrap_err();
+    assert_eq!(error.current, "one");
+    assert_eq!(error.value, "uno");
+    assert_eq!(map.get(&1, &guard).map(String::as_str), Some("one"));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn exactly_one_racing_try_insert_wins() {
+    let map: ConcurrentHashMap<u64, usize> = ConcurrentHashMap::new();
+    let wins = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for t in 0..8 {
+            let map = &map;
+            let wins = &wins;
+            s.spawn(move || {
+                let guard = map.guard();
+                for key in 0..256 {
+                    match map.try_insert(key, t, &guard) {
+                        Ok(&value) => {
+                            assert_eq!(value, t);
+                            wins.fetch_add(1, Ordering::SeqCst);
+                        }
+                        Err(error) => {
+                            assert_eq!(error.value, t);
+                            assert_ne!(*error.current, t);
+                        }
+                    }
+                }
+            });
+        }
+    });
+    assert_eq!(wins.load(Ordering::SeqCst), 256);
+    assert_eq!(map.len(), 256);
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use concurrent_hash_table::{
+    map::builder::Builder, util::hashing::KeyHasher, ConcurrentHashMap, ValuePool,
+};
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct Unordered(u32);
+
+#[derive(Clone, Copy)]
+struct Buckets(u64);
+
+impl KeyHasher<Unordered> for Buckets {
+    fn hash_key(&self, key: &Unordered) -> u64 {
+        u64::from(key.0) % self.0 * 16
+    }
+}
+
+#[test]
+fn keys_without_ord_fill_tree_bins() {
+    let map: ConcurrentHashMap<Unordered, u32, Buckets> =
+        Builder::new().build_with_hasher(Buckets(4));
+    let guard = map.guard();
+    for n in 0..200 {
+        map.insert(Unordered(n), n, &guard);
+    }
+    assert_eq!(map.len(), 200);
+    for n in 0..200 {
+        assert_eq!(map.get(&Unordered(n), &guard), Some(&n));
+    }
+    assert_eq!(map.get(&Unordered(200), &guard), None);
+
+    map.insert(Unordered(5), 50, &guard);
+    assert_eq!(map.get(&Unordered(5), &guard), Some(&50));
+    assert_eq!(map.len(), 200);
+}
+
+#[test]
+fn fully_colliding_keys_fall_back_to_identity_order() {
+    let map: ConcurrentHashMap<Unordered, u32, Buckets> =
+        Builder::new().build_with_hasher(Buckets(1));
+    let guard = map.guard();
+    for n in 0..64 {
+        map.insert(Unordered(n), n, &guard);
+    }
+    assert!((0..64).all(|n| map.get(&Unordered(n), &guard) == Some(&n)));
+}
+
+#[test]
+fn value_pool_interns_values_without_ord() {
+    let pool: ValuePool<Unordered> = ValuePool::new();
+    let guard = pool.guard();
+    let first = pool.intern(Unordered(1), &guard);
+    assert!(Arc::ptr_eq(&first, &pool.intern(Unordered(1), &guard)));
+    assert!(!Arc::ptr_eq(&first, &pool.intern(Unordered(2), &guard)));
+}
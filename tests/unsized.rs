@@ -0,0 +1,23 @@
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn str_values_round_trip_through_boxes() {
+    let map: ConcurrentHashMap<u32, Box<str>> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert_boxed(1, "one", &guard);
+    map.insert_boxed(2, String::from("two"), &guard);
+
+    let one: &str = map.get_unsized(&1, &guard).unwrap();
+    assert_eq!(one, "one");
+    assert_eq!(map.get_unsized(&2, &guard), Some("two"));
+    assert_eq!(map.get_unsized(&3, &guard), None);
+}
+
+#[test]
+fn slice_values_round_trip_through_boxes() {
+    let map: ConcurrentHashMap<&str, Box<[u8]>> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert_boxed("token", vec![1, 2, 3], &guard);
+    map.insert_boxed("token", &[4u8, 5][..], &guard);
+    assert_eq!(map.get_unsized("token", &guard), Some(&[4u8, 5][..]));
+}
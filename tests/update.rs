@@ -0,0 +1,41 @@
+use std::{sync::Arc, thread};
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn update_returns_old_and_new_values() {
+    let map: ConcurrentHashMap<u64, String> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(map.update(&1, |value| value.clone() + "!", &guard), None);
+
+    map.insert(1, "a".to_string(), &guard);
+    let (old, new) = map.update(&1, |value| value.clone() + "b", &guard).unwrap();
+    assert_eq!((old.as_str(), new.as_str()), ("a", "ab"));
+    assert_eq!(map.get(&1, &guard).map(String::as_str), Some("ab"));
+
+    let pinned = map.pin();
+    let (old, new) = pinned.update(&1, |value| value.len().to_string()).unwrap();
+    assert_eq!((old.as_str(), new.as_str()), ("ab", "2"));
+}
+
+#[test]
+fn concurrent_updates_lose_no_increments() {
+    let map: Arc<ConcurrentHashMap<u64, u64>> = Arc::new(ConcurrentHashMap::new());
+    map.insert(1, 0, &map.guard());
+
+    let writers: Vec<_> = (0..4)
+        .map(|_| {
+            let map = Arc::clone(&map);
+            thread::spawn(move || {
+                let guard = map.guard();
+                for _ in 0..5_000 {
+                    map.update(&1, |value| value + 1, &guard);
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    assert_eq!(map.get(&1, &map.guard()), Some(&20_000));
+}
@@ -0,0 +1,43 @@
+use std::{sync::Arc, thread};
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn update_cow_on_removed_key_returns_none() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    assert_eq!(map.update_cow(&1, |value| *value += 1, &guard), None);
+
+    map.insert(1, 1, &guard);
+    assert_eq!(map.update_cow(&1, |value| *value += 1, &guard), Some(&2));
+    map.remove(&1, &guard);
+    assert_eq!(map.update_cow(&1, |value| *value += 1, &guard), None);
+}
+
+#[test]
+fn concurrent_update_cow_loses_no_increments() {
+    let map: Arc<ConcurrentHashMap<u64, Vec<u64>>> = Arc::new(ConcurrentHashMap::new());
+    map.insert(1, Vec::new(), &map.guard());
+
+    let writers: Vec<_> = (0..4)
+        .map(|thread| {
+            let map = Arc::clone(&map);
+            thread::spawn(move || {
+                let guard = map.guard();
+                for i in 0..500 {
+                    map.update_cow(&1, |value| value.push(thread * 1000 + i), &guard);
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    let guard = map.guard();
+    let mut values = map.get(&1, &guard).unwrap().clone();
+    assert_eq!(values.len(), 2000);
+    values.sort_unstable();
+    values.dedup();
+    assert_eq!(values.len(), 2000);
+}
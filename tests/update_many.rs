@@ -0,0 +1,98 @@
+use std::{sync::Arc, thread, time::Duration};
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn update_many_applies_the_closure_to_every_key() {
+    let map: ConcurrentHashMap<u64, i64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(1, 100, &guard);
+    map.insert(2, 0, &guard);
+
+    assert!(map.update_many(
+        &[&1, &2],
+        |values| {
+            values[0] -= 30;
+            values[1] += 30;
+        },
+        &guard
+    ));
+    assert_eq!(map.get(&1, &guard), Some(&70));
+    assert_eq!(map.get(&2, &guard), Some(&30));
+    assert!(map.pin().update_many(&[&2], |values| values[0] = 0));
+    assert_eq!(map.get(&2, &guard), Some(&0));
+}
+
+#[test]
+fn update_many_rejects_missing_and_duplicate_keys_without_calling_the_closure() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+
+    assert!(!map.update_many(&[&1, &2], |_| unreachable!("missing key"), &guard));
+    assert!(!map.update_many(&[&1, &1], |_| unreachable!("duplicate key"), &guard));
+    assert_eq!(map.get(&1, &guard), Some(&1));
+
+    assert!(map.update_many(&[&1], |values| values[0] = 2, &guard));
+    assert_eq!(map.get(&1, &guard), Some(&2));
+}
+
+#[test]
+fn concurrent_transfers_preserve_the_total() {
+    let map: Arc<ConcurrentHashMap<u64, i64>> = Arc::new(ConcurrentHashMap::new());
+    for key in 0..8 {
+        map.insert(key, 1_000, &map.guard());
+    }
+
+    let workers: Vec<_> = (0..4u64)
+        .map(|thread| {
+            let map = Arc::clone(&map);
+            thread::spawn(move || {
+                let guard = map.guard();
+                for i in 0..2_000u64 {
+                    let from = (thread + i) % 8;
+                    let to = (thread * 3 + i * 5 + 1) % 8;
+                    if from == to {
+                        continue;
+                    }
+                    map.update_many(
+                        &[&from, &to],
+                        |values| {
+                            values[0] -= 1;
+                            values[1] += 1;
+                        },
+                        &guard,
+                    );
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let guard = map.guard();
+    let total: i64 = (0..8).map(|key| *map.get(&key, &guard).unwrap()).sum();
+    assert_eq!(total, 8_000);
+}
+
+#[test]
+fn update_many_waits_for_a_leased_key() {
+    let map: Arc<ConcurrentHashMap<u64, u64>> = Arc::new(ConcurrentHashMap::new());
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+    map.insert(2, 2, &guard);
+    let lease = map.lease(&2, Duration::from_secs(60), &guard).unwrap();
+
+    let writer = {
+        let map = Arc::clone(&map);
+        thread::spawn(move || map.update_many(&[&1, &2], |values| values.swap(0, 1), &map.guard()))
+    };
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(map.get(&1, &guard), Some(&1));
+
+    assert!(map.release_lease(&2, lease, &guard));
+    assert!(writer.join().unwrap());
+    assert_eq!(map.get(&1, &guard), Some(&2));
+    assert_eq!(map.get(&2, &guard), Some(&1));
+}
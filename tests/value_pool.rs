@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use concurrent_hash_table::{map::ConcurrentHashMap, ValuePool};
+
+#[test]
+fn equal_values_share_one_allocation() {
+    let pool: ValuePool<String> = ValuePool::new();
+    let guard = pool.guard();
+    let first = pool.intern("active".to_string(), &guard);
+    let second = pool.intern("active".to_string(), &guard);
+    assert!(Arc::ptr_eq(&first, &second));
+    assert!(!Arc::ptr_eq(
+        &first,
+        &pool.intern("idle".to_string(), &guard)
+    ));
+    assert_eq!(pool.len(), 2);
+}
+
+#[test]
+fn insert_dedup_stores_the_pooled_value() {
+    let pool: ValuePool<String> = ValuePool::new();
+    let map: ConcurrentHashMap<u32, Arc<String>> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    let status = pool.insert_dedup(&map, 1, "active".to_string(), &guard);
+    pool.insert_dedup(&map, 2, "active".to_string(), &guard);
+
+    assert!(Arc::ptr_eq(map.get(&1, &guard).unwrap(), &status));
+    assert!(Arc::ptr_eq(map.get(&2, &guard).unwrap(), &status));
+    assert_eq!(Arc::strong_count(&status), 4);
+}
+
+#[test]
+fn purge_drops_values_only_the_pool_holds() {
+    let pool: ValuePool<String> = ValuePool::new();
+    let guard = pool.guard();
+    let kept = pool.intern("kept".to_string(), &guard);
+    drop(pool.intern("dropped".to_string(), &guard));
+    assert_eq!(pool.len(), 2);
+
+    assert_eq!(pool.purge(&guard), 1);
+    assert_eq!(pool.len(), 1);
+    assert!(Arc::ptr_eq(&kept, &pool.intern("kept".to_string(), &guard)));
+}
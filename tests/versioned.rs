@@ -0,0 +1,91 @@
+use std::thread;
+
+use concurrent_hash_table::{
+    map::builder::Builder, util::version::VersionError, ConcurrentHashMap,
+};
+
+fn versioned() -> ConcurrentHashMap<u64, u64> {
+    Builder::new().versioned(true).build()
+}
+
+#[test]
+fn unversioned_map_rejects_versioned_operations() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::new();
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+
+    assert_eq!(map.get_versioned(&1, &guard), None);
+    assert_eq!(
+        map.insert_if_version(&1, 0, 2, &guard),
+        Err(VersionError::Unsupported(2))
+    );
+    assert_eq!(map.get(&1, &guard), Some(&1));
+}
+
+#[test]
+fn stale_generation_conflicts() {
+    let map = versioned();
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+
+    let (generation, _) = map.get_versioned(&1, &guard).unwrap();
+    let next = map.insert_if_version(&1, generation, 2, &guard).unwrap();
+    assert!(next > generation);
+    assert_eq!(
+        map.insert_if_version(&1, generation, 3, &guard),
+        Err(VersionError::Conflict(3))
+    );
+    assert_eq!(map.get_versioned(&1, &guard), Some((next, &2)));
+
+    map.insert(1, 4, &guard);
+    let (after_insert, _) = map.get_versioned(&1, &guard).unwrap();
+    assert!(after_insert > next);
+    assert_eq!(
+        map.insert_if_version(&1, next, 5, &guard)
+            .map_err(VersionError::into_value),
+        Err(5)
+    );
+    assert_eq!(
+        map.insert_if_version(&2, 0, 5, &guard),
+        Err(VersionError::Conflict(5))
+    );
+}
+
+#[test]
+fn reinserted_key_does_not_reuse_generations() {
+    let map = versioned();
+    let guard = map.guard();
+    map.insert(1, 1, &guard);
+    let (old, _) = map.get_versioned(&1, &guard).unwrap();
+    map.remove(&1, &guard);
+    map.insert(1, 1, &guard);
+
+    let (new, _) = map.get_versioned(&1, &guard).unwrap();
+    assert_ne!(old, new);
+    assert!(map.insert_if_version(&1, old, 2, &guard).is_err());
+}
+
+#[test]
+fn optimistic_increments_are_not_lost() {
+    let map = versioned();
+    map.insert(1, 0, &map.guard());
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let guard = map.guard();
+                for _ in 0..1000 {
+                    loop {
+                        let (generation, &value) = map.get_versioned(&1, &guard).unwrap();
+                        if map
+                            .insert_if_version(&1, generation, value + 1, &guard)
+                            .is_ok()
+                        {
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+    });
+    assert_eq!(map.get(&1, &map.guard()), Some(&4000));
+}
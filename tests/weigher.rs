@@ -0,0 +1,52 @@
+use concurrent_hash_table::{map::builder::Builder, ConcurrentHashMap};
+
+#[test]
+fn weighted_size_follows_writes_and_removals() {
+    let map: ConcurrentHashMap<u64, Vec<u8>> = Builder::new()
+        .weigher(1 << 20, |_, value: &Vec<u8>| value.len() as u32)
+        .build();
+    let guard = map.guard();
+    map.insert(1, vec![0; 10], &guard);
+    map.insert(2, vec![0; 20], &guard);
+    assert_eq!(map.weighted_size(), 30);
+
+    map.insert(1, vec![0; 5], &guard);
+    assert_eq!(map.weighted_size(), 25);
+    map.update(&2, |value| vec![0; value.len() * 2], &guard);
+    assert_eq!(map.weighted_size(), 45);
+
+    map.remove(&1, &guard);
+    assert_eq!(map.weighted_size(), 40);
+    map.clear(&guard);
+    assert_eq!(map.weighted_size(), 0);
+}
+
+#[test]
+fn inserts_evict_down_to_max_weight() {
+    let map: ConcurrentHashMap<u64, Vec<u8>> = Builder::new()
+        .weigher(1000, |_, value: &Vec<u8>| value.len() as u32)
+        .build();
+    let guard = map.guard();
+    for key in 0..500 {
+        map.insert(key, vec![0; (key % 7 + 1) as usize * 10], &guard);
+        assert!(map.weighted_size() <= 1000);
+    }
+    let total: u64 = map.values(&guard).map(|value| value.len() as u64).sum();
+    assert_eq!(total, map.weighted_size());
+}
+
+#[test]
+fn weigher_charges_entries_from_the_first_insert() {
+    let map: ConcurrentHashMap<u64, Vec<u8>> = Builder::new()
+        .capacity(16)
+        .weigher(50, |_, value: &Vec<u8>| value.len() as u32)
+        .build();
+    let guard = map.guard();
+    for key in 0..10 {
+        map.insert(key, vec![0; 10], &guard);
+        assert!(map.weighted_size() <= 50);
+    }
+    assert!(map.len() <= 5);
+    let total: u64 = map.values(&guard).map(|value| value.len() as u64).sum();
+    assert_eq!(total, map.weighted_size());
+}
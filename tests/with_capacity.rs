@@ -0,0 +1,24 @@
+use std::collections::hash_map::RandomState;
+
+use concurrent_hash_table::ConcurrentHashMap;
+
+#[test]
+fn with_capacity_holds_the_expected_entries() {
+    let map: ConcurrentHashMap<u64, u64> = ConcurrentHashMap::with_capacity(1000);
+    let guard = map.guard();
+    assert!(map.is_empty());
+    for key in 0..1000 {
+        map.insert(key, key, &guard);
+    }
+    assert_eq!(map.len(), 1000);
+    assert!((0..1000).all(|key| map.get(&key, &guard) == Some(&key)));
+}
+
+#[test]
+fn with_capacity_and_hasher_uses_the_hasher() {
+    let map: ConcurrentHashMap<String, u64, RandomState> =
+        ConcurrentHashMap::with_capacity_and_hasher(0, RandomState::new());
+    let guard = map.guard();
+    map.insert("a".to_string(), 1, &guard);
+    assert_eq!(map.get("a", &guard), Some(&1));
+}